@@ -1272,6 +1272,15 @@ pub fn is_approximately_pattern(&self) -> bool {
     }
 }
 
+/// Whether a `for` loop consumes an `Iterator` or an `AsyncIterator`.
+#[derive(Copy, Clone, PartialEq, Eq, Encodable, Decodable, Debug)]
+pub enum ForLoopKind {
+    /// `for pat in expr { block }`
+    For,
+    /// `for await pat in expr { block }`
+    ForAwait,
+}
+
 /// Limit types of a range (inclusive or exclusive)
 #[derive(Copy, Clone, PartialEq, Encodable, Decodable, Debug)]
 pub enum RangeLimits {
@@ -1352,10 +1361,10 @@ pub enum ExprKind {
     While(P<Expr>, P<Block>, Option<Label>),
     /// A `for` loop, with an optional label.
     ///
-    /// `'label: for pat in expr { block }`
+    /// `'label: for pat in expr { block }` or `'label: for await pat in expr { block }`
     ///
     /// This is desugared to a combination of `loop` and `match` expressions.
-    ForLoop(P<Pat>, P<Expr>, P<Block>, Option<Label>),
+    ForLoop(P<Pat>, P<Expr>, P<Block>, Option<Label>, ForLoopKind),
     /// Conditionless loop (can be exited with `break`, `continue`, or `return`).
     ///
     /// `'label: loop { block }`
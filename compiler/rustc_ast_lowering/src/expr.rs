@@ -20,6 +20,14 @@
 use rustc_span::DUMMY_SP;
 use thin_vec::thin_vec;
 
+/// What an `.await`-style polling loop built by `make_lowered_await` is polling.
+enum FutureKind {
+    /// Polls a `Future` with `Future::poll`.
+    Future,
+    /// Polls a pinned `AsyncIterator` with `AsyncIterator::poll_next`, as used by `for await`.
+    AsyncIterator,
+}
+
 impl<'hir> LoweringContext<'_, 'hir> {
     fn lower_exprs(&mut self, exprs: &[AstP<Expr>]) -> &'hir [hir::Expr<'hir>] {
         self.arena.alloc_from_iter(exprs.iter().map(|x| self.lower_expr_mut(x)))
@@ -306,8 +314,8 @@ pub(super) fn lower_expr_mut(&mut self, e: &Expr) -> hir::Expr<'hir> {
 
                 // Desugar `ExprForLoop`
                 // from: `[opt_ident]: for <pat> in <head> <body>`
-                ExprKind::ForLoop(ref pat, ref head, ref body, opt_label) => {
-                    return self.lower_expr_for(e, pat, head, body, opt_label);
+                ExprKind::ForLoop(ref pat, ref head, ref body, opt_label, loop_kind) => {
+                    return self.lower_expr_for(e, pat, head, body, opt_label, loop_kind);
                 }
                 ExprKind::MacCall(_) => panic!("{:?} shouldn't exist here", e.span),
             };
@@ -717,22 +725,38 @@ pub(super) fn make_async_expr(
     /// ```
     fn lower_expr_await(&mut self, dot_await_span: Span, expr: &Expr) -> hir::ExprKind<'hir> {
         let full_span = expr.span.to(dot_await_span);
+        let expr = self.lower_expr_mut(expr);
+        self.make_lowered_await(dot_await_span, full_span, expr, FutureKind::Future)
+    }
+
+    /// Builds the `.await` polling loop around an already lowered `expr`.
+    ///
+    /// For `FutureKind::Future` this is the desugaring documented on `lower_expr_await`. For
+    /// `FutureKind::AsyncIterator`, `expr` must be a pinned async iterator; it is polled with
+    /// `AsyncIterator::poll_next` instead of `Future::poll`, and is not passed through
+    /// `IntoFuture::into_future` first. This is used to lower `for await` loops.
+    fn make_lowered_await(
+        &mut self,
+        await_kw_span: Span,
+        full_span: Span,
+        expr: hir::Expr<'hir>,
+        await_kind: FutureKind,
+    ) -> hir::ExprKind<'hir> {
         match self.generator_kind {
             Some(hir::GeneratorKind::Async(_)) => {}
             Some(hir::GeneratorKind::Gen) | None => {
                 self.tcx.sess.emit_err(AwaitOnlyInAsyncFnAndBlocks {
-                    dot_await_span,
+                    dot_await_span: await_kw_span,
                     item_span: self.current_item,
                 });
             }
         }
-        let span = self.mark_span_with_reason(DesugaringKind::Await, dot_await_span, None);
+        let span = self.mark_span_with_reason(DesugaringKind::Await, await_kw_span, None);
         let gen_future_span = self.mark_span_with_reason(
             DesugaringKind::Await,
             full_span,
             self.allow_gen_future.clone(),
         );
-        let expr = self.lower_expr_mut(expr);
         let expr_hir_id = expr.hir_id;
 
         // Note that the name of this binding must not be changed to something else because
@@ -771,9 +795,13 @@ fn lower_expr_await(&mut self, dot_await_span: Span, expr: &Expr) -> hir::ExprKi
                 arena_vec![self; task_context],
                 Some(expr_hir_id),
             );
+            let poll_lang_item = match await_kind {
+                FutureKind::Future => hir::LangItem::FuturePoll,
+                FutureKind::AsyncIterator => hir::LangItem::AsyncIteratorPollNext,
+            };
             let call = self.expr_call_lang_item_fn(
                 span,
-                hir::LangItem::FuturePoll,
+                poll_lang_item,
                 arena_vec![self; new_unchecked, get_context],
                 Some(expr_hir_id),
             );
@@ -867,17 +895,23 @@ fn lower_expr_await(&mut self, dot_await_span: Span, expr: &Expr) -> hir::ExprKi
         let awaitee_arm = self.arm(awaitee_pat, loop_expr);
 
         // `match ::std::future::IntoFuture::into_future(<expr>) { ... }`
-        let into_future_span = self.mark_span_with_reason(
-            DesugaringKind::Await,
-            dot_await_span,
-            self.allow_into_future.clone(),
-        );
-        let into_future_expr = self.expr_call_lang_item_fn(
-            into_future_span,
-            hir::LangItem::IntoFutureIntoFuture,
-            arena_vec![self; expr],
-            Some(expr_hir_id),
-        );
+        let into_future_expr = match await_kind {
+            FutureKind::Future => {
+                let into_future_span = self.mark_span_with_reason(
+                    DesugaringKind::Await,
+                    await_kw_span,
+                    self.allow_into_future.clone(),
+                );
+                self.expr_call_lang_item_fn(
+                    into_future_span,
+                    hir::LangItem::IntoFutureIntoFuture,
+                    arena_vec![self; expr],
+                    Some(expr_hir_id),
+                )
+            }
+            // The async iterator has already been pinned by the `for await` desugaring.
+            FutureKind::AsyncIterator => self.arena.alloc(expr),
+        };
 
         // match <into_future_expr> {
         //     mut __awaitee => loop { .. }
@@ -1469,6 +1503,25 @@ fn lower_expr_yield(&mut self, span: Span, opt_expr: Option<&Expr>) -> hir::Expr
     ///     result
     /// }
     /// ```
+    ///
+    /// `[opt_ident]: for await <pat> in <head> <body>` is desugared the same way, except that
+    /// `<head>` must already be an async iterator, which is pinned in place and polled for its
+    /// next element with the `.await` desugaring:
+    /// ```ignore (pseudo-rust)
+    /// {
+    ///     let result = match <head> {
+    ///         mut iter => {
+    ///             [opt_ident]: loop {
+    ///                 match unsafe { Pin::new_unchecked(&mut iter) }.<poll_next until ready> {
+    ///                     None => break,
+    ///                     Some(<pat>) => <body>,
+    ///                 };
+    ///             }
+    ///         }
+    ///     };
+    ///     result
+    /// }
+    /// ```
     fn lower_expr_for(
         &mut self,
         e: &Expr,
@@ -1476,12 +1529,20 @@ fn lower_expr_for(
         head: &Expr,
         body: &Block,
         opt_label: Option<Label>,
+        loop_kind: ForLoopKind,
     ) -> hir::Expr<'hir> {
         let head = self.lower_expr_mut(head);
         let pat = self.lower_pat(pat);
         let for_span =
             self.mark_span_with_reason(DesugaringKind::ForLoop, self.lower_span(e.span), None);
-        let head_span = self.mark_span_with_reason(DesugaringKind::ForLoop, head.span, None);
+        let head_span = match loop_kind {
+            ForLoopKind::For => self.mark_span_with_reason(DesugaringKind::ForLoop, head.span, None),
+            ForLoopKind::ForAwait => self.mark_span_with_reason(
+                DesugaringKind::ForLoop,
+                head.span,
+                self.allow_for_await.clone(),
+            ),
+        };
         let pat_span = self.mark_span_with_reason(DesugaringKind::ForLoop, pat.span, None);
 
         // `None => break`
@@ -1509,12 +1570,31 @@ fn lower_expr_for(
         let match_expr = {
             let iter = self.expr_ident(head_span, iter, iter_pat_nid);
             let ref_mut_iter = self.expr_mut_addr_of(head_span, iter);
-            let next_expr = self.expr_call_lang_item_fn(
-                head_span,
-                hir::LangItem::IteratorNext,
-                arena_vec![self; ref_mut_iter],
-                None,
-            );
+            let next_expr = match loop_kind {
+                ForLoopKind::For => self.expr_call_lang_item_fn(
+                    head_span,
+                    hir::LangItem::IteratorNext,
+                    arena_vec![self; ref_mut_iter],
+                    None,
+                ),
+                ForLoopKind::ForAwait => {
+                    // `iter` is never moved again, so it is sound to pin it in place.
+                    let pinned_iter = self.expr_call_lang_item_fn(
+                        head_span,
+                        hir::LangItem::PinNewUnchecked,
+                        arena_vec![self; ref_mut_iter],
+                        None,
+                    );
+                    let pinned_iter = self.expr_unsafe(pinned_iter);
+                    let kind = self.make_lowered_await(
+                        head_span,
+                        head_span,
+                        pinned_iter,
+                        FutureKind::AsyncIterator,
+                    );
+                    self.arena.alloc(self.expr(head_span, kind, AttrVec::new()))
+                }
+            };
             let arms = arena_vec![self; none_arm, some_arm];
 
             self.expr_match(head_span, next_expr, arms, hir::MatchSource::ForLoopDesugar)
@@ -1537,13 +1617,15 @@ fn lower_expr_for(
         let iter_arm = self.arm(iter_pat, loop_expr);
 
         // `match ::std::iter::IntoIterator::into_iter(<head>) { ... }`
-        let into_iter_expr = {
-            self.expr_call_lang_item_fn(
+        let into_iter_expr = match loop_kind {
+            ForLoopKind::For => self.expr_call_lang_item_fn(
                 head_span,
                 hir::LangItem::IntoIterIntoIter,
                 arena_vec![self; head],
                 None,
-            )
+            ),
+            // `for await` loops take an `AsyncIterator` directly.
+            ForLoopKind::ForAwait => self.arena.alloc(head),
         };
 
         let match_expr = self.arena.alloc(self.expr_match(
@@ -162,6 +162,7 @@ macro_rules! gate_doc { ($($name:ident => $feature:ident)*) => {
                 gate_doc!(
                     cfg => doc_cfg
                     cfg_hide => doc_cfg_hide
+                    example => doc_example
                     masked => doc_masked
                     notable_trait => doc_notable_trait
                 );
@@ -537,6 +538,7 @@ macro_rules! gate_all {
     gate_all!(inline_const_pat, "inline-const in pattern position is experimental");
     gate_all!(associated_const_equality, "associated const equality is incomplete");
     gate_all!(yeet_expr, "`do yeet` expression is experimental");
+    gate_all!(async_for_loop, "`for await` loops are experimental");
 
     // All uses of `gate_all!` below this point were added in #65742,
     // and subsequently disabled (with the non-early gating readded).
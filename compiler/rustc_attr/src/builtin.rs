@@ -5,6 +5,7 @@
 use rustc_ast_pretty::pprust;
 use rustc_feature::{find_gated_cfg, is_builtin_attr_name, Features, GatedCfg};
 use rustc_macros::HashStable_Generic;
+use rustc_session::config::OptLevel;
 use rustc_session::lint::builtin::UNEXPECTED_CFGS;
 use rustc_session::lint::BuiltinLintDiagnostics;
 use rustc_session::parse::{feature_err, ParseSess};
@@ -98,6 +99,9 @@ pub enum OptimizeAttr {
     None,
     Speed,
     Size,
+    /// `#[optimize(level = "...")]`, optimizing the function at the given level regardless of the
+    /// optimization level of the crate.
+    Level(OptLevel),
 }
 
 /// Represents the following attributes:
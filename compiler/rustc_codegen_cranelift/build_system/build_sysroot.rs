@@ -186,7 +186,12 @@ fn build_clif_sysroot_for_triple(
 
     // Build sysroot
     let mut build_cmd = cargo_command("cargo", "build", Some(triple), Path::new("build_sysroot"));
-    let mut rustflags = "-Zforce-unstable-if-unmarked -Cpanic=abort".to_string();
+    let mut rustflags = "-Zforce-unstable-if-unmarked".to_string();
+    if super::config::get_bool("unwind_sysroot") {
+        rustflags.push_str(" -Cpanic=unwind");
+    } else {
+        rustflags.push_str(" -Cpanic=abort");
+    }
     rustflags.push_str(&format!(" -Zcodegen-backend={}", cg_clif_dylib_path.to_str().unwrap()));
     rustflags.push_str(&format!(" --sysroot={}", target_dir.to_str().unwrap()));
     if channel == "release" {
@@ -7,8 +7,9 @@
 use std::env;
 use std::ffi::OsStr;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{self, Command, Stdio};
 
 struct TestCase {
     config: &'static str,
@@ -149,6 +150,69 @@ const fn new(config: &'static str, func: &'static dyn Fn(&TestRunner)) -> Self {
             &runner.host_triple,
         ]);
     }),
+    TestCase::new("jit.hot_reload", &|runner| {
+        // The source file is edited while the program is running, so use a copy.
+        let source = fs::read_to_string("example/hot_reload.rs").unwrap();
+        let source_path = runner.out_dir.join("hot_reload.rs");
+        fs::write(&source_path, &source).unwrap();
+
+        let mut jit_cmd = runner.rustc_command([
+            "-Zunstable-options",
+            "-Cllvm-args=mode=jit-lazy",
+            "-Cllvm-args=hot_reload=true",
+            "-Cprefer-dynamic",
+            source_path.to_str().unwrap(),
+            "--target",
+            &runner.host_triple,
+        ]);
+        jit_cmd.stderr(Stdio::piped());
+        let mut child = jit_cmd.spawn().unwrap();
+        let mut stderr = BufReader::new(child.stderr.take().unwrap()).lines();
+        let mut wait_for = |expected: &str| {
+            for line in &mut stderr {
+                let line = line.unwrap();
+                eprintln!("{}", line);
+                if line.contains(expected) {
+                    return;
+                }
+            }
+            eprintln!("Expected `{}` in the output of the hot reload test", expected);
+            process::exit(1);
+        };
+
+        wait_for("hot_reload: running");
+
+        // Each rejected edit also changes `probe`, which must not be applied either.
+        fs::write(
+            &source_path,
+            source
+                .replace("fn convert(x: u32)", "fn convert(x: u64)")
+                .replace("1 // PROBE", "100 // PROBE"),
+        )
+        .unwrap();
+        wait_for("the signature of `convert` changed");
+        wait_for("hot reload: compilation failed");
+
+        fs::write(
+            &source_path,
+            source
+                .replace("x: u32, // POINT", "x: u32, y: u64, // POINT")
+                .replace("1 // PROBE", "200 // PROBE"),
+        )
+        .unwrap();
+        wait_for("the layout of `Point` changed");
+        wait_for("hot reload: compilation failed");
+
+        fs::write(&source_path, source.replace("1 // PROBE", "2 // PROBE")).unwrap();
+        wait_for("hot reload: reloaded");
+
+        for line in stderr {
+            eprintln!("{}", line.unwrap());
+        }
+        if !child.wait().unwrap().success() {
+            process::exit(1);
+        }
+    }),
     TestCase::new("aot.std_example", &|runner| {
         runner.run_rustc([
             "example/std_example.rs",
@@ -214,6 +278,27 @@ const fn new(config: &'static str, func: &'static dyn Fn(&TestRunner)) -> Self {
         ]);
         runner.run_out_command("mod_bench", []);
     }),
+    TestCase::new("aot.unwinding", &|runner| {
+        // Landing pads are only supported on x86_64 ELF targets.
+        if !config::get_bool("unwind_sysroot")
+            || !runner.target_triple.starts_with("x86_64-")
+            || runner.target_triple.contains("darwin")
+            || runner.target_triple.contains("windows")
+        {
+            eprintln!("[AOT] unwinding (skipped, requires `unwind_sysroot` on x86_64 ELF)");
+            return;
+        }
+
+        runner.run_rustc([
+            "example/unwinding.rs",
+            "--crate-type",
+            "bin",
+            "-Cpanic=unwind",
+            "--target",
+            &runner.target_triple,
+        ]);
+        runner.run_out_command("unwinding", []);
+    }),
 ];
 
 const EXTENDED_SYSROOT_SUITE: &[TestCase] = &[
@@ -0,0 +1,50 @@
+// Checks that changed functions are swapped into the running program by hot reloading and that
+// changes which can't be applied leave it untouched.
+//
+// This is run by the `jit.hot_reload` test, which edits the lines marked below while the program
+// is running. `probe` returns 1 until the final edit changes it to 2. The edits before that also
+// change `probe`, but are rejected, so it must never return anything else.
+
+use std::time::{Duration, Instant};
+
+#[inline(never)]
+fn probe() -> u32 {
+    1 // PROBE
+}
+
+#[inline(never)]
+fn convert(x: u32) -> u32 {
+    x as u32
+}
+
+#[derive(Default)]
+struct Point {
+    x: u32, // POINT
+}
+
+#[inline(never)]
+fn point_x(point: &Point) -> u32 {
+    point.x
+}
+
+fn main() {
+    let point = Point::default();
+    assert_eq!(point_x(&point), 0);
+    assert_eq!(convert(probe().into()), 1);
+
+    eprintln!("hot_reload: running");
+
+    let deadline = Instant::now() + Duration::from_secs(300);
+    loop {
+        match probe() {
+            1 => {}
+            2 => break,
+            value => panic!("a rejected change was applied: `probe` returned {}", value),
+        }
+        assert!(Instant::now() < deadline, "timed out waiting for the change to be applied");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    assert_eq!(point_x(&point), 0);
+    assert_eq!(convert(probe().into()), 2);
+}
@@ -0,0 +1,100 @@
+// Checks that destructors run while unwinding and that panics can be caught.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic;
+
+thread_local! {
+    static DROPPED: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+struct Noisy(&'static str);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        DROPPED.with(|dropped| dropped.borrow_mut().push(self.0));
+    }
+}
+
+fn take_dropped() -> Vec<&'static str> {
+    DROPPED.with(|dropped| std::mem::take(&mut *dropped.borrow_mut()))
+}
+
+#[inline(never)]
+fn panic_with_locals() {
+    let _a = Noisy("a");
+    let _b = Noisy("b");
+    panic_in_callee();
+    unreachable!();
+}
+
+#[inline(never)]
+fn panic_in_callee() {
+    let _c = Noisy("c");
+    panic!("boom");
+}
+
+#[inline(never)]
+fn consume(_arg: Noisy, _vec: Vec<Noisy>) {
+    panic!("consumed");
+}
+
+#[inline(never)]
+fn out_of_bounds_index() -> usize {
+    3
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {}));
+
+    // Locals of all frames between the panic and `catch_unwind` are dropped innermost first.
+    let payload = panic::catch_unwind(|| panic_with_locals()).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+    assert_eq!(take_dropped(), ["c", "b", "a"]);
+
+    // Arguments moved into the panicking function are dropped by it.
+    let payload =
+        panic::catch_unwind(|| consume(Noisy("arg"), vec![Noisy("v1"), Noisy("v2")])).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"consumed"));
+    assert_eq!(take_dropped(), ["v1", "v2", "arg"]);
+
+    // Failed bounds checks unwind too.
+    let result = panic::catch_unwind(|| {
+        let _d = Noisy("d");
+        let array = [1u8, 2];
+        array[out_of_bounds_index()]
+    });
+    assert!(result.is_err());
+    assert_eq!(take_dropped(), ["d"]);
+
+    // Trait objects are dropped through their vtable.
+    let result = panic::catch_unwind(|| {
+        let _e: Box<dyn Any> = Box::new(Noisy("e"));
+        panic!();
+    });
+    assert!(result.is_err());
+    assert_eq!(take_dropped(), ["e"]);
+
+    // Nested `catch_unwind` and rethrowing with `resume_unwind`.
+    let payload = panic::catch_unwind(|| {
+        let _f = Noisy("f");
+        let inner = panic::catch_unwind(|| {
+            let _g = Noisy("g");
+            panic!("inner");
+        });
+        assert!(inner.is_err());
+        assert_eq!(take_dropped(), ["g"]);
+        panic::resume_unwind(Box::new(42i32));
+    })
+    .unwrap_err();
+    assert_eq!(payload.downcast_ref::<i32>(), Some(&42));
+    assert_eq!(take_dropped(), ["f"]);
+
+    // Nothing is dropped twice when no panic happens.
+    let result = panic::catch_unwind(|| {
+        let _h = Noisy("h");
+        1
+    });
+    assert_eq!(result.ok(), Some(1));
+    assert_eq!(take_dropped(), ["h"]);
+}
@@ -116,4 +116,11 @@ rm src/test/ui/stdio-is-blocking.rs # really slow with unoptimized libstd
 
 echo "[TEST] rustc test suite"
 RUST_TEST_NOCAPTURE=1 COMPILETEST_FORCE_STAGE0=1 ./x.py test --stage 0 src/test/{codegen-units,run-make,run-pass-valgrind,ui,incremental}
+
+# Most debuginfo tests depend on pretty printers or on variable locations in optimized code, which
+# aren't supported yet. Only run the tests checking that variables end up in the right scope.
+echo "[TEST] rustc debuginfo tests for lexical scopes"
+RUST_TEST_NOCAPTURE=1 COMPILETEST_FORCE_STAGE0=1 ./x.py test --stage 0 src/test/debuginfo \
+    --test-args lexical-scope --test-args shadowed-variable --test-args shadowed-argument \
+    --test-args name-shadowing-and-scope-nesting
 popd
@@ -11,6 +11,7 @@
 use rustc_target::spec::abi::Abi;
 
 use cranelift_codegen::ir::{AbiParam, SigRef};
+use smallvec::SmallVec;
 
 use self::pass_mode::*;
 use crate::prelude::*;
@@ -328,6 +329,43 @@ fn codegen_call_argument_operand<'tcx>(
     }
 }
 
+#[derive(Copy, Clone)]
+pub(crate) enum CallTarget {
+    Direct(FuncRef),
+    Indirect(SigRef, Value),
+}
+
+/// Emits a call. If `unwind` is a cleanup block and unwinding is supported, the call continues at
+/// it when the callee unwinds. Returns the return values of the call.
+pub(crate) fn codegen_call_with_unwind(
+    fx: &mut FunctionCx<'_, '_, '_>,
+    callee: CallTarget,
+    args: &[Value],
+    unwind: Option<BasicBlock>,
+) -> SmallVec<[Value; 2]> {
+    match unwind {
+        Some(cleanup) if fx.cx.can_catch_unwind => {
+            let (sig, callee) = match callee {
+                CallTarget::Direct(func_ref) => {
+                    let sig = fx.bcx.func.dfg.ext_funcs[func_ref].signature;
+                    (sig, fx.bcx.ins().func_addr(fx.pointer_type, func_ref))
+                }
+                CallTarget::Indirect(sig, func_ptr) => (sig, func_ptr),
+            };
+            crate::catch_unwind::codegen_invoke(fx, sig, callee, args, cleanup)
+        }
+        _ => {
+            let call_inst = match callee {
+                CallTarget::Direct(func_ref) => fx.bcx.ins().call(func_ref, args),
+                CallTarget::Indirect(sig, func_ptr) => {
+                    fx.bcx.ins().call_indirect(sig, func_ptr, args)
+                }
+            };
+            fx.bcx.inst_results(call_inst).iter().copied().collect()
+        }
+    }
+}
+
 pub(crate) fn codegen_terminator_call<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     source_info: mir::SourceInfo,
@@ -335,6 +373,7 @@ pub(crate) fn codegen_terminator_call<'tcx>(
     args: &[Operand<'tcx>],
     destination: Place<'tcx>,
     target: Option<BasicBlock>,
+    cleanup: Option<BasicBlock>,
 ) {
     let fn_ty = fx.monomorphize(func.ty(fx.mir, fx.tcx));
     let fn_sig =
@@ -445,11 +484,6 @@ pub(crate) fn codegen_terminator_call<'tcx>(
     let args = args;
     assert_eq!(fn_abi.args.len(), args.len());
 
-    enum CallTarget {
-        Direct(FuncRef),
-        Indirect(SigRef, Value),
-    }
-
     let (func_ref, first_arg_override) = match instance {
         // Trait object call
         Some(Instance { def: InstanceDef::Virtual(_, idx), .. }) => {
@@ -504,13 +538,6 @@ enum CallTarget {
             )
             .collect::<Vec<Value>>();
 
-        let call_inst = match func_ref {
-            CallTarget::Direct(func_ref) => fx.bcx.ins().call(func_ref, &call_args),
-            CallTarget::Indirect(sig, func_ptr) => {
-                fx.bcx.ins().call_indirect(sig, func_ptr, &call_args)
-            }
-        };
-
         // FIXME find a cleaner way to support varargs
         if fn_sig.c_variadic {
             if !matches!(fn_sig.abi, Abi::C { .. }) {
@@ -519,10 +546,13 @@ enum CallTarget {
                     &format!("Variadic call for non-C abi {:?}", fn_sig.abi),
                 );
             }
-            let sig_ref = fx.bcx.func.dfg.call_signature(call_inst).unwrap();
+            let sig_ref = match func_ref {
+                CallTarget::Direct(func_ref) => fx.bcx.func.dfg.ext_funcs[func_ref].signature,
+                CallTarget::Indirect(sig_ref, _) => sig_ref,
+            };
             let abi_params = call_args
-                .into_iter()
-                .map(|arg| {
+                .iter()
+                .map(|&arg| {
                     let ty = fx.bcx.func.dfg.value_type(arg);
                     if !ty.is_int() {
                         // FIXME set %al to upperbound on float args once floats are supported
@@ -537,7 +567,7 @@ enum CallTarget {
             fx.bcx.func.dfg.signatures[sig_ref].params = abi_params;
         }
 
-        call_inst
+        codegen_call_with_unwind(fx, func_ref, &call_args, cleanup)
     });
 
     if let Some(dest) = target {
@@ -552,6 +582,7 @@ pub(crate) fn codegen_drop<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     source_info: mir::SourceInfo,
     drop_place: CPlace<'tcx>,
+    unwind: Option<BasicBlock>,
 ) {
     let ty = drop_place.layout().ty;
     let drop_instance = Instance::resolve_drop_in_place(fx.tcx, ty).polymorphize(fx.tcx);
@@ -588,7 +619,7 @@ pub(crate) fn codegen_drop<'tcx>(
 
                 let sig = clif_sig_from_fn_abi(fx.tcx, fx.target_config.default_call_conv, &fn_abi);
                 let sig = fx.bcx.import_signature(sig);
-                fx.bcx.ins().call_indirect(sig, drop_fn, &[ptr]);
+                codegen_call_with_unwind(fx, CallTarget::Indirect(sig, drop_fn), &[ptr], unwind);
             }
             ty::Dynamic(_, _, ty::DynStar) => {
                 // IN THIS ARM, WE HAVE:
@@ -625,7 +656,7 @@ pub(crate) fn codegen_drop<'tcx>(
 
                 let sig = clif_sig_from_fn_abi(fx.tcx, fx.target_config.default_call_conv, &fn_abi);
                 let sig = fx.bcx.import_signature(sig);
-                fx.bcx.ins().call_indirect(sig, drop_fn, &[data]);
+                codegen_call_with_unwind(fx, CallTarget::Indirect(sig, drop_fn), &[data], unwind);
             }
             _ => {
                 assert!(!matches!(drop_instance.def, InstanceDef::Virtual(_, _)));
@@ -653,7 +684,7 @@ pub(crate) fn codegen_drop<'tcx>(
                 }
 
                 let func_ref = fx.get_function_ref(drop_instance);
-                fx.bcx.ins().call(func_ref, &call_args);
+                codegen_call_with_unwind(fx, CallTarget::Direct(func_ref), &call_args, unwind);
             }
         }
     }
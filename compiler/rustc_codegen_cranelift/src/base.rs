@@ -8,8 +8,9 @@
 
 use cranelift_codegen::ir::UserFuncName;
 
+use crate::abi::CallTarget;
 use crate::constant::ConstantCx;
-use crate::debuginfo::FunctionDebugContext;
+use crate::debuginfo::{FunctionDebugContext, TypeDebugContext};
 use crate::prelude::*;
 use crate::pretty_clif::CommentWriter;
 
@@ -25,6 +26,7 @@ pub(crate) struct CodegenedFunction {
 pub(crate) fn codegen_and_compile_fn<'tcx>(
     tcx: TyCtxt<'tcx>,
     cx: &mut crate::CodegenCx,
+    type_dbg: &mut TypeDebugContext<'tcx>,
     cached_context: &mut Context,
     module: &mut dyn Module,
     instance: Instance<'tcx>,
@@ -33,7 +35,7 @@ pub(crate) fn codegen_and_compile_fn<'tcx>(
         crate::PrintOnPanic(|| format!("{:?} {}", instance, tcx.symbol_name(instance).name));
 
     let cached_func = std::mem::replace(&mut cached_context.func, Function::new());
-    let codegened_func = codegen_fn(tcx, cx, cached_func, module, instance);
+    let codegened_func = codegen_fn(tcx, cx, type_dbg, cached_func, module, instance);
 
     compile_fn(cx, cached_context, module, codegened_func);
 }
@@ -41,6 +43,7 @@ pub(crate) fn codegen_and_compile_fn<'tcx>(
 pub(crate) fn codegen_fn<'tcx>(
     tcx: TyCtxt<'tcx>,
     cx: &mut crate::CodegenCx,
+    type_dbg: &mut TypeDebugContext<'tcx>,
     cached_func: Function,
     module: &mut dyn Module,
     instance: Instance<'tcx>,
@@ -109,11 +112,16 @@ pub(crate) fn codegen_fn<'tcx>(
 
         clif_comments,
         last_source_file: None,
+        unwind_exception: None,
         next_ssa_var: 0,
     };
 
     tcx.sess.time("codegen clif ir", || codegen_fn_body(&mut fx, start_block));
 
+    if fx.func_debug_cx.is_some() {
+        crate::debuginfo::define_variables(&mut fx, type_dbg);
+    }
+
     // Recover all necessary data from fx, before accessing func will prevent future access to it.
     let symbol_name = fx.symbol_name;
     let clif_comments = fx.clif_comments;
@@ -222,6 +230,7 @@ pub(crate) fn compile_fn(
         if let Some(debug_context) = debug_context {
             codegened_func.func_debug_cx.unwrap().finalize(
                 debug_context,
+                isa,
                 codegened_func.func_id,
                 context,
             );
@@ -278,11 +287,14 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
         fx.bcx.switch_to_block(block);
 
         if bb_data.is_cleanup {
-            // Unwinding after panicking is not supported
-            continue;
+            if !fx.cx.can_catch_unwind {
+                // Without landing pads cleanup blocks are never run while unwinding.
+                continue;
+            }
 
-            // FIXME Once unwinding is supported and Cranelift supports marking blocks as cold, do
-            // so for cleanup blocks.
+            // Cleanup blocks are only reached through the landing pads of invokes. See
+            // `catch_unwind.rs`.
+            fx.bcx.set_cold_block(block);
         }
 
         fx.bcx.ins().nop();
@@ -328,7 +340,7 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
             TerminatorKind::Return => {
                 crate::abi::codegen_return(fx);
             }
-            TerminatorKind::Assert { cond, expected, msg, target, cleanup: _ } => {
+            TerminatorKind::Assert { cond, expected, msg, target, cleanup } => {
                 if !fx.tcx.sess.overflow_checks() {
                     if let mir::AssertKind::OverflowNeg(_) = *msg {
                         let target = fx.get_block(*target);
@@ -362,12 +374,13 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
                             fx,
                             rustc_hir::LangItem::PanicBoundsCheck,
                             &[index, len, location],
+                            *cleanup,
                             source_info.span,
                         );
                     }
                     _ => {
                         let msg_str = msg.description();
-                        codegen_panic(fx, msg_str, source_info);
+                        codegen_panic_with_unwind(fx, msg_str, source_info, *cleanup);
                     }
                 }
             }
@@ -426,7 +439,7 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
                 destination,
                 target,
                 fn_span,
-                cleanup: _,
+                cleanup,
                 from_hir_call: _,
             } => {
                 fx.tcx.sess.time("codegen call", || {
@@ -437,6 +450,7 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
                         args,
                         *destination,
                         *target,
+                        *cleanup,
                     )
                 });
             }
@@ -464,8 +478,15 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
                     *destination,
                 );
             }
-            TerminatorKind::Resume | TerminatorKind::Abort => {
-                // FIXME implement unwinding
+            TerminatorKind::Resume => {
+                if fx.cx.can_catch_unwind {
+                    crate::catch_unwind::codegen_resume(fx);
+                } else {
+                    // Cleanup blocks are skipped, so `Resume` is never reached.
+                    fx.bcx.ins().trap(TrapCode::UnreachableCodeReached);
+                }
+            }
+            TerminatorKind::Abort => {
                 fx.bcx.ins().trap(TrapCode::UnreachableCodeReached);
             }
             TerminatorKind::Unreachable => {
@@ -478,9 +499,9 @@ fn codegen_fn_body(fx: &mut FunctionCx<'_, '_, '_>, start_block: Block) {
             | TerminatorKind::GeneratorDrop => {
                 bug!("shouldn't exist at codegen {:?}", bb_data.terminator());
             }
-            TerminatorKind::Drop { place, target, unwind: _ } => {
+            TerminatorKind::Drop { place, target, unwind } => {
                 let drop_place = codegen_place(fx, *place);
-                crate::abi::codegen_drop(fx, source_info, drop_place);
+                crate::abi::codegen_drop(fx, source_info, drop_place, *unwind);
 
                 let target_block = fx.get_block(*target);
                 fx.bcx.ins().jump(target_block, &[]);
@@ -928,6 +949,15 @@ pub(crate) fn codegen_panic<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     msg_str: &str,
     source_info: mir::SourceInfo,
+) {
+    codegen_panic_with_unwind(fx, msg_str, source_info, None);
+}
+
+fn codegen_panic_with_unwind<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    msg_str: &str,
+    source_info: mir::SourceInfo,
+    unwind: Option<BasicBlock>,
 ) {
     let location = fx.get_caller_location(source_info).load_scalar(fx);
 
@@ -935,13 +965,14 @@ pub(crate) fn codegen_panic<'tcx>(
     let msg_len = fx.bcx.ins().iconst(fx.pointer_type, i64::try_from(msg_str.len()).unwrap());
     let args = [msg_ptr, msg_len, location];
 
-    codegen_panic_inner(fx, rustc_hir::LangItem::Panic, &args, source_info.span);
+    codegen_panic_inner(fx, rustc_hir::LangItem::Panic, &args, unwind, source_info.span);
 }
 
 pub(crate) fn codegen_panic_inner<'tcx>(
     fx: &mut FunctionCx<'_, '_, 'tcx>,
     lang_item: rustc_hir::LangItem,
     args: &[Value],
+    unwind: Option<BasicBlock>,
     span: Span,
 ) {
     let def_id = fx
@@ -953,16 +984,17 @@ pub(crate) fn codegen_panic_inner<'tcx>(
     let instance = Instance::mono(fx.tcx, def_id).polymorphize(fx.tcx);
     let symbol_name = fx.tcx.symbol_name(instance).name;
 
-    fx.lib_call(
-        &*symbol_name,
-        vec![
-            AbiParam::new(fx.pointer_type),
-            AbiParam::new(fx.pointer_type),
-            AbiParam::new(fx.pointer_type),
-        ],
-        vec![],
-        args,
-    );
+    let sig = Signature {
+        params: vec![AbiParam::new(fx.pointer_type); 3],
+        returns: vec![],
+        call_conv: fx.target_config.default_call_conv,
+    };
+    let func_id = fx.module.declare_function(symbol_name, Linkage::Import, &sig).unwrap();
+    let func_ref = fx.module.declare_func_in_func(func_id, &mut fx.bcx.func);
+    if fx.clif_comments.enabled() {
+        fx.add_comment(func_ref, format!("{:?}", symbol_name));
+    }
+    crate::abi::codegen_call_with_unwind(fx, CallTarget::Direct(func_ref), args, unwind);
 
     fx.bcx.ins().trap(TrapCode::UnreachableCodeReached);
 }
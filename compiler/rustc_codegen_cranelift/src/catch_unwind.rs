@@ -0,0 +1,370 @@
+//! Landing pads and catching of unwinding panics.
+//!
+//! Cranelift can't express landing pads, so calls which need one go through shims written in
+//! assembly. The shims have their own `.eh_frame` entries and LSDAs, which make
+//! `rust_eh_personality` jump to the landing pad of the shim when a panic unwinds out of the
+//! called function.
+//!
+//! * The closure passed to the `try` intrinsic is called through `__cg_clif_try`, whose landing pad
+//!   passes the exception to the catch function of the intrinsic.
+//! * Calls with an unwind target are made through `__cg_clif_invoke`, whose landing pad returns
+//!   the exception to the caller. The shim can only call functions taking a single pointer, so
+//!   the callee and its arguments are stored in a stack slot and a thunk generated for the
+//!   signature of the callee loads them, makes the actual call and stores the return values. When
+//!   an exception is returned, the caller continues at the cleanup block, which ends in a call to
+//!   `_Unwind_Resume` once all cleanup is done.
+//!
+//! Functions compiled by cg_clif are unwound through using the `.eh_frame` entries emitted by
+//! [`UnwindContext`](crate::debuginfo::UnwindContext).
+
+use cranelift_codegen::ir::SigRef;
+use rustc_target::spec::PanicStrategy;
+use smallvec::SmallVec;
+
+use crate::prelude::*;
+use crate::{BackendConfig, CodegenMode};
+
+const TRY_SHIM_NAME: &str = "__cg_clif_try";
+const INVOKE_SHIM_NAME: &str = "__cg_clif_invoke";
+
+/// The unwinding shims for x86_64 ELF targets.
+///
+/// `__cg_clif_try(f, data, catch_fn) -> i32` calls `f(data)` and returns 0. If a panic unwinds out
+/// of `f`, it calls `catch_fn(data, exception)` and returns 1 instead.
+///
+/// `__cg_clif_invoke(f, data) -> *mut _Unwind_Exception` calls `f(data)` and returns null. Its
+/// landing pad is a cleanup rather than a catch, so the unwinder only enters it once a handler
+/// further up the stack has been found. It then returns the exception to the caller, which runs
+/// the cleanup blocks and passes the exception to `_Unwind_Resume`.
+///
+/// The shims are emitted into every codegen unit using them, so they and the indirection to the
+/// personality function are put in COMDAT groups.
+const UNWIND_SHIMS_X86_64_ELF: &str = r#"
+.att_syntax
+.section .text.__cg_clif_try,"axG",@progbits,__cg_clif_try,comdat
+.weak __cg_clif_try
+.hidden __cg_clif_try
+.type __cg_clif_try,@function
+.p2align 4
+__cg_clif_try:
+    .cfi_startproc
+    .cfi_personality 0x9b, DW.ref.rust_eh_personality
+    .cfi_lsda 0x1b, .Lcg_clif_try_lsda
+    push %rbx
+    .cfi_def_cfa_offset 16
+    push %r14
+    .cfi_def_cfa_offset 24
+    push %rax
+    .cfi_def_cfa_offset 32
+    .cfi_offset %r14, -24
+    .cfi_offset %rbx, -16
+    mov %rsi, %rbx
+    mov %rdx, %r14
+    mov %rdi, %rax
+    mov %rsi, %rdi
+.Lcg_clif_try_call_begin:
+    call *%rax
+.Lcg_clif_try_call_end:
+    xor %eax, %eax
+.Lcg_clif_try_return:
+    .cfi_remember_state
+    add $8, %rsp
+    .cfi_def_cfa_offset 24
+    pop %r14
+    .cfi_def_cfa_offset 16
+    pop %rbx
+    .cfi_def_cfa_offset 8
+    ret
+    .cfi_restore_state
+.Lcg_clif_try_landing_pad:
+    mov %rbx, %rdi
+    mov %rax, %rsi
+    call *%r14
+    mov $1, %eax
+    jmp .Lcg_clif_try_return
+    .cfi_endproc
+.size __cg_clif_try, .-__cg_clif_try
+
+.section .gcc_except_table.__cg_clif_try,"aG",@progbits,__cg_clif_try,comdat
+.p2align 2
+.Lcg_clif_try_lsda:
+    .byte 0xff # @LPStart encoding: omitted, landing pads are relative to the function start
+    .byte 0xff # @TType encoding: omitted, rust_eh_personality doesn't look at types
+    .byte 0x01 # call site encoding: uleb128
+    .uleb128 .Lcg_clif_try_call_sites_end - .Lcg_clif_try_call_sites
+.Lcg_clif_try_call_sites:
+    .uleb128 .Lcg_clif_try_call_begin - __cg_clif_try
+    .uleb128 .Lcg_clif_try_call_end - .Lcg_clif_try_call_begin
+    .uleb128 .Lcg_clif_try_landing_pad - __cg_clif_try
+    .uleb128 1 # first action record, which catches the exception
+.Lcg_clif_try_call_sites_end:
+    .byte 1
+    .byte 0
+
+.section .text.__cg_clif_invoke,"axG",@progbits,__cg_clif_invoke,comdat
+.weak __cg_clif_invoke
+.hidden __cg_clif_invoke
+.type __cg_clif_invoke,@function
+.p2align 4
+__cg_clif_invoke:
+    .cfi_startproc
+    .cfi_personality 0x9b, DW.ref.rust_eh_personality
+    .cfi_lsda 0x1b, .Lcg_clif_invoke_lsda
+    push %rax
+    .cfi_def_cfa_offset 16
+    mov %rdi, %rax
+    mov %rsi, %rdi
+.Lcg_clif_invoke_call_begin:
+    call *%rax
+.Lcg_clif_invoke_call_end:
+    xor %eax, %eax
+.Lcg_clif_invoke_return:
+    .cfi_remember_state
+    pop %rcx
+    .cfi_def_cfa_offset 8
+    ret
+    .cfi_restore_state
+.Lcg_clif_invoke_landing_pad:
+    # The personality function passes the exception in %rax.
+    jmp .Lcg_clif_invoke_return
+    .cfi_endproc
+.size __cg_clif_invoke, .-__cg_clif_invoke
+
+.section .gcc_except_table.__cg_clif_invoke,"aG",@progbits,__cg_clif_invoke,comdat
+.p2align 2
+.Lcg_clif_invoke_lsda:
+    .byte 0xff # @LPStart encoding: omitted, landing pads are relative to the function start
+    .byte 0xff # @TType encoding: omitted, rust_eh_personality doesn't look at types
+    .byte 0x01 # call site encoding: uleb128
+    .uleb128 .Lcg_clif_invoke_call_sites_end - .Lcg_clif_invoke_call_sites
+.Lcg_clif_invoke_call_sites:
+    .uleb128 .Lcg_clif_invoke_call_begin - __cg_clif_invoke
+    .uleb128 .Lcg_clif_invoke_call_end - .Lcg_clif_invoke_call_begin
+    .uleb128 .Lcg_clif_invoke_landing_pad - __cg_clif_invoke
+    .uleb128 0 # no action, the landing pad is a cleanup
+.Lcg_clif_invoke_call_sites_end:
+
+.section .data.DW.ref.rust_eh_personality,"awG",@progbits,DW.ref.rust_eh_personality,comdat
+.p2align 3
+.weak DW.ref.rust_eh_personality
+.hidden DW.ref.rust_eh_personality
+.type DW.ref.rust_eh_personality,@object
+.size DW.ref.rust_eh_personality, 8
+DW.ref.rust_eh_personality:
+    .quad rust_eh_personality
+
+.text
+"#;
+
+/// Returns whether panics can be caught and cleanup blocks run in the current codegen unit.
+///
+/// The shims are assembled by the external assembler, so this is never the case in JIT mode.
+pub(crate) fn can_catch_unwind(tcx: TyCtxt<'_>, backend_config: &BackendConfig) -> bool {
+    let target = &tcx.sess.target;
+    tcx.sess.panic_strategy() == PanicStrategy::Unwind
+        && matches!(backend_config.codegen_mode, CodegenMode::Aot)
+        && cfg!(feature = "inline_asm")
+        && target.arch == "x86_64"
+        && !target.is_like_osx
+        && !target.is_like_windows
+}
+
+/// Calls `f(data)`, catching any panic unwinding out of it with `catch_fn`. Returns 1 if a panic
+/// was caught and 0 otherwise.
+pub(crate) fn codegen_try(
+    fx: &mut FunctionCx<'_, '_, '_>,
+    f: Value,
+    data: Value,
+    catch_fn: Value,
+) -> Value {
+    emit_unwind_shims(fx);
+
+    let try_shim = fx
+        .module
+        .declare_function(
+            TRY_SHIM_NAME,
+            Linkage::Import,
+            &Signature {
+                call_conv: CallConv::SystemV,
+                params: vec![AbiParam::new(fx.pointer_type); 3],
+                returns: vec![AbiParam::new(types::I32)],
+            },
+        )
+        .unwrap();
+    let try_shim = fx.module.declare_func_in_func(try_shim, &mut fx.bcx.func);
+    if fx.clif_comments.enabled() {
+        fx.add_comment(try_shim, TRY_SHIM_NAME);
+    }
+
+    let call = fx.bcx.ins().call(try_shim, &[f, data, catch_fn]);
+    fx.bcx.inst_results(call)[0]
+}
+
+/// Calls `callee` with the signature `sig`, continuing at the cleanup block `cleanup` if it
+/// unwinds. Returns the return values of the call.
+pub(crate) fn codegen_invoke(
+    fx: &mut FunctionCx<'_, '_, '_>,
+    sig: SigRef,
+    callee: Value,
+    args: &[Value],
+    cleanup: BasicBlock,
+) -> SmallVec<[Value; 2]> {
+    emit_unwind_shims(fx);
+
+    let sig = fx.bcx.func.dfg.signatures[sig].clone();
+    let data_layout = InvokeDataLayout::new(fx.pointer_type, &sig);
+    let thunk = invoke_thunk(fx, sig.clone());
+
+    let data = Pointer::stack_slot(fx.bcx.create_sized_stack_slot(StackSlotData {
+        kind: StackSlotKind::ExplicitSlot,
+        size: data_layout.size,
+    }));
+    data.store(fx, callee, MemFlags::trusted());
+    for (&arg, &offset) in args.iter().zip(&data_layout.arg_offsets) {
+        data.offset_i64(fx, i64::from(offset)).store(fx, arg, MemFlags::trusted());
+    }
+
+    let invoke_shim = fx
+        .module
+        .declare_function(
+            INVOKE_SHIM_NAME,
+            Linkage::Import,
+            &Signature {
+                call_conv: CallConv::SystemV,
+                params: vec![AbiParam::new(fx.pointer_type); 2],
+                returns: vec![AbiParam::new(fx.pointer_type)],
+            },
+        )
+        .unwrap();
+    let invoke_shim = fx.module.declare_func_in_func(invoke_shim, &mut fx.bcx.func);
+    if fx.clif_comments.enabled() {
+        fx.add_comment(invoke_shim, INVOKE_SHIM_NAME);
+    }
+    let thunk = fx.module.declare_func_in_func(thunk, &mut fx.bcx.func);
+    let thunk = fx.bcx.ins().func_addr(fx.pointer_type, thunk);
+    let data_addr = data.get_addr(fx);
+    let call = fx.bcx.ins().call(invoke_shim, &[thunk, data_addr]);
+    let exception = fx.bcx.inst_results(call)[0];
+
+    let exception_var = unwind_exception_var(fx);
+    fx.bcx.def_var(exception_var, exception);
+    let cleanup_block = fx.get_block(cleanup);
+    let return_block = fx.bcx.create_block();
+    fx.bcx.ins().brnz(exception, cleanup_block, &[]);
+    fx.bcx.ins().jump(return_block, &[]);
+    fx.bcx.switch_to_block(return_block);
+
+    sig.returns
+        .iter()
+        .zip(&data_layout.ret_offsets)
+        .map(|(ret, &offset)| {
+            data.offset_i64(fx, i64::from(offset)).load(fx, ret.value_type, MemFlags::trusted())
+        })
+        .collect()
+}
+
+/// Continues unwinding with the exception caught by the landing pad of the last invoke.
+pub(crate) fn codegen_resume(fx: &mut FunctionCx<'_, '_, '_>) {
+    let exception_var = unwind_exception_var(fx);
+    let exception = fx.bcx.use_var(exception_var);
+    fx.lib_call("_Unwind_Resume", vec![AbiParam::new(fx.pointer_type)], vec![], &[exception]);
+    fx.bcx.ins().trap(TrapCode::UnreachableCodeReached);
+}
+
+fn emit_unwind_shims(fx: &mut FunctionCx<'_, '_, '_>) {
+    assert!(fx.cx.can_catch_unwind);
+    if !fx.cx.unwind_shims_emitted {
+        fx.cx.global_asm.push_str(UNWIND_SHIMS_X86_64_ELF);
+        fx.cx.unwind_shims_emitted = true;
+    }
+}
+
+/// Returns the variable holding the exception while the cleanup blocks run.
+fn unwind_exception_var(fx: &mut FunctionCx<'_, '_, '_>) -> Variable {
+    if let Some(var) = fx.unwind_exception {
+        return var;
+    }
+    let var = Variable::with_u32(fx.next_ssa_var);
+    fx.next_ssa_var += 1;
+    fx.bcx.declare_var(var, fx.pointer_type);
+    fx.unwind_exception = Some(var);
+    var
+}
+
+/// The layout of the data passed to an invoke thunk. The callee is stored at offset 0, followed by
+/// the arguments and the return values, each naturally aligned.
+struct InvokeDataLayout {
+    arg_offsets: Vec<i32>,
+    ret_offsets: Vec<i32>,
+    size: u32,
+}
+
+impl InvokeDataLayout {
+    fn new(pointer_type: Type, sig: &Signature) -> Self {
+        let mut size = pointer_type.bytes();
+        let mut offset_of = |param: &AbiParam| {
+            let ty_size = param.value_type.bytes();
+            let offset = (size + ty_size - 1) / ty_size * ty_size;
+            size = offset + ty_size;
+            i32::try_from(offset).unwrap()
+        };
+        let arg_offsets = sig.params.iter().map(&mut offset_of).collect();
+        let ret_offsets = sig.returns.iter().map(&mut offset_of).collect();
+        // FIXME Don't force the size to a multiple of 16 bytes once Cranelift gets a way to
+        // specify stack slot alignment.
+        InvokeDataLayout { arg_offsets, ret_offsets, size: (size + 15) / 16 * 16 }
+    }
+}
+
+/// Returns the thunk calling functions with the signature `sig` for `__cg_clif_invoke`, defining it
+/// if this is the first invoke of such a function in the codegen unit.
+fn invoke_thunk(fx: &mut FunctionCx<'_, '_, '_>, sig: Signature) -> FuncId {
+    if let Some(&func_id) = fx.cx.invoke_thunks.get(&sig) {
+        return func_id;
+    }
+
+    let pointer_type = fx.pointer_type;
+    let data_layout = InvokeDataLayout::new(pointer_type, &sig);
+    let thunk_sig = Signature {
+        call_conv: CallConv::SystemV,
+        params: vec![AbiParam::new(pointer_type)],
+        returns: vec![],
+    };
+    let func_id = fx.module.declare_anonymous_function(&thunk_sig).unwrap();
+
+    let mut ctx = Context::new();
+    ctx.func.signature = thunk_sig;
+    {
+        let mut func_ctx = FunctionBuilderContext::new();
+        let mut bcx = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+
+        let block = bcx.create_block();
+        bcx.switch_to_block(block);
+        let data = bcx.append_block_param(block, pointer_type);
+
+        let callee = bcx.ins().load(pointer_type, MemFlags::trusted(), data, 0);
+        let args = sig
+            .params
+            .iter()
+            .zip(&data_layout.arg_offsets)
+            .map(|(param, &offset)| {
+                bcx.ins().load(param.value_type, MemFlags::trusted(), data, offset)
+            })
+            .collect::<Vec<Value>>();
+        let callee_sig = bcx.import_signature(sig.clone());
+        let call_inst = bcx.ins().call_indirect(callee_sig, callee, &args);
+        let results = bcx.inst_results(call_inst).to_vec(); // Clone to prevent borrow error
+        for (result, &offset) in results.into_iter().zip(&data_layout.ret_offsets) {
+            bcx.ins().store(MemFlags::trusted(), result, data, offset);
+        }
+
+        bcx.ins().return_(&[]);
+        bcx.seal_all_blocks();
+        bcx.finalize();
+    }
+    fx.module.define_function(func_id, &mut ctx).unwrap();
+    fx.cx.unwind_context.add_function(func_id, &ctx, fx.module.isa());
+
+    fx.cx.invoke_thunks.insert(sig, func_id);
+    func_id
+}
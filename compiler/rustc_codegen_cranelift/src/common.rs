@@ -1,3 +1,4 @@
+use cranelift_codegen::ir::ValueLabel;
 use cranelift_codegen::isa::TargetFrontendConfig;
 use gimli::write::FileId;
 
@@ -263,7 +264,11 @@ pub(crate) struct FunctionCx<'m, 'clif, 'tcx: 'm> {
     /// For optimization purposes only
     pub(crate) last_source_file: Option<(Lrc<SourceFile>, FileId)>,
 
-    /// This should only be accessed by `CPlace::new_var`.
+    /// The variable holding the exception while cleanup blocks run. Declared by the first invoke.
+    pub(crate) unwind_exception: Option<Variable>,
+
+    /// This should only be accessed by `CPlace::new_var`, `FunctionCx::new_value_label` and
+    /// `catch_unwind::unwind_exception_var`.
     pub(crate) next_ssa_var: u32,
 }
 
@@ -334,6 +339,13 @@ pub(crate) fn clif_pair_type(&self, ty: Ty<'tcx>) -> Option<(Type, Type)> {
         clif_pair_type_from_ty(self.tcx, ty)
     }
 
+    /// Returns a value label which isn't used for the values of any variable.
+    pub(crate) fn new_value_label(&mut self) -> ValueLabel {
+        let label = ValueLabel::from_u32(self.next_ssa_var);
+        self.next_ssa_var += 1;
+        label
+    }
+
     pub(crate) fn get_block(&self, bb: BasicBlock) -> Block {
         *self.block_map.get(bb).unwrap()
     }
@@ -366,8 +378,12 @@ pub(crate) fn set_debug_loc(&mut self, source_info: mir::SourceInfo) {
                 debug_context.add_source_file(&file)
             };
 
-            let source_loc =
-                self.func_debug_cx.as_mut().unwrap().add_dbg_loc(file_id, line, column);
+            let source_loc = self.func_debug_cx.as_mut().unwrap().add_dbg_loc(
+                file_id,
+                line,
+                column,
+                source_info.scope,
+            );
             self.bcx.set_srcloc(source_loc);
         }
     }
@@ -42,6 +42,12 @@ pub struct BackendConfig {
     /// Defaults to the value of `CG_CLIF_JIT_ARGS`.
     pub jit_args: Vec<String>,
 
+    /// Recompile functions whose source changed while the program runs in lazy JIT mode.
+    ///
+    /// Defaults to true when the `CG_CLIF_JIT_HOT_RELOAD` env var is set to 1 or false otherwise.
+    /// Can be set using `-Cllvm-args=hot_reload=...`.
+    pub jit_hot_reload: bool,
+
     /// Display the time it took to perform codegen for a crate.
     ///
     /// Defaults to true when the `CG_CLIF_DISPLAY_CG_TIME` env var is set to 1 or false otherwise.
@@ -73,6 +79,7 @@ fn default() -> Self {
                 let args = std::env::var("CG_CLIF_JIT_ARGS").unwrap_or_else(|_| String::new());
                 args.split(' ').map(|arg| arg.to_string()).collect()
             },
+            jit_hot_reload: bool_env_var("CG_CLIF_JIT_HOT_RELOAD"),
             display_cg_time: bool_env_var("CG_CLIF_DISPLAY_CG_TIME"),
             enable_verifier: cfg!(debug_assertions) || bool_env_var("CG_CLIF_ENABLE_VERIFIER"),
             disable_incr_cache: bool_env_var("CG_CLIF_DISABLE_INCR_CACHE"),
@@ -92,6 +99,7 @@ fn parse_bool(name: &str, value: &str) -> Result<bool, String> {
             if let Some((name, value)) = opt.split_once('=') {
                 match name {
                     "mode" => config.codegen_mode = value.parse()?,
+                    "hot_reload" => config.jit_hot_reload = parse_bool(name, value)?,
                     "display_cg_time" => config.display_cg_time = parse_bool(name, value)?,
                     "enable_verifier" => config.enable_verifier = parse_bool(name, value)?,
                     "disable_incr_cache" => config.disable_incr_cache = parse_bool(name, value)?,
@@ -3,7 +3,9 @@
 mod emit;
 mod line_info;
 mod object;
+mod types;
 mod unwind;
+mod variables;
 
 use crate::prelude::*;
 
@@ -16,9 +18,14 @@
 };
 use gimli::{Encoding, Format, LineEncoding, RunTimeEndian};
 use indexmap::IndexSet;
+use rustc_index::vec::IndexVec;
 
 pub(crate) use emit::{DebugReloc, DebugRelocName};
+pub(crate) use types::TypeDebugContext;
 pub(crate) use unwind::UnwindContext;
+pub(crate) use variables::define_variables;
+
+use variables::VariableLocation;
 
 pub(crate) struct DebugContext {
     endian: RunTimeEndian,
@@ -30,7 +37,10 @@ pub(crate) struct DebugContext {
 pub(crate) struct FunctionDebugContext {
     entry_id: UnitEntryId,
     function_source_loc: (FileId, u64, u64),
-    source_loc_set: indexmap::IndexSet<(FileId, u64, u64)>,
+    source_loc_set: indexmap::IndexSet<(FileId, u64, u64, SourceScope)>,
+    scope_parents: IndexVec<SourceScope, Option<SourceScope>>,
+    lexical_blocks: IndexVec<SourceScope, Option<UnitEntryId>>,
+    variables: Vec<(UnitEntryId, VariableLocation)>,
 }
 
 impl DebugContext {
@@ -131,6 +141,9 @@ pub(crate) fn define_function(
             entry_id,
             function_source_loc: (file_id, line, column),
             source_loc_set: IndexSet::new(),
+            scope_parents: IndexVec::new(),
+            lexical_blocks: IndexVec::new(),
+            variables: Vec::new(),
         }
     }
 }
@@ -139,12 +152,15 @@ impl FunctionDebugContext {
     pub(crate) fn finalize(
         mut self,
         debug_context: &mut DebugContext,
+        isa: &dyn TargetIsa,
         func_id: FuncId,
         context: &Context,
     ) {
         let symbol = func_id.as_u32() as usize;
 
         let end = self.create_debug_lines(debug_context, symbol, context);
+        self.add_variable_locations(debug_context, isa, symbol, context);
+        self.add_lexical_block_ranges(debug_context, symbol, context);
 
         debug_context.unit_range_list.0.push(Range::StartLength {
             begin: Address::Symbol { symbol, addend: 0 },
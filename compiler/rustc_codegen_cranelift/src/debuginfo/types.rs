@@ -0,0 +1,316 @@
+//! Type debuginfo generation (`DW_TAG_*_type`)
+
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_target::abi::{FieldsShape, TagEncoding, Variants};
+
+use gimli::write::{AttributeValue, UnitEntryId};
+
+use crate::debuginfo::DebugContext;
+use crate::prelude::*;
+
+/// The type debuginfo entries of a codegen unit.
+///
+/// This is kept separate from [`DebugContext`] as it can't outlive the `TyCtxt`.
+#[derive(Default)]
+pub(crate) struct TypeDebugContext<'tcx> {
+    type_map: FxHashMap<Ty<'tcx>, UnitEntryId>,
+}
+
+impl DebugContext {
+    pub(crate) fn debug_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> UnitEntryId {
+        if let Some(&type_id) = type_dbg.type_map.get(&ty) {
+            return type_id;
+        }
+
+        let layout = RevealAllLayoutCx(tcx).layout_of(ty);
+
+        let type_id = match ty.kind() {
+            ty::Bool => self.basic_type(layout, gimli::DW_ATE_boolean),
+            ty::Char => self.basic_type(layout, gimli::DW_ATE_UTF),
+            ty::Int(_) => self.basic_type(layout, gimli::DW_ATE_signed),
+            ty::Uint(_) => self.basic_type(layout, gimli::DW_ATE_unsigned),
+            ty::Float(_) => self.basic_type(layout, gimli::DW_ATE_float),
+            ty::Ref(_, pointee_ty, _) | ty::RawPtr(TypeAndMut { ty: pointee_ty, mutbl: _ })
+                if !has_ptr_meta(tcx, *pointee_ty) =>
+            {
+                let type_id = self.new_type_entry(layout, gimli::DW_TAG_pointer_type);
+                // Insert the type before recursing to support recursive types.
+                type_dbg.type_map.insert(ty, type_id);
+                let pointee_id = self.debug_type(tcx, type_dbg, *pointee_ty);
+                self.dwarf
+                    .unit
+                    .get_mut(type_id)
+                    .set(gimli::DW_AT_type, AttributeValue::UnitRef(pointee_id));
+                type_id
+            }
+            ty::Adt(adt_def, _) if adt_def.is_box() && !has_ptr_meta(tcx, ty.boxed_ty()) => {
+                let type_id = self.new_type_entry(layout, gimli::DW_TAG_pointer_type);
+                type_dbg.type_map.insert(ty, type_id);
+                let pointee_id = self.debug_type(tcx, type_dbg, ty.boxed_ty());
+                self.dwarf
+                    .unit
+                    .get_mut(type_id)
+                    .set(gimli::DW_AT_type, AttributeValue::UnitRef(pointee_id));
+                type_id
+            }
+            // Function pointers are described as `void *`.
+            ty::FnPtr(_) => self.new_type_entry(layout, gimli::DW_TAG_pointer_type),
+            ty::Array(elem_ty, len) => {
+                let len = len.eval_usize(tcx, ParamEnv::reveal_all());
+                self.array_type(tcx, type_dbg, ty, layout, *elem_ty, Some(len))
+            }
+            ty::Slice(elem_ty) => self.array_type(tcx, type_dbg, ty, layout, *elem_ty, None),
+            ty::Str => self.array_type(tcx, type_dbg, ty, layout, tcx.types.u8, None),
+            ty::Adt(adt_def, _) if adt_def.is_union() => {
+                self.struct_type(tcx, type_dbg, ty, layout, gimli::DW_TAG_union_type)
+            }
+            ty::Adt(_, _) | ty::Generator(_, _, _) if layout.is_sized() => match layout.variants {
+                Variants::Single { .. } => {
+                    self.struct_type(tcx, type_dbg, ty, layout, gimli::DW_TAG_structure_type)
+                }
+                Variants::Multiple { .. } => self.enum_type(tcx, type_dbg, ty, layout),
+            },
+            // Fat pointers are described as a struct with a `data_ptr` field and a `length` or
+            // `vtable` field, like the LLVM backend does.
+            ty::Ref(..) | ty::RawPtr(..) | ty::Adt(..) | ty::Tuple(_) | ty::Closure(..) => {
+                self.struct_type(tcx, type_dbg, ty, layout, gimli::DW_TAG_structure_type)
+            }
+            // FIXME describe trait objects and their vtables
+            _ => self.new_type_entry(layout, gimli::DW_TAG_structure_type),
+        };
+
+        let name = with_no_trimmed_paths!(ty.to_string());
+        let name_id = self.dwarf.strings.add(name);
+        self.dwarf.unit.get_mut(type_id).set(gimli::DW_AT_name, AttributeValue::StringRef(name_id));
+
+        type_dbg.type_map.insert(ty, type_id);
+
+        type_id
+    }
+
+    fn new_type_entry(&mut self, layout: TyAndLayout<'_>, tag: gimli::DwTag) -> UnitEntryId {
+        let type_id = self.dwarf.unit.add(self.dwarf.unit.root(), tag);
+        if layout.is_sized() {
+            self.dwarf
+                .unit
+                .get_mut(type_id)
+                .set(gimli::DW_AT_byte_size, AttributeValue::Udata(layout.size.bytes()));
+        }
+        type_id
+    }
+
+    fn basic_type(&mut self, layout: TyAndLayout<'_>, encoding: gimli::DwAte) -> UnitEntryId {
+        let type_id = self.new_type_entry(layout, gimli::DW_TAG_base_type);
+        self.dwarf
+            .unit
+            .get_mut(type_id)
+            .set(gimli::DW_AT_encoding, AttributeValue::Encoding(encoding));
+        type_id
+    }
+
+    fn array_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        ty: Ty<'tcx>,
+        layout: TyAndLayout<'tcx>,
+        elem_ty: Ty<'tcx>,
+        len: Option<u64>,
+    ) -> UnitEntryId {
+        let type_id = self.new_type_entry(layout, gimli::DW_TAG_array_type);
+        type_dbg.type_map.insert(ty, type_id);
+
+        let elem_id = self.debug_type(tcx, type_dbg, elem_ty);
+        self.dwarf.unit.get_mut(type_id).set(gimli::DW_AT_type, AttributeValue::UnitRef(elem_id));
+
+        let subrange_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_subrange_type);
+        if let Some(len) = len {
+            self.dwarf
+                .unit
+                .get_mut(subrange_id)
+                .set(gimli::DW_AT_count, AttributeValue::Udata(len));
+        }
+
+        type_id
+    }
+
+    fn struct_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        ty: Ty<'tcx>,
+        layout: TyAndLayout<'tcx>,
+        tag: gimli::DwTag,
+    ) -> UnitEntryId {
+        let type_id = self.new_type_entry(layout, tag);
+        type_dbg.type_map.insert(ty, type_id);
+        self.add_fields(tcx, type_dbg, type_id, layout);
+        type_id
+    }
+
+    /// Describes an enum with multiple variants as a struct containing a `DW_TAG_variant_part`.
+    fn enum_type<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        ty: Ty<'tcx>,
+        layout: TyAndLayout<'tcx>,
+    ) -> UnitEntryId {
+        let (tag_encoding, tag_field, variants) = match &layout.variants {
+            Variants::Multiple { tag: _, tag_encoding, tag_field, variants } => {
+                (tag_encoding, *tag_field, variants)
+            }
+            Variants::Single { .. } => unreachable!(),
+        };
+
+        let type_id = self.new_type_entry(layout, gimli::DW_TAG_structure_type);
+        type_dbg.type_map.insert(ty, type_id);
+
+        let variant_part_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_variant_part);
+
+        let tag_layout = layout.field(&RevealAllLayoutCx(tcx), tag_field);
+        let tag_member_id = self.add_member(
+            tcx,
+            type_dbg,
+            variant_part_id,
+            "<<discriminant>>".to_owned(),
+            tag_layout.ty,
+            layout.fields.offset(tag_field),
+        );
+        self.dwarf
+            .unit
+            .get_mut(tag_member_id)
+            .set(gimli::DW_AT_artificial, AttributeValue::Flag(true));
+        self.dwarf
+            .unit
+            .get_mut(variant_part_id)
+            .set(gimli::DW_AT_discr, AttributeValue::UnitRef(tag_member_id));
+
+        for variant_index in variants.indices() {
+            let discr_value = match tag_encoding {
+                TagEncoding::Direct => {
+                    let discr = ty.discriminant_for_variant(tcx, variant_index).unwrap().val;
+                    Some(tag_layout.size.truncate(discr))
+                }
+                TagEncoding::Niche { untagged_variant, niche_variants, niche_start } => {
+                    if variant_index == *untagged_variant {
+                        // The untagged variant is the default variant of the variant part.
+                        None
+                    } else if niche_variants.contains(&variant_index) {
+                        let niche_value = variant_index.as_u32() - niche_variants.start().as_u32();
+                        let niche_value = u128::from(niche_value).wrapping_add(*niche_start);
+                        Some(tag_layout.size.truncate(niche_value))
+                    } else {
+                        // Uninhabited variants which aren't represented by any tag value.
+                        continue;
+                    }
+                }
+            };
+
+            let variant_id = self.dwarf.unit.add(variant_part_id, gimli::DW_TAG_variant);
+            if let Some(discr_value) = discr_value {
+                self.dwarf.unit.get_mut(variant_id).set(
+                    gimli::DW_AT_discr_value,
+                    AttributeValue::Udata(u64::try_from(discr_value).unwrap()),
+                );
+            }
+
+            let variant_layout = layout.for_variant(&RevealAllLayoutCx(tcx), variant_index);
+            let variant_name = match ty.kind() {
+                ty::Adt(adt_def, _) => adt_def.variant(variant_index).name.to_string(),
+                _ => format!("{}", variant_index.as_usize()),
+            };
+
+            // The fields of the variant are described by a struct nested in the enum type.
+            let variant_type_id = self.dwarf.unit.add(type_id, gimli::DW_TAG_structure_type);
+            let variant_type = self.dwarf.unit.get_mut(variant_type_id);
+            variant_type.set(
+                gimli::DW_AT_name,
+                AttributeValue::StringRef(self.dwarf.strings.add(variant_name.clone())),
+            );
+            variant_type.set(gimli::DW_AT_byte_size, AttributeValue::Udata(layout.size.bytes()));
+            self.add_fields(tcx, type_dbg, variant_type_id, variant_layout);
+
+            let member_id = self.dwarf.unit.add(variant_id, gimli::DW_TAG_member);
+            let member = self.dwarf.unit.get_mut(member_id);
+            member.set(
+                gimli::DW_AT_name,
+                AttributeValue::StringRef(self.dwarf.strings.add(variant_name)),
+            );
+            member.set(gimli::DW_AT_type, AttributeValue::UnitRef(variant_type_id));
+            member.set(gimli::DW_AT_data_member_location, AttributeValue::Udata(0));
+        }
+
+        type_id
+    }
+
+    fn add_fields<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        type_id: UnitEntryId,
+        layout: TyAndLayout<'tcx>,
+    ) {
+        if let FieldsShape::Primitive = layout.fields {
+            return;
+        }
+
+        for field_idx in 0..layout.fields.count() {
+            let field_layout = layout.field(&RevealAllLayoutCx(tcx), field_idx);
+            self.add_member(
+                tcx,
+                type_dbg,
+                type_id,
+                field_name(tcx, layout, field_idx),
+                field_layout.ty,
+                layout.fields.offset(field_idx),
+            );
+        }
+    }
+
+    fn add_member<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        type_dbg: &mut TypeDebugContext<'tcx>,
+        parent: UnitEntryId,
+        name: String,
+        ty: Ty<'tcx>,
+        offset: Size,
+    ) -> UnitEntryId {
+        let member_type_id = self.debug_type(tcx, type_dbg, ty);
+
+        let member_id = self.dwarf.unit.add(parent, gimli::DW_TAG_member);
+        let member = self.dwarf.unit.get_mut(member_id);
+        member.set(gimli::DW_AT_name, AttributeValue::StringRef(self.dwarf.strings.add(name)));
+        member.set(gimli::DW_AT_type, AttributeValue::UnitRef(member_type_id));
+        member.set(gimli::DW_AT_data_member_location, AttributeValue::Udata(offset.bytes()));
+        member_id
+    }
+}
+
+fn field_name<'tcx>(tcx: TyCtxt<'tcx>, layout: TyAndLayout<'tcx>, field_idx: usize) -> String {
+    match layout.ty.kind() {
+        ty::Adt(adt_def, _) => {
+            let variant_index = match layout.variants {
+                Variants::Single { index } => index,
+                Variants::Multiple { .. } => unreachable!(),
+            };
+            adt_def.variant(variant_index).fields[field_idx].name.to_string()
+        }
+        ty::Ref(_, pointee_ty, _) | ty::RawPtr(TypeAndMut { ty: pointee_ty, mutbl: _ }) => {
+            if field_idx == 0 {
+                return "data_ptr".to_owned();
+            }
+            match tcx.struct_tail_erasing_lifetimes(*pointee_ty, ParamEnv::reveal_all()).kind() {
+                ty::Dynamic(..) => "vtable".to_owned(),
+                _ => "length".to_owned(),
+            }
+        }
+        _ => format!("__{}", field_idx),
+    }
+}
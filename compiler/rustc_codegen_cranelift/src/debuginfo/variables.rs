@@ -0,0 +1,426 @@
+//! Variable debuginfo generation (`DW_TAG_variable`, `DW_TAG_formal_parameter` and the
+//! `DW_TAG_lexical_block`s containing them)
+
+use cranelift_codegen::entity::EntityRef;
+use cranelift_codegen::ir::{LabelValueLoc, ValueLabel};
+use cranelift_codegen::isa::TargetIsa;
+use cranelift_codegen::{MachSrcLoc, ValueLabelsRanges, ValueLocRange};
+
+use gimli::write::{
+    Address, AttributeValue, Expression, Location, LocationList, Range, RangeList, UnitEntryId,
+};
+use rustc_index::vec::IndexVec;
+use target_lexicon::Architecture;
+
+use crate::debuginfo::types::TypeDebugContext;
+use crate::debuginfo::{DebugContext, FunctionDebugContext};
+use crate::pointer::PointerBase;
+use crate::prelude::*;
+
+/// The storage of a variable. This is translated to a DWARF location description once the
+/// function has been compiled.
+pub(super) enum VariableLocation {
+    /// The variable is stored in SSA values, each of which holds a piece of the given size.
+    Values(Vec<(ValueLabel, Size)>),
+    /// The variable is stored at an offset into a stack slot.
+    StackSlot(StackSlot, i64),
+    /// The variable is stored at an offset from the address held by an SSA value.
+    Indirect(ValueLabel, i64),
+}
+
+/// Defines the user variables of the function. This has to be called after the function body
+/// has been codegened, as the storage of all locals is only known at that point.
+pub(crate) fn define_variables<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    type_dbg: &mut TypeDebugContext<'tcx>,
+) {
+    let mir = fx.mir;
+
+    let func_debug_cx = fx.func_debug_cx.as_mut().unwrap();
+    func_debug_cx.scope_parents =
+        mir.source_scopes.iter().map(|scope_data| scope_data.parent_scope).collect();
+    func_debug_cx.lexical_blocks = IndexVec::from_elem(None, &mir.source_scopes);
+
+    for var_debug_info in &mir.var_debug_info {
+        let place = match var_debug_info.value {
+            VarDebugInfoContents::Place(place) => place,
+            // FIXME describe constants and variables split into multiple fragments
+            VarDebugInfoContents::Const(_) | VarDebugInfoContents::Composite { .. } => continue,
+        };
+
+        let ty = fx.monomorphize(place.ty(mir, fx.tcx).ty);
+        let location = variable_location(fx, place);
+
+        let is_param = var_debug_info.source_info.scope == OUTERMOST_SOURCE_SCOPE
+            && place.projection.is_empty()
+            && mir.args_iter().any(|arg| arg == place.local);
+        let (file, line, column) =
+            DebugContext::get_span_loc(fx.tcx, mir.span, var_debug_info.source_info.span);
+
+        let debug_context = fx.cx.debug_context.as_mut().unwrap();
+        let func_debug_cx = fx.func_debug_cx.as_mut().unwrap();
+        let file_id = debug_context.add_source_file(&file);
+        let type_id = debug_context.debug_type(fx.tcx, type_dbg, ty);
+
+        let scope_id = func_debug_cx.scope_entry(debug_context, var_debug_info.source_info.scope);
+        let var_id = debug_context.dwarf.unit.add(
+            scope_id,
+            if is_param { gimli::DW_TAG_formal_parameter } else { gimli::DW_TAG_variable },
+        );
+        let var_entry = debug_context.dwarf.unit.get_mut(var_id);
+        let name_id = debug_context.dwarf.strings.add(var_debug_info.name.as_str());
+        var_entry.set(gimli::DW_AT_name, AttributeValue::StringRef(name_id));
+        var_entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(type_id));
+        var_entry.set(gimli::DW_AT_decl_file, AttributeValue::FileIndex(Some(file_id)));
+        var_entry.set(gimli::DW_AT_decl_line, AttributeValue::Udata(line));
+        var_entry.set(gimli::DW_AT_decl_column, AttributeValue::Udata(column));
+
+        if let Some(location) = location {
+            func_debug_cx.variables.push((var_id, location));
+        }
+    }
+}
+
+fn variable_location<'tcx>(
+    fx: &mut FunctionCx<'_, '_, 'tcx>,
+    place: Place<'tcx>,
+) -> Option<VariableLocation> {
+    // The local map is empty when the function body consists of just a trap.
+    let cplace = *fx.local_map.get(place.local)?;
+    let mut layout = cplace.layout();
+
+    let mut location = match *cplace.inner() {
+        CPlaceInner::Var(_local, var) => {
+            VariableLocation::Values(vec![(ValueLabel::new(var.index()), layout.size)])
+        }
+        CPlaceInner::VarPair(_local, var1, var2) => {
+            let (a_scalar, b_scalar) = match layout.abi {
+                Abi::ScalarPair(a, b) => (a, b),
+                _ => unreachable!("{:?}", layout),
+            };
+            let b_offset = a_scalar.size(fx).align_to(b_scalar.align(fx).abi);
+            VariableLocation::Values(vec![
+                (ValueLabel::new(var1.index()), b_offset),
+                (ValueLabel::new(var2.index()), layout.size - b_offset),
+            ])
+        }
+        // FIXME describe SIMD vector lanes and unsized locals
+        CPlaceInner::VarLane(_, _, _) | CPlaceInner::Addr(_, Some(_)) => return None,
+        CPlaceInner::Addr(ptr, None) => {
+            let (base, offset) = ptr.debug_base_and_offset();
+            let offset = i64::from(offset);
+            match base {
+                PointerBase::Stack(stack_slot) => VariableLocation::StackSlot(stack_slot, offset),
+                PointerBase::Addr(addr) => {
+                    let label = fx.new_value_label();
+                    fx.bcx.set_val_label(addr, label);
+                    VariableLocation::Indirect(label, offset)
+                }
+                PointerBase::Dangling(_) => return None,
+            }
+        }
+    };
+
+    for elem in place.projection {
+        location = match (elem, location) {
+            (ProjectionElem::Deref, VariableLocation::Values(pieces))
+                if pieces.len() == 1 && matches!(layout.abi, Abi::Scalar(_)) =>
+            {
+                layout = fx.layout_of(layout.ty.builtin_deref(true).unwrap().ty);
+                VariableLocation::Indirect(pieces[0].0, 0)
+            }
+            (ProjectionElem::Field(field, _), VariableLocation::StackSlot(stack_slot, offset)) => {
+                let field_offset = layout.fields.offset(field.index()).bytes();
+                layout = layout.field(fx, field.index());
+                VariableLocation::StackSlot(
+                    stack_slot,
+                    offset + i64::try_from(field_offset).unwrap(),
+                )
+            }
+            (ProjectionElem::Field(field, _), VariableLocation::Indirect(label, offset)) => {
+                let field_offset = layout.fields.offset(field.index()).bytes();
+                layout = layout.field(fx, field.index());
+                VariableLocation::Indirect(label, offset + i64::try_from(field_offset).unwrap())
+            }
+            (ProjectionElem::Downcast(_, variant_index), location) => {
+                layout = layout.for_variant(fx, variant_index);
+                location
+            }
+            // FIXME support the remaining projections
+            _ => return None,
+        };
+    }
+
+    Some(location)
+}
+
+impl FunctionDebugContext {
+    /// Returns the entry the variables of `scope` belong in. This is the function itself for the
+    /// outermost scope and a `DW_TAG_lexical_block` nested inside the entries of the parent scopes
+    /// otherwise. Only scopes containing variables get a lexical block.
+    fn scope_entry(&mut self, debug_context: &mut DebugContext, scope: SourceScope) -> UnitEntryId {
+        if let Some(entry_id) = self.lexical_blocks[scope] {
+            return entry_id;
+        }
+        let parent_id = match self.scope_parents[scope] {
+            Some(parent) => self.scope_entry(debug_context, parent),
+            None => return self.entry_id,
+        };
+
+        // FIXME use DW_TAG_inlined_subroutine for the scopes of inlined functions
+        let entry_id = debug_context.dwarf.unit.add(parent_id, gimli::DW_TAG_lexical_block);
+        self.lexical_blocks[scope] = Some(entry_id);
+        entry_id
+    }
+
+    /// Sets the code ranges of the lexical blocks. A lexical block covers all code whose source
+    /// location belongs to its scope or to any scope nested inside it.
+    pub(super) fn add_lexical_block_ranges(
+        &self,
+        debug_context: &mut DebugContext,
+        symbol: usize,
+        context: &Context,
+    ) {
+        if self.lexical_blocks.iter().all(Option::is_none) {
+            return;
+        }
+
+        let mcr = context.compiled_code().unwrap();
+
+        let mut block_ranges: IndexVec<SourceScope, Vec<(u32, u32)>> =
+            IndexVec::from_elem(vec![], &self.lexical_blocks);
+        for &MachSrcLoc { start, end, loc } in mcr.buffer.get_srclocs_sorted() {
+            let mut scope = if loc.is_default() {
+                OUTERMOST_SOURCE_SCOPE
+            } else {
+                self.source_loc_set.get_index(loc.bits() as usize).unwrap().3
+            };
+            loop {
+                if self.lexical_blocks[scope].is_some() {
+                    let ranges = &mut block_ranges[scope];
+                    match ranges.last_mut() {
+                        Some((_, last_end)) if *last_end == start => *last_end = end,
+                        _ => ranges.push((start, end)),
+                    }
+                }
+                match self.scope_parents[scope] {
+                    Some(parent) => scope = parent,
+                    None => break,
+                }
+            }
+        }
+
+        for (scope, entry_id) in self.lexical_blocks.iter_enumerated() {
+            let entry_id = match *entry_id {
+                Some(entry_id) => entry_id,
+                None => continue,
+            };
+            match block_ranges[scope][..] {
+                // All code of the scope has been optimized away.
+                [] => {}
+                [(start, end)] => {
+                    let entry = debug_context.dwarf.unit.get_mut(entry_id);
+                    entry.set(
+                        gimli::DW_AT_low_pc,
+                        AttributeValue::Address(Address::Symbol {
+                            symbol,
+                            addend: i64::from(start),
+                        }),
+                    );
+                    entry.set(gimli::DW_AT_high_pc, AttributeValue::Udata(u64::from(end - start)));
+                }
+                ref ranges => {
+                    let range_list = RangeList(
+                        ranges
+                            .iter()
+                            .map(|&(start, end)| Range::StartEnd {
+                                begin: Address::Symbol { symbol, addend: i64::from(start) },
+                                end: Address::Symbol { symbol, addend: i64::from(end) },
+                            })
+                            .collect(),
+                    );
+                    let range_list_id = debug_context.dwarf.unit.ranges.add(range_list);
+                    debug_context
+                        .dwarf
+                        .unit
+                        .get_mut(entry_id)
+                        .set(gimli::DW_AT_ranges, AttributeValue::RangeListRef(range_list_id));
+                }
+            }
+        }
+    }
+
+    pub(super) fn add_variable_locations(
+        &self,
+        debug_context: &mut DebugContext,
+        isa: &dyn TargetIsa,
+        symbol: usize,
+        context: &Context,
+    ) {
+        let mcr = context.compiled_code().unwrap();
+
+        for (var_id, location) in &self.variables {
+            let location = match *location {
+                VariableLocation::Values(ref pieces) => {
+                    let loc_list =
+                        values_location_list(isa, symbol, &mcr.value_labels_ranges, pieces);
+                    if loc_list.0.is_empty() {
+                        continue;
+                    }
+                    AttributeValue::LocationListRef(
+                        debug_context.dwarf.unit.locations.add(loc_list),
+                    )
+                }
+                VariableLocation::StackSlot(stack_slot, offset) => {
+                    let sp = match stack_pointer_register(isa) {
+                        Some(sp) => sp,
+                        None => continue,
+                    };
+                    // Stack slot offsets are relative to the stack pointer after the prologue.
+                    let stack_slot_offset = i64::from(mcr.sized_stackslot_offsets[stack_slot]);
+                    let mut expr = Expression::new();
+                    expr.op_breg(sp, stack_slot_offset + offset);
+                    AttributeValue::Exprloc(expr)
+                }
+                VariableLocation::Indirect(label, offset) => {
+                    let ranges = mcr.value_labels_ranges.get(&label).map_or(&[][..], |r| &r[..]);
+                    let loc_list = LocationList(
+                        ranges
+                            .iter()
+                            .filter_map(|range| {
+                                let mut expr = Expression::new();
+                                if !push_address_loc(isa, &mut expr, range.loc, offset) {
+                                    return None;
+                                }
+                                Some(location_for_range(symbol, range.start, range.end, expr))
+                            })
+                            .collect(),
+                    );
+                    if loc_list.0.is_empty() {
+                        continue;
+                    }
+                    AttributeValue::LocationListRef(
+                        debug_context.dwarf.unit.locations.add(loc_list),
+                    )
+                }
+            };
+
+            debug_context.dwarf.unit.get_mut(*var_id).set(gimli::DW_AT_location, location);
+        }
+    }
+}
+
+/// Builds the location list for a variable stored in one or more SSA values. The function is
+/// split at every point where the location of any of the values changes.
+fn values_location_list(
+    isa: &dyn TargetIsa,
+    symbol: usize,
+    value_labels_ranges: &ValueLabelsRanges,
+    pieces: &[(ValueLabel, Size)],
+) -> LocationList {
+    let piece_ranges = pieces
+        .iter()
+        .map(|(label, _)| value_labels_ranges.get(label).map_or(&[][..], |r| &r[..]))
+        .collect::<Vec<&[ValueLocRange]>>();
+
+    let mut boundaries = piece_ranges
+        .iter()
+        .flat_map(|ranges| ranges.iter().flat_map(|range| [range.start, range.end]))
+        .collect::<Vec<u32>>();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut locations = vec![];
+    for window in boundaries.windows(2) {
+        let (start, end) = (window[0], window[1]);
+
+        let mut expr = Expression::new();
+        let mut any_available = false;
+        for (&(_, size), ranges) in pieces.iter().zip(&piece_ranges) {
+            if let Some(range) =
+                ranges.iter().find(|range| range.start <= start && end <= range.end)
+            {
+                any_available |= push_value_loc(isa, &mut expr, range.loc);
+            }
+            // A piece without a location before it is optimized out.
+            if pieces.len() > 1 {
+                expr.op_piece(size.bytes());
+            }
+        }
+
+        if any_available {
+            locations.push(location_for_range(symbol, start, end, expr));
+        }
+    }
+
+    LocationList(locations)
+}
+
+fn location_for_range(symbol: usize, start: u32, end: u32, expr: Expression) -> Location {
+    Location::StartEnd {
+        begin: Address::Symbol { symbol, addend: i64::from(start) },
+        end: Address::Symbol { symbol, addend: i64::from(end) },
+        data: expr,
+    }
+}
+
+/// Pushes the location of a value. Returns `false` if the location can't be described.
+fn push_value_loc(isa: &dyn TargetIsa, expr: &mut Expression, loc: LabelValueLoc) -> bool {
+    match loc {
+        LabelValueLoc::Reg(reg) => match isa.map_regalloc_reg_to_dwarf(reg) {
+            Ok(dwarf_reg) => {
+                expr.op_reg(gimli::Register(dwarf_reg));
+                true
+            }
+            Err(_) => false,
+        },
+        LabelValueLoc::SPOffset(sp_offset) => match stack_pointer_register(isa) {
+            Some(sp) => {
+                expr.op_breg(sp, sp_offset);
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+/// Pushes the location `offset` bytes past the address stored in a value. Returns `false` if the
+/// location can't be described.
+fn push_address_loc(
+    isa: &dyn TargetIsa,
+    expr: &mut Expression,
+    loc: LabelValueLoc,
+    offset: i64,
+) -> bool {
+    match loc {
+        LabelValueLoc::Reg(reg) => match isa.map_regalloc_reg_to_dwarf(reg) {
+            Ok(dwarf_reg) => {
+                expr.op_breg(gimli::Register(dwarf_reg), offset);
+                true
+            }
+            Err(_) => false,
+        },
+        LabelValueLoc::SPOffset(sp_offset) => match stack_pointer_register(isa) {
+            Some(sp) => {
+                expr.op_breg(sp, sp_offset);
+                expr.op_deref();
+                if offset != 0 {
+                    expr.op_consts(offset);
+                    expr.op(gimli::DW_OP_plus);
+                }
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+fn stack_pointer_register(isa: &dyn TargetIsa) -> Option<gimli::Register> {
+    match isa.triple().architecture {
+        Architecture::X86_64 => Some(gimli::X86_64::RSP),
+        Architecture::Aarch64(_) => Some(gimli::AArch64::SP),
+        Architecture::Riscv64(_) => Some(gimli::RiscV::SP),
+        // FIXME add the stack pointer of s390x
+        _ => None,
+    }
+}
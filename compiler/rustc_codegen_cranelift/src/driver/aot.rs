@@ -20,6 +20,7 @@
 use cranelift_object::{ObjectBuilder, ObjectModule};
 
 use crate::concurrency_limiter::{ConcurrencyLimiter, ConcurrencyLimiterToken};
+use crate::debuginfo::TypeDebugContext;
 use crate::global_asm::GlobalAsmConfig;
 use crate::{prelude::*, BackendConfig};
 
@@ -271,6 +272,7 @@ fn module_codegen(
             tcx.sess.opts.debuginfo != DebugInfo::None,
             cgu_name,
         );
+        let mut type_dbg = TypeDebugContext::default();
         super::predefine_mono_items(tcx, &mut module, &mono_items);
         let mut codegened_functions = vec![];
         for (mono_item, _) in mono_items {
@@ -280,6 +282,7 @@ fn module_codegen(
                         let codegened_function = crate::base::codegen_fn(
                             tcx,
                             &mut cx,
+                            &mut type_dbg,
                             Function::new(),
                             &mut module,
                             inst,
@@ -0,0 +1,446 @@
+//! Hot reloading of changed functions for the lazy JIT mode.
+//!
+//! While the jitted program is running, the source files of the crate are polled for changes.
+//! After a change the crate is compiled again in a new compiler session. Every function whose MIR
+//! changed is compiled in this session and swapped into the running program by redefining it in
+//! the [`JITModule`]. As the lazy JIT mode enables hotswapping, all calls go through the GOT and
+//! will use the new definition from then on.
+//!
+//! Functions which haven't been called yet keep their lazy JIT trampoline and are compiled from
+//! the original compiler session when they are first called. This is fine as their MIR is the
+//! same in both sessions.
+//!
+//! Changing the signature of a function or the layout of a type is not supported, as the running
+//! program may still hold values of the old types. All changes are checked before anything is
+//! redefined, so a rejected change leaves the running program untouched.
+//!
+//! The compiler sessions used for reloading share an incremental cache, so only the first reload
+//! has to analyze the whole crate again.
+
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_errors::FatalErrorMarker;
+use rustc_interface::interface;
+use rustc_middle::mir::mono::{Linkage as RLinkage, MonoItem, Visibility};
+use rustc_session::config::Input;
+use rustc_span::{FileName, Symbol};
+use rustc_target::abi::{FieldsShape, Variants};
+
+use cranelift_jit::JITModule;
+use cranelift_module::FuncOrDataId;
+
+use crate::debuginfo::TypeDebugContext;
+use crate::prelude::*;
+use crate::{BackendConfig, CodegenCx};
+
+/// How often the source files are checked for changes.
+pub(super) const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub(super) struct HotReloadState {
+    /// The fingerprint of the MIR every function in the running program was or will be compiled
+    /// from, indexed by symbol name.
+    fingerprints: FxHashMap<String, Fingerprint>,
+    /// The fingerprint of the layout of every type the running program operates on, indexed by
+    /// the fingerprint of the type.
+    layouts: FxHashMap<Fingerprint, Fingerprint>,
+    /// The source files of the crate and their modification time when they were last compiled.
+    watched_files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl HotReloadState {
+    pub(super) fn new(tcx: TyCtxt<'_>) -> Self {
+        if tcx.sess.local_crate_source_file.is_none() {
+            tcx.sess.fatal("hot reloading requires the crate to be read from a file");
+        }
+
+        let mono_items = mono_items(tcx);
+        HotReloadState {
+            fingerprints: mir_fingerprints(tcx, &mono_items),
+            layouts: type_layouts(tcx, &mono_items)
+                .into_iter()
+                .map(|(ty_hash, (_, layout_hash))| (ty_hash, layout_hash))
+                .collect(),
+            watched_files: watched_files(tcx),
+        }
+    }
+
+    /// Returns whether any source file was modified since it was last compiled.
+    fn sources_changed(&self) -> bool {
+        self.watched_files.iter().any(|(path, mtime)| modification_time(path) != *mtime)
+    }
+}
+
+/// Checks for changed source files and reloads all changed functions if there are any.
+pub(super) fn poll(
+    tcx: TyCtxt<'_>,
+    backend_config: &BackendConfig,
+    jit_module: &mut JITModule,
+    hot_reload: &mut HotReloadState,
+) {
+    if !hot_reload.sources_changed() {
+        return;
+    }
+
+    // Wait for editors which write files in multiple steps to finish.
+    std::thread::sleep(POLL_INTERVAL);
+
+    let config = session_config(tcx);
+
+    // `JITModule` isn't `Send` as it contains raw pointers to the jitted code. `run_compiler` runs
+    // the compiler session on a new thread and joins it before returning, so the borrow doesn't
+    // outlive this call and this thread doesn't access the module in the meantime. Neither the
+    // jitted code nor the module refer to anything owned by this thread or interned in the
+    // session globals of the original session.
+    struct SendJitModule<'a>(&'a mut JITModule);
+    unsafe impl Send for SendJitModule<'_> {}
+    let jit_module = SendJitModule(jit_module);
+
+    let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        interface::run_compiler(config, |compiler| {
+            // Capture the wrapper rather than just the reference inside it.
+            let jit_module = jit_module;
+            let crate_hash = compiler.enter(|queries| {
+                queries.global_ctxt()?.peek_mut().enter(|tcx| {
+                    tcx.analysis(())?;
+                    reload_changed_functions(tcx, backend_config, jit_module.0, hot_reload);
+                    Ok(tcx.crate_hash(LOCAL_CRATE))
+                })
+            })?;
+            // This is normally done when linking, which doesn't happen for hot reloading.
+            rustc_incremental::finalize_session_directory(compiler.session(), crate_hash);
+            Ok(())
+        })
+    }));
+
+    match res {
+        Ok(Ok(())) => {}
+        Ok(Err(_)) => compilation_failed(hot_reload),
+        Err(err) if err.is::<FatalErrorMarker>() => compilation_failed(hot_reload),
+        Err(err) => std::panic::resume_unwind(err),
+    }
+}
+
+fn compilation_failed(hot_reload: &mut HotReloadState) {
+    eprintln!("hot reload: compilation failed; the program keeps running the old code");
+
+    // Don't try again until the next change.
+    for (path, mtime) in &mut hot_reload.watched_files {
+        *mtime = modification_time(path);
+    }
+}
+
+/// Creates the configuration for a compiler session which compiles the crate again with the
+/// same options.
+fn session_config(tcx: TyCtxt<'_>) -> interface::Config {
+    let sess = tcx.sess;
+    let input_path = sess.local_crate_source_file.clone().unwrap();
+    let crate_cfg = sess
+        .parse_sess
+        .config
+        .iter()
+        .map(|&(name, value)| (name.to_string(), value.map(|value| value.to_string())))
+        .collect();
+
+    let mut opts = sess.opts.clone();
+    // The incremental session directory of the original session is still locked, so use a
+    // separate cache which is shared by all reloads.
+    opts.incremental = Some(match &sess.opts.incremental {
+        Some(incr_dir) => incr_dir.join("cg_clif-hot-reload"),
+        None => std::env::temp_dir().join(format!(
+            "cg_clif-hot-reload-{}-{:016x}",
+            tcx.crate_name(LOCAL_CRATE),
+            sess.local_stable_crate_id().to_u64(),
+        )),
+    });
+
+    interface::Config {
+        opts,
+        crate_cfg,
+        crate_check_cfg: Default::default(),
+        input: Input::File(input_path.clone()),
+        input_path: Some(input_path),
+        output_dir: None,
+        output_file: None,
+        file_loader: None,
+        lint_caps: Default::default(),
+        parse_sess_created: None,
+        register_lints: None,
+        override_queries: None,
+        make_codegen_backend: Some(Box::new(|_| crate::__rustc_codegen_backend())),
+        registry: rustc_driver::diagnostics_registry(),
+    }
+}
+
+fn reload_changed_functions<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    backend_config: &BackendConfig,
+    jit_module: &mut JITModule,
+    hot_reload: &mut HotReloadState,
+) {
+    let mono_items = mono_items(tcx);
+
+    // Check that all changes can be applied before touching the running program.
+    let mut changed_fns = vec![];
+    let mut new_statics = vec![];
+    for &(mono_item, _) in &mono_items {
+        match mono_item {
+            MonoItem::Fn(instance) => {
+                let name = tcx.symbol_name(instance).name;
+                let fingerprint = mir_fingerprint(tcx, instance);
+                if hot_reload.fingerprints.get(name) == Some(&fingerprint) {
+                    continue;
+                }
+
+                match jit_module.get_name(name) {
+                    Some(FuncOrDataId::Func(func_id)) => {
+                        let sig = get_function_sig(tcx, jit_module.isa().triple(), instance);
+                        if jit_module.declarations().get_function_decl(func_id).signature != sig {
+                            tcx.sess.err(&format!(
+                                "hot reload: the signature of `{}` changed; restart the program to \
+                                 apply this change",
+                                instance,
+                            ));
+                            continue;
+                        }
+                    }
+                    Some(FuncOrDataId::Data(_)) => unreachable!("{} is not a function", name),
+                    None => {}
+                }
+                changed_fns.push((instance, fingerprint));
+            }
+            MonoItem::Static(def_id) => {
+                // Existing statics keep their current value.
+                let name = tcx.symbol_name(Instance::mono(tcx, def_id)).name;
+                if jit_module.get_name(name).is_none() {
+                    new_statics.push(def_id);
+                }
+            }
+            MonoItem::GlobalAsm(item_id) => {
+                let item = tcx.hir().item(item_id);
+                tcx.sess.span_fatal(item.span, "Global asm is not supported in JIT mode");
+            }
+        }
+    }
+
+    let layouts = type_layouts(tcx, &mono_items);
+    for (ty_hash, &(ty, layout_hash)) in &layouts {
+        let old_layout_hash = hot_reload.layouts.get(ty_hash);
+        if old_layout_hash.is_some() && old_layout_hash != Some(&layout_hash) {
+            tcx.sess.err(&format!(
+                "hot reload: the layout of `{}` changed; restart the program to apply this change",
+                ty,
+            ));
+        }
+    }
+
+    tcx.sess.abort_if_errors();
+
+    let mut cx = CodegenCx::new(
+        tcx,
+        backend_config.clone(),
+        jit_module.isa(),
+        false,
+        Symbol::intern("dummy_cgu_name"),
+    );
+
+    // Generating code may still fail, so don't redefine any function before this is done for all
+    // of them.
+    let codegened_fns = changed_fns
+        .iter()
+        .map(|&(instance, _)| {
+            let name = tcx.symbol_name(instance).name;
+            if jit_module.get_name(name).is_none() {
+                let sig = get_function_sig(tcx, jit_module.isa().triple(), instance);
+                jit_module.declare_function(name, Linkage::Export, &sig).unwrap();
+            }
+
+            tcx.sess.time("codegen fn", || {
+                crate::base::codegen_fn(
+                    tcx,
+                    &mut cx,
+                    &mut TypeDebugContext::default(),
+                    Function::new(),
+                    jit_module,
+                    instance,
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    tcx.sess.abort_if_errors();
+
+    let mut cached_context = Context::new();
+    for (&(instance, _), codegened_func) in changed_fns.iter().zip(codegened_fns) {
+        let name = tcx.symbol_name(instance).name;
+        // Functions which were declared but never defined by an earlier failed reload don't have
+        // a definition to replace.
+        if hot_reload.fingerprints.contains_key(name) {
+            let func_id = match jit_module.get_name(name) {
+                Some(FuncOrDataId::Func(func_id)) => func_id,
+                _ => unreachable!("{} is not a function", name),
+            };
+            jit_module.prepare_for_function_redefine(func_id).unwrap();
+        }
+        tcx.sess.time("compile fn", || {
+            crate::base::compile_fn(&mut cx, &mut cached_context, jit_module, codegened_func)
+        });
+    }
+
+    for def_id in new_statics {
+        crate::constant::codegen_static(tcx, jit_module, def_id);
+    }
+
+    assert!(cx.global_asm.is_empty());
+
+    jit_module.finalize_definitions();
+    unsafe { cx.unwind_context.register_jit(jit_module) };
+
+    let reloaded = changed_fns.len();
+    for (instance, fingerprint) in changed_fns {
+        hot_reload.fingerprints.insert(tcx.symbol_name(instance).name.to_owned(), fingerprint);
+    }
+    hot_reload
+        .layouts
+        .extend(layouts.into_iter().map(|(ty_hash, (_, layout_hash))| (ty_hash, layout_hash)));
+    hot_reload.watched_files = watched_files(tcx);
+
+    eprintln!("hot reload: reloaded {} changed functions", reloaded);
+}
+
+fn mono_items(tcx: TyCtxt<'_>) -> Vec<(MonoItem<'_>, (RLinkage, Visibility))> {
+    let (_, cgus) = tcx.collect_and_partition_mono_items(());
+    cgus.iter()
+        .flat_map(|cgu| cgu.items_in_deterministic_order(tcx).into_iter())
+        .collect::<FxHashMap<_, _>>()
+        .into_iter()
+        .collect()
+}
+
+fn mir_fingerprints<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    mono_items: &[(MonoItem<'tcx>, (RLinkage, Visibility))],
+) -> FxHashMap<String, Fingerprint> {
+    tcx.sess.time("hash mir for hot reload", || {
+        mono_items
+            .iter()
+            .filter_map(|&(mono_item, _)| match mono_item {
+                MonoItem::Fn(instance) => Some((
+                    tcx.symbol_name(instance).name.to_owned(),
+                    mir_fingerprint(tcx, instance),
+                )),
+                MonoItem::Static(_) | MonoItem::GlobalAsm(_) => None,
+            })
+            .collect()
+    })
+}
+
+/// Hashes everything the code of a function is generated from. Spans are ignored, so moving a
+/// function around in a file doesn't cause it to be reloaded.
+fn mir_fingerprint<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Fingerprint {
+    let mir = tcx.instance_mir(instance.def);
+    tcx.with_stable_hashing_context(|mut hcx| {
+        let mut hasher = StableHasher::new();
+        hcx.while_hashing_spans(false, |hcx| {
+            instance.hash_stable(hcx, &mut hasher);
+            mir.hash_stable(hcx, &mut hasher);
+        });
+        hasher.finish()
+    })
+}
+
+/// Computes the layout of every type the given functions operate on, indexed by the fingerprint of
+/// the type. These are the types of all locals and every type reachable from them through fields
+/// and pointers.
+fn type_layouts<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    mono_items: &[(MonoItem<'tcx>, (RLinkage, Visibility))],
+) -> FxHashMap<Fingerprint, (Ty<'tcx>, Fingerprint)> {
+    tcx.sess.time("hash layouts for hot reload", || {
+        let cx = RevealAllLayoutCx(tcx);
+        let mut worklist = vec![];
+        for &(mono_item, _) in mono_items {
+            if let MonoItem::Fn(instance) = mono_item {
+                let mir = tcx.instance_mir(instance.def);
+                worklist.extend(mir.local_decls.iter().map(|local_decl| {
+                    instance.subst_mir_and_normalize_erasing_regions(
+                        tcx,
+                        ParamEnv::reveal_all(),
+                        local_decl.ty,
+                    )
+                }));
+            }
+        }
+
+        let mut visited = FxHashSet::default();
+        let mut layouts = FxHashMap::default();
+        tcx.with_stable_hashing_context(|mut hcx| {
+            while let Some(ty) = worklist.pop() {
+                if !visited.insert(ty) {
+                    continue;
+                }
+                let layout = match tcx.layout_of(ParamEnv::reveal_all().and(ty)) {
+                    Ok(layout) => layout,
+                    Err(_) => continue,
+                };
+
+                let mut hasher = StableHasher::new();
+                ty.hash_stable(&mut hcx, &mut hasher);
+                let ty_hash: Fingerprint = hasher.finish();
+                let mut hasher = StableHasher::new();
+                layout.layout.hash_stable(&mut hcx, &mut hasher);
+                let layout_hash: Fingerprint = hasher.finish();
+                layouts.insert(ty_hash, (ty, layout_hash));
+
+                if let Some(pointee) = ty.builtin_deref(true) {
+                    worklist.push(pointee.ty);
+                }
+                push_field_tys(&cx, layout, &mut worklist);
+                if let Variants::Multiple { variants, .. } = &layout.variants {
+                    for variant_index in variants.indices() {
+                        push_field_tys(&cx, layout.for_variant(&cx, variant_index), &mut worklist);
+                    }
+                }
+            }
+        });
+        layouts
+    })
+}
+
+fn push_field_tys<'tcx>(
+    cx: &RevealAllLayoutCx<'tcx>,
+    layout: TyAndLayout<'tcx>,
+    worklist: &mut Vec<Ty<'tcx>>,
+) {
+    // All elements of an array have the same type.
+    let field_count = match layout.fields {
+        FieldsShape::Array { .. } => 1,
+        ref fields => fields.count(),
+    };
+    worklist.extend((0..field_count).map(|i| layout.field(cx, i).ty));
+}
+
+fn watched_files(tcx: TyCtxt<'_>) -> Vec<(PathBuf, Option<SystemTime>)> {
+    tcx.sess
+        .source_map()
+        .files()
+        .iter()
+        .filter(|file| !file.is_imported())
+        .filter_map(|file| match &file.name {
+            FileName::Real(name) => name.local_path().map(|path| path.to_owned()),
+            _ => None,
+        })
+        .map(|path| {
+            let mtime = modification_time(&path);
+            (path, mtime)
+        })
+        .collect()
+}
+
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
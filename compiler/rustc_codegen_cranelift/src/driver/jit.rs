@@ -16,12 +16,15 @@
 // FIXME use std::sync::OnceLock once it stabilizes
 use once_cell::sync::OnceCell;
 
+use super::hot_reload::{self, HotReloadState};
+use crate::debuginfo::TypeDebugContext;
 use crate::{prelude::*, BackendConfig};
 use crate::{CodegenCx, CodegenMode};
 
 struct JitState {
     backend_config: BackendConfig,
     jit_module: JITModule,
+    hot_reload: Option<HotReloadState>,
 }
 
 thread_local! {
@@ -105,6 +108,11 @@ pub(crate) fn run_jit(tcx: TyCtxt<'_>, backend_config: BackendConfig) -> ! {
         tcx.sess.fatal("can't jit non-executable crate");
     }
 
+    if backend_config.jit_hot_reload && !matches!(backend_config.codegen_mode, CodegenMode::JitLazy)
+    {
+        tcx.sess.fatal("hot reloading is only supported in lazy JIT mode");
+    }
+
     let (mut jit_module, mut cx) = create_jit_module(
         tcx,
         &backend_config,
@@ -132,6 +140,7 @@ pub(crate) fn run_jit(tcx: TyCtxt<'_>, backend_config: BackendConfig) -> ! {
                             crate::base::codegen_and_compile_fn(
                                 tcx,
                                 &mut cx,
+                                &mut TypeDebugContext::default(),
                                 &mut cached_context,
                                 &mut jit_module,
                                 inst,
@@ -182,10 +191,14 @@ pub(crate) fn run_jit(tcx: TyCtxt<'_>, backend_config: BackendConfig) -> ! {
     let start_func_id = jit_module.declare_function("main", Linkage::Import, &start_sig).unwrap();
     let finalized_start: *const u8 = jit_module.get_finalized_function(start_func_id);
 
+    let hot_reload =
+        if backend_config.jit_hot_reload { Some(HotReloadState::new(tcx)) } else { None };
+    let poll_for_changes = hot_reload.is_some();
+
     LAZY_JIT_STATE.with(|lazy_jit_state| {
         let mut lazy_jit_state = lazy_jit_state.borrow_mut();
         assert!(lazy_jit_state.is_none());
-        *lazy_jit_state = Some(JitState { backend_config, jit_module });
+        *lazy_jit_state = Some(JitState { backend_config, jit_module, hot_reload });
     });
 
     let f: extern "C" fn(c_int, *const *const c_char) -> c_int =
@@ -209,7 +222,20 @@ pub(crate) fn run_jit(tcx: TyCtxt<'_>, backend_config: BackendConfig) -> ! {
 
     // Handle messages
     loop {
-        match rx.recv().unwrap() {
+        let message = if poll_for_changes {
+            match rx.recv_timeout(hot_reload::POLL_INTERVAL) {
+                Ok(message) => message,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    poll_hot_reload(tcx);
+                    continue;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => unreachable!(),
+            }
+        } else {
+            rx.recv().unwrap()
+        };
+
+        match message {
             // lazy JIT compilation request - compile requested instance and return pointer to result
             UnsafeMessage::JitFn { instance_ptr, trampoline_ptr, tx } => {
                 tx.send(jit_fn(instance_ptr, trampoline_ptr))
@@ -219,6 +245,14 @@ pub(crate) fn run_jit(tcx: TyCtxt<'_>, backend_config: BackendConfig) -> ! {
     }
 }
 
+fn poll_hot_reload(tcx: TyCtxt<'_>) {
+    LAZY_JIT_STATE.with(|lazy_jit_state| {
+        let mut lazy_jit_state = lazy_jit_state.borrow_mut();
+        let JitState { backend_config, jit_module, hot_reload } = lazy_jit_state.as_mut().unwrap();
+        hot_reload::poll(tcx, backend_config, jit_module, hot_reload.as_mut().unwrap());
+    });
+}
+
 extern "C" fn clif_jit_fn(
     instance_ptr: *const Instance<'static>,
     trampoline_ptr: *const u8,
@@ -271,6 +305,7 @@ fn jit_fn(instance_ptr: *const Instance<'static>, trampoline_ptr: *const u8) ->
                 crate::base::codegen_and_compile_fn(
                     tcx,
                     &mut cx,
+                    &mut TypeDebugContext::default(),
                     &mut Context::new(),
                     jit_module,
                     instance,
@@ -10,6 +10,8 @@
 
 pub(crate) mod aot;
 #[cfg(feature = "jit")]
+mod hot_reload;
+#[cfg(feature = "jit")]
 pub(crate) mod jit;
 
 fn predefine_mono_items<'tcx>(
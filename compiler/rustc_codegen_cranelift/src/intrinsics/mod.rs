@@ -1195,20 +1195,25 @@ fn swap(bcx: &mut FunctionBuilder<'_>, v: Value) -> Value {
             intrinsic_args!(fx, args => (f, data, catch_fn); intrinsic);
             let f = f.load_scalar(fx);
             let data = data.load_scalar(fx);
-            let _catch_fn = catch_fn.load_scalar(fx);
+            let catch_fn = catch_fn.load_scalar(fx);
 
-            // FIXME once unwinding is supported, change this to actually catch panics
-            let f_sig = fx.bcx.func.import_signature(Signature {
-                call_conv: fx.target_config.default_call_conv,
-                params: vec![AbiParam::new(pointer_ty(fx.tcx))],
-                returns: vec![],
-            });
+            if fx.cx.can_catch_unwind {
+                let caught = crate::catch_unwind::codegen_try(fx, f, data, catch_fn);
+                ret.write_cvalue(fx, CValue::by_val(caught, ret.layout()));
+            } else {
+                // Panics can't unwind in this configuration, so there is nothing to catch.
+                let f_sig = fx.bcx.func.import_signature(Signature {
+                    call_conv: fx.target_config.default_call_conv,
+                    params: vec![AbiParam::new(pointer_ty(fx.tcx))],
+                    returns: vec![],
+                });
 
-            fx.bcx.ins().call_indirect(f_sig, f, &[data]);
+                fx.bcx.ins().call_indirect(f_sig, f, &[data]);
 
-            let layout = ret.layout();
-            let ret_val = CValue::const_val(fx, layout, ty::ScalarInt::null(layout.size));
-            ret.write_cvalue(fx, ret_val);
+                let layout = ret.layout();
+                let ret_val = CValue::const_val(fx, layout, ty::ScalarInt::null(layout.size));
+                ret.write_cvalue(fx, ret_val);
+            }
         }
 
         sym::fadd_fast | sym::fsub_fast | sym::fmul_fast | sym::fdiv_fast | sym::frem_fast => {
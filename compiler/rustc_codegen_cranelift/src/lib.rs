@@ -51,6 +51,7 @@
 mod archive;
 mod base;
 mod cast;
+mod catch_unwind;
 mod codegen_i128;
 mod common;
 mod compiler_builtins;
@@ -133,6 +134,9 @@ struct CodegenCx {
     debug_context: Option<DebugContext>,
     unwind_context: UnwindContext,
     cgu_name: Symbol,
+    can_catch_unwind: bool,
+    unwind_shims_emitted: bool,
+    invoke_thunks: FxHashMap<Signature, FuncId>,
 }
 
 impl CodegenCx {
@@ -147,6 +151,7 @@ fn new(
 
         let unwind_context =
             UnwindContext::new(isa, matches!(backend_config.codegen_mode, CodegenMode::Aot));
+        let can_catch_unwind = crate::catch_unwind::can_catch_unwind(tcx, &backend_config);
         let debug_context = if debug_info && !tcx.sess.target.options.is_like_windows {
             Some(DebugContext::new(tcx, isa))
         } else {
@@ -161,6 +166,9 @@ fn new(
             debug_context,
             unwind_context,
             cgu_name,
+            can_catch_unwind,
+            unwind_shims_emitted: false,
+            invoke_thunks: FxHashMap::default(),
         }
     }
 }
@@ -267,6 +275,8 @@ fn build_isa(sess: &Session, backend_config: &BackendConfig) -> Box<dyn isa::Tar
 
     flags_builder.set("enable_llvm_abi_extensions", "true").unwrap();
 
+    // Cranelift only supports an optimization level for the whole ISA, so functions with
+    // `#[optimize(level = "...")]` are optimized at the optimization level of the crate.
     use rustc_session::config::OptLevel;
     match sess.opts.optimize {
         OptLevel::No => {
@@ -227,6 +227,9 @@ fn print_pass_timings(&self) {
     }
 
     unsafe fn optimize(_cgcx: &CodegenContext<Self>, _diag_handler: &Handler, module: &ModuleCodegen<Self::Module>, config: &ModuleConfig) -> Result<(), FatalError> {
+        // libgccjit doesn't support per-function optimization levels, so with functions having
+        // `#[optimize(level = "...")]`, the whole module is optimized at the highest level requested
+        // by any of them (see `backend_optimization_level`).
         module.module_llvm.context.set_optimization_level(to_gcc_opt_level(config.opt_level));
         Ok(())
     }
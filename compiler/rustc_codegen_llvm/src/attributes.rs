@@ -4,6 +4,7 @@
 use rustc_data_structures::small_str::SmallStr;
 use rustc_hir::def_id::DefId;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::mir::mono::{InstantiationMode, MonoItem};
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_session::config::OptLevel;
 use rustc_span::symbol::sym;
@@ -247,18 +248,47 @@ pub fn from_fn_attrs<'ll, 'tcx>(
 
     let mut to_add = SmallVec::<[_; 16]>::new();
 
-    match codegen_fn_attrs.optimize {
+    let optimize = match codegen_fn_attrs.optimize {
+        OptimizeAttr::Level(opt_level) => OptimizeAttr::Level(opt_level),
+        // When `#[optimize(level = "...")]` raises the pipeline of an unoptimized crate, it is
+        // raised for every module of the crate, so all other functions are made `optnone` to
+        // keep them unoptimized. Functions which are meant to be inlined, or copied into each
+        // codegen unit, are not, so they are optimized wherever they end up.
+        _ if cx.sess().opts.optimize == OptLevel::No
+            && cx.tcx.backend_optimization_level(()) != OptLevel::No
+            && !codegen_fn_attrs.requests_inline()
+            && !instance.def.requires_inline(cx.tcx)
+            && MonoItem::Fn(instance).instantiation_mode(cx.tcx) != InstantiationMode::LocalCopy =>
+        {
+            OptimizeAttr::Level(OptLevel::No)
+        }
+        ref optimize => optimize.clone(),
+    };
+    match optimize {
         OptimizeAttr::None => {
             to_add.extend(default_optimisation_attrs(cx));
         }
-        OptimizeAttr::Size => {
+        OptimizeAttr::Size | OptimizeAttr::Level(OptLevel::SizeMin) => {
             to_add.push(llvm::AttributeKind::MinSize.create_attr(cx.llcx));
             to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
         }
-        OptimizeAttr::Speed => {}
+        OptimizeAttr::Level(OptLevel::Size) => {
+            to_add.push(llvm::AttributeKind::OptimizeForSize.create_attr(cx.llcx));
+        }
+        // `optnone` requires `noinline`, which is added below.
+        OptimizeAttr::Level(OptLevel::No) => {
+            to_add.push(llvm::AttributeKind::OptimizeNone.create_attr(cx.llcx));
+        }
+        // The module is optimized at least at the highest level requested by a function, see
+        // `backend_optimization_level`.
+        OptimizeAttr::Speed
+        | OptimizeAttr::Level(OptLevel::Less | OptLevel::Default | OptLevel::Aggressive) => {}
     }
 
-    let inline = if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
+    let inline = if let OptimizeAttr::Level(OptLevel::No) = optimize {
+        to_add.push(llvm::AttributeKind::NoInline.create_attr(cx.llcx));
+        InlineAttr::None
+    } else if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
         InlineAttr::Never
     } else if codegen_fn_attrs.inline == InlineAttr::None && instance.def.requires_inline(cx.tcx) {
         InlineAttr::Hint
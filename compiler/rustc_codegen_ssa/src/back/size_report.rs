@@ -0,0 +1,310 @@
+//! The report written by `-Zlink-size-report`.
+//!
+//! After linking, the symbol table of the linked output is used to attribute the bytes of every
+//! loaded section to the symbols in it and to the crates defining these symbols. This only relies
+//! on the linked output, so it works the same for every linker as long as the symbol table isn't
+//! stripped. Bytes not covered by any symbol, like padding or the contents of sections without
+//! symbols, are reported as unattributed.
+//!
+//! The report also lists the symbols defined by the object files of the local crate which didn't
+//! make it into the linked output, usually because the linker removed their sections as unused.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use object::{Object, ObjectSection, ObjectSymbol, SectionIndex, SectionKind, SymbolKind};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::memmap::Mmap;
+use rustc_session::config::SwitchWithOptPath;
+use rustc_session::Session;
+use serde::Serialize;
+
+use crate::{errors, CodegenResults};
+
+/// The crate of symbols whose name isn't a Rust symbol name.
+const NON_RUST_CRATE: &str = "[non-rust]";
+
+#[derive(Serialize)]
+struct SizeReport {
+    /// The path of the linked output.
+    output: String,
+    sections: Vec<SectionSize>,
+    /// The total size of every crate over all sections.
+    crates: Vec<CrateSize>,
+    /// The symbols of the linked output, biggest first.
+    symbols: Vec<SymbolSize>,
+    /// The symbols defined by the local crate which aren't part of the linked output.
+    removed_symbols: Vec<SymbolSize>,
+}
+
+#[derive(Serialize)]
+struct SectionSize {
+    name: String,
+    size: u64,
+    /// The bytes of the section not covered by any symbol.
+    unattributed: u64,
+    crates: Vec<CrateSize>,
+}
+
+#[derive(Serialize)]
+struct CrateSize {
+    name: String,
+    size: u64,
+}
+
+#[derive(Serialize)]
+struct SymbolSize {
+    /// The demangled name of the symbol, without the hash.
+    name: String,
+    mangled_name: String,
+    #[serde(rename = "crate")]
+    krate: String,
+    section: String,
+    size: u64,
+}
+
+pub(super) fn write_size_report(
+    sess: &Session,
+    codegen_results: &CodegenResults,
+    out_filename: &Path,
+) {
+    let SwitchWithOptPath::Enabled(ref output_dir) = sess.opts.unstable_opts.link_size_report
+    else {
+        return;
+    };
+
+    let _timer = sess.timer("link_size_report");
+
+    let data = match map_file(out_filename) {
+        Ok(data) => data,
+        Err(error) => {
+            sess.emit_err(errors::SizeReportReadFailure {
+                path: out_filename.to_owned(),
+                error: error.to_string(),
+            });
+            return;
+        }
+    };
+    let file = match object::File::parse(&*data) {
+        Ok(file) => file,
+        Err(error) => {
+            sess.emit_err(errors::SizeReportReadFailure {
+                path: out_filename.to_owned(),
+                error: error.to_string(),
+            });
+            return;
+        }
+    };
+
+    let (sections, symbols) = attribute_sections(&file);
+    if symbols.is_empty() {
+        sess.emit_warning(errors::SizeReportNoSymbols { path: out_filename.to_owned() });
+    }
+
+    let linked_names: FxHashSet<&str> =
+        symbols.iter().map(|symbol| &*symbol.mangled_name).collect();
+    let removed_symbols = removed_local_symbols(codegen_results, &linked_names);
+
+    let mut crates: FxHashMap<&str, u64> = Default::default();
+    for section in &sections {
+        for krate in &section.crates {
+            *crates.entry(&krate.name).or_default() += krate.size;
+        }
+    }
+    let crates = sorted_crate_sizes(crates.into_iter().map(|(name, size)| (name.to_owned(), size)));
+
+    let report = SizeReport {
+        output: out_filename.display().to_string(),
+        sections,
+        crates,
+        symbols,
+        removed_symbols,
+    };
+
+    let output_dir = match output_dir {
+        Some(output_dir) => output_dir.as_path(),
+        None => out_filename.parent().unwrap_or(Path::new(".")),
+    };
+    let mut report_name = out_filename.file_name().unwrap().to_owned();
+    report_name.push(".size_report.json");
+    let report_path = output_dir.join(report_name);
+    if let Err(error) = write_report(&report, output_dir, &report_path) {
+        sess.emit_err(errors::FailedToWrite { path: report_path, error });
+    }
+}
+
+fn map_file(path: &Path) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    unsafe { Mmap::map(file) }
+}
+
+/// Returns whether the section is part of the loaded image, as opposed to debuginfo, notes and
+/// other metadata.
+fn is_loaded_section(kind: SectionKind) -> bool {
+    matches!(
+        kind,
+        SectionKind::Text
+            | SectionKind::Data
+            | SectionKind::ReadOnlyData
+            | SectionKind::ReadOnlyString
+            | SectionKind::UninitializedData
+            | SectionKind::Tls
+            | SectionKind::UninitializedTls
+    )
+}
+
+/// Attributes the bytes of every loaded section to the symbols in it. A symbol only gets the
+/// bytes up to the start of the next symbol, so overlapping symbols are not counted twice.
+fn attribute_sections(file: &object::File<'_>) -> (Vec<SectionSize>, Vec<SymbolSize>) {
+    let mut symbols_per_section: FxHashMap<SectionIndex, Vec<(u64, u64, &str)>> =
+        Default::default();
+    for symbol in file.symbols() {
+        if !symbol.is_definition()
+            || symbol.size() == 0
+            || matches!(symbol.kind(), SymbolKind::Section | SymbolKind::File)
+        {
+            continue;
+        }
+        let (Some(section_index), Ok(name)) = (symbol.section_index(), symbol.name()) else {
+            continue;
+        };
+        symbols_per_section.entry(section_index).or_default().push((
+            symbol.address(),
+            symbol.size(),
+            name,
+        ));
+    }
+
+    let mut sections = vec![];
+    let mut symbols = vec![];
+    for section in file.sections() {
+        if !is_loaded_section(section.kind()) || section.size() == 0 {
+            continue;
+        }
+        let section_name = section.name().unwrap_or("<unknown>").to_owned();
+        let section_end = section.address() + section.size();
+
+        let mut section_symbols = symbols_per_section.remove(&section.index()).unwrap_or_default();
+        // Sorting by name as well keeps the choice between aliases deterministic.
+        section_symbols.sort_unstable();
+        // Aliases of a symbol are only counted once.
+        section_symbols.dedup_by_key(|&mut (address, _, _)| address);
+
+        let mut crates: FxHashMap<String, u64> = Default::default();
+        let mut attributed = 0;
+        for (i, &(address, size, mangled_name)) in section_symbols.iter().enumerate() {
+            let next_address = section_symbols.get(i + 1).map_or(section_end, |next| next.0);
+            let size = size.min(next_address.saturating_sub(address));
+            if size == 0 {
+                continue;
+            }
+            let (name, krate) = demangle(mangled_name);
+            *crates.entry(krate.clone()).or_default() += size;
+            attributed += size;
+            symbols.push(SymbolSize {
+                name,
+                mangled_name: mangled_name.to_owned(),
+                krate,
+                section: section_name.clone(),
+                size,
+            });
+        }
+
+        sections.push(SectionSize {
+            name: section_name,
+            size: section.size(),
+            unattributed: section.size().saturating_sub(attributed),
+            crates: sorted_crate_sizes(crates.into_iter()),
+        });
+    }
+
+    symbols.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    (sections, symbols)
+}
+
+/// Returns the symbols defined in the loaded sections of the object files of the local crate
+/// which are not part of the linked output.
+fn removed_local_symbols(
+    codegen_results: &CodegenResults,
+    linked_names: &FxHashSet<&str>,
+) -> Vec<SymbolSize> {
+    let mut removed = vec![];
+    let objects = codegen_results
+        .modules
+        .iter()
+        .chain(&codegen_results.allocator_module)
+        .filter_map(|module| module.object.as_deref());
+    for object_path in objects {
+        // Object files which can't be read anymore were already reported by the linker.
+        let Ok(data) = map_file(object_path) else { continue };
+        let Ok(file) = object::File::parse(&*data) else { continue };
+        for symbol in file.symbols() {
+            if !symbol.is_definition() || symbol.size() == 0 {
+                continue;
+            }
+            let (Some(section_index), Ok(mangled_name)) = (symbol.section_index(), symbol.name())
+            else {
+                continue;
+            };
+            let Ok(section) = file.section_by_index(section_index) else { continue };
+            if !is_loaded_section(section.kind()) || linked_names.contains(mangled_name) {
+                continue;
+            }
+            let (name, krate) = demangle(mangled_name);
+            removed.push(SymbolSize {
+                name,
+                mangled_name: mangled_name.to_owned(),
+                krate,
+                section: section.name().unwrap_or("<unknown>").to_owned(),
+                size: symbol.size(),
+            });
+        }
+    }
+
+    removed.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    removed.dedup_by(|a, b| a.mangled_name == b.mangled_name);
+    removed
+}
+
+fn sorted_crate_sizes(crates: impl Iterator<Item = (String, u64)>) -> Vec<CrateSize> {
+    let mut crates: Vec<_> = crates.map(|(name, size)| CrateSize { name, size }).collect();
+    crates.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    crates
+}
+
+/// Returns the demangled name of a symbol and the crate it belongs to.
+fn demangle(mangled_name: &str) -> (String, String) {
+    match rustc_demangle::try_demangle(mangled_name) {
+        Ok(demangled) => {
+            // The alternate format omits the hash.
+            let name = format!("{:#}", demangled);
+            let krate = crate_of_path(&name).unwrap_or(NON_RUST_CRATE).to_owned();
+            (name, krate)
+        }
+        Err(_) => (mangled_name.to_owned(), NON_RUST_CRATE.to_owned()),
+    }
+}
+
+/// Returns the crate of a demangled Rust path. For trait impls on a type of another crate, like
+/// `<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop`, this is the crate of the type, as the
+/// impl is instantiated for it. For impls on primitive types it is the crate of the trait.
+fn crate_of_path(path: &str) -> Option<&str> {
+    fn leading_crate(path: &str) -> Option<&str> {
+        let mut path = path.trim_start_matches(|c| matches!(c, '<' | '&' | '*' | '['));
+        for prefix in ["mut ", "const ", "dyn "] {
+            path = path.strip_prefix(prefix).unwrap_or(path);
+        }
+        let end = path.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+        path[end..].starts_with("::").then(|| &path[..end])
+    }
+
+    leading_crate(path).or_else(|| leading_crate(&path[path.find(" as ")? + " as ".len()..]))
+}
+
+fn write_report(report: &SizeReport, output_dir: &Path, report_path: &Path) -> io::Result<()> {
+    fs::create_dir_all(output_dir)?;
+    let mut file = BufWriter::new(File::create(report_path)?);
+    serde_json::to_writer_pretty(&mut file, report)?;
+    file.flush()
+}
@@ -38,14 +38,15 @@
 use std::any::Any;
 use std::borrow::Cow;
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::marker::PhantomData;
 use std::mem;
 use std::path::{Path, PathBuf};
 use std::str;
 use std::sync::mpsc::{channel, Receiver, Sender};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 const PRE_LTO_BC_EXT: &str = "pre-lto.bc";
 
@@ -123,6 +124,7 @@ impl ModuleConfig {
     fn new(
         kind: ModuleKind,
         sess: &Session,
+        opt_level: config::OptLevel,
         no_builtins: bool,
         is_compiler_builtins: bool,
     ) -> ModuleConfig {
@@ -134,7 +136,7 @@ macro_rules! if_regular {
             };
         }
 
-        let opt_level_and_size = if_regular!(Some(sess.opts.optimize), None);
+        let opt_level_and_size = if_regular!(Some(opt_level), None);
 
         let save_temps = sess.opts.cg.save_temps;
 
@@ -235,10 +237,10 @@ macro_rules! if_regular {
             // Copy what clang does by turning on loop vectorization at O2 and
             // slp vectorization at O3.
             vectorize_loop: !sess.opts.cg.no_vectorize_loops
-                && (sess.opts.optimize == config::OptLevel::Default
-                    || sess.opts.optimize == config::OptLevel::Aggressive),
+                && (opt_level == config::OptLevel::Default
+                    || opt_level == config::OptLevel::Aggressive),
             vectorize_slp: !sess.opts.cg.no_vectorize_slp
-                && sess.opts.optimize == config::OptLevel::Aggressive,
+                && opt_level == config::OptLevel::Aggressive,
 
             // Some targets (namely, NVPTX) interact badly with the
             // MergeFunctions pass. This is because MergeFunctions can generate
@@ -355,6 +357,8 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     pub cgu_reuse_tracker: CguReuseTracker,
     // Channel back to the main control thread to send messages to
     pub coordinator_send: Sender<Box<dyn Any + Send>>,
+    // The time spent on each codegen unit, if it is recorded for `-Zdump-cgu-timings`
+    pub cgu_timings: Option<Arc<Mutex<CguTimings>>>,
 }
 
 impl<B: WriteBackendMethods> CodegenContext<B> {
@@ -412,6 +416,51 @@ pub struct CompiledModules {
     pub allocator_module: Option<CompiledModule>,
 }
 
+/// The time spent on each codegen unit, recorded with `-Zdump-cgu-timings`.
+///
+/// Every codegen unit is written together with the items in it, so the file can be passed to
+/// `-Zcgu-partitioning-profile` to balance the codegen units of later compilations by how long
+/// they take to compile.
+#[derive(Default)]
+pub struct CguTimings {
+    /// The time spent generating, optimizing and emitting each codegen unit, indexed by its name.
+    times: FxHashMap<String, Duration>,
+    /// The paths of the definitions of the items in each codegen unit, indexed by its name.
+    items: FxHashMap<String, Vec<String>>,
+}
+
+impl CguTimings {
+    pub fn record(&mut self, cgu_name: &str, time: Duration) {
+        *self.times.entry(cgu_name.to_owned()).or_default() += time;
+    }
+
+    pub fn set_items(&mut self, cgu_name: &str, items: Vec<String>) {
+        self.items.insert(cgu_name.to_owned(), items);
+    }
+
+    /// Writes the timings as `time` directives of a partitioning profile. The fields are
+    /// separated by tabs, as the paths of items may contain spaces.
+    fn write(&self, path: &Path) -> io::Result<()> {
+        let mut times: Vec<_> = self.times.iter().collect();
+        times.sort();
+        let mut file = io::BufWriter::new(fs::File::create(path)?);
+        writeln!(file, "# The time spent compiling each codegen unit and the items in it.")?;
+        for (cgu_name, &time) in times {
+            // Modules without items, like the result of fat LTO, can't guide partitioning.
+            if let Some(items) = self.items.get(cgu_name) {
+                writeln!(
+                    file,
+                    "time\t{:.6}\t{}\t{}",
+                    time.as_secs_f64(),
+                    cgu_name,
+                    items.join("\t")
+                )?;
+            }
+        }
+        file.flush()
+    }
+}
+
 fn need_bitcode_in_object(sess: &Session) -> bool {
     let requested_for_rlib = sess.opts.cg.embed_bitcode
         && sess.crate_types().contains(&CrateType::Rlib)
@@ -448,15 +497,28 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
 
     let crate_info = CrateInfo::new(tcx, target_cpu);
 
+    // The crate may be optimized beyond `-Copt-level` for functions with `#[optimize]`.
+    let opt_level = tcx.backend_optimization_level(());
     let regular_config =
-        ModuleConfig::new(ModuleKind::Regular, sess, no_builtins, is_compiler_builtins);
+        ModuleConfig::new(ModuleKind::Regular, sess, opt_level, no_builtins, is_compiler_builtins);
     let metadata_config =
-        ModuleConfig::new(ModuleKind::Metadata, sess, no_builtins, is_compiler_builtins);
-    let allocator_config =
-        ModuleConfig::new(ModuleKind::Allocator, sess, no_builtins, is_compiler_builtins);
+        ModuleConfig::new(ModuleKind::Metadata, sess, opt_level, no_builtins, is_compiler_builtins);
+    let allocator_config = ModuleConfig::new(
+        ModuleKind::Allocator,
+        sess,
+        opt_level,
+        no_builtins,
+        is_compiler_builtins,
+    );
 
     let (shared_emitter, shared_emitter_main) = SharedEmitter::new();
     let (codegen_worker_send, codegen_worker_receive) = channel();
+    let cgu_timings = sess
+        .opts
+        .unstable_opts
+        .dump_cgu_timings
+        .is_some()
+        .then(|| Arc::new(Mutex::new(CguTimings::default())));
 
     let coordinator_thread = start_executing_work(
         backend.clone(),
@@ -471,6 +533,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
         Arc::new(metadata_config),
         Arc::new(allocator_config),
         coordinator_send.clone(),
+        cgu_timings.clone(),
     );
 
     OngoingCodegen {
@@ -487,6 +550,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
             phantom: PhantomData,
         },
         output_filenames: tcx.output_filenames(()).clone(),
+        cgu_timings,
     }
 }
 
@@ -997,6 +1061,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
     metadata_config: Arc<ModuleConfig>,
     allocator_config: Arc<ModuleConfig>,
     tx_to_llvm_workers: Sender<Box<dyn Any + Send>>,
+    cgu_timings: Option<Arc<Mutex<CguTimings>>>,
 ) -> thread::JoinHandle<Result<CompiledModules, ()>> {
     let coordinator_send = tx_to_llvm_workers;
     let sess = tcx.sess;
@@ -1089,6 +1154,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         debuginfo: tcx.sess.opts.debuginfo,
         split_debuginfo: tcx.sess.split_debuginfo(),
         split_dwarf_kind: tcx.sess.opts.unstable_opts.split_dwarf_kind,
+        cgu_timings,
     };
 
     // This is the "main loop" of parallel work happening for parallel codegen.
@@ -1689,7 +1755,19 @@ fn drop(&mut self) {
         // surface that there was an error in this worker.
         bomb.result = {
             let _prof_timer = work.start_profiling(&cgcx);
-            Some(execute_work_item(&cgcx, work))
+            let cgu_timing = cgcx.cgu_timings.as_ref().and_then(|cgu_timings| {
+                let cgu_name = match &work {
+                    WorkItem::Optimize(m) if m.kind == ModuleKind::Regular => m.name.clone(),
+                    WorkItem::LTO(m) => m.name().to_owned(),
+                    _ => return None,
+                };
+                Some((cgu_timings, cgu_name, Instant::now()))
+            });
+            let result = execute_work_item(&cgcx, work);
+            if let Some((cgu_timings, cgu_name, start_time)) = cgu_timing {
+                cgu_timings.lock().unwrap().record(&cgu_name, start_time.elapsed());
+            }
+            Some(result)
         };
     })
     .expect("failed to spawn thread");
@@ -1871,6 +1949,7 @@ pub struct OngoingCodegen<B: ExtraBackendMethods> {
     pub shared_emitter_main: SharedEmitterMain,
     pub output_filenames: Arc<OutputFilenames>,
     pub coordinator: Coordinator<B>,
+    pub cgu_timings: Option<Arc<Mutex<CguTimings>>>,
 }
 
 impl<B: ExtraBackendMethods> OngoingCodegen<B> {
@@ -1897,6 +1976,14 @@ pub fn join(self, sess: &Session) -> (CodegenResults, FxHashMap<WorkProductId, W
             copy_all_cgu_workproducts_to_incr_comp_cache_dir(sess, &compiled_modules);
         produce_final_output_artifacts(sess, &compiled_modules, &self.output_filenames);
 
+        if let (Some(cgu_timings), Some(path)) =
+            (&self.cgu_timings, &sess.opts.unstable_opts.dump_cgu_timings)
+        {
+            if let Err(error) = cgu_timings.lock().unwrap().write(path) {
+                sess.emit_err(errors::CguTimingsWriteFailure { path: path.clone(), error });
+            }
+        }
+
         // FIXME: time_llvm_passes support - does this use a global context or
         // something?
         if sess.codegen_units() == 1 && sess.time_llvm_passes() {
@@ -30,6 +30,7 @@
 use rustc_middle::middle::lang_items;
 use rustc_middle::mir::mono::{CodegenUnit, CodegenUnitNameBuilder, MonoItem};
 use rustc_middle::ty::layout::{HasTyCtxt, LayoutOf, TyAndLayout};
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_session::cgu_reuse_tracker::CguReuse;
@@ -681,6 +682,24 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         codegen_units.iter().map(|cgu| determine_cgu_reuse(tcx, &cgu)).collect::<Vec<_>>()
     });
 
+    // With `-Zdump-cgu-timings` the time spent on each codegen unit is written together with the
+    // items in it. They are named by the paths of their definitions, like in
+    // `-Zcgu-partitioning-profile`, so the file can guide the partitioning of later compilations.
+    let cgu_timings = &ongoing_codegen.cgu_timings;
+    if let Some(cgu_timings) = cgu_timings {
+        let mut cgu_timings = cgu_timings.lock().unwrap();
+        for cgu in codegen_units {
+            let mut items: Vec<String> = cgu
+                .items()
+                .keys()
+                .map(|item| with_no_trimmed_paths!(tcx.def_path_str(item.def_id())))
+                .collect();
+            items.sort_unstable();
+            items.dedup();
+            cgu_timings.set_items(cgu.name().as_str(), items);
+        }
+    }
+
     let mut total_codegen_time = Duration::new(0, 0);
     let start_rss = tcx.sess.time_passes().then(|| get_resident_set_size());
 
@@ -709,7 +728,15 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
 
             let pre_compiled_cgus = par_iter(cgus)
                 .map(|(i, _)| {
+                    let cgu_start_time = cgu_timings.as_ref().map(|_| Instant::now());
                     let module = backend.compile_codegen_unit(tcx, codegen_units[i].name());
+                    if let (Some(cgu_timings), Some(cgu_start_time)) = (cgu_timings, cgu_start_time)
+                    {
+                        cgu_timings
+                            .lock()
+                            .unwrap()
+                            .record(codegen_units[i].name().as_str(), cgu_start_time.elapsed());
+                    }
                     (i, module)
                 })
                 .collect();
@@ -737,6 +764,12 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
                     let start_time = Instant::now();
                     let module = backend.compile_codegen_unit(tcx, cgu.name());
                     total_codegen_time += start_time.elapsed();
+                    if let Some(cgu_timings) = cgu_timings {
+                        cgu_timings
+                            .lock()
+                            .unwrap()
+                            .record(cgu.name().as_str(), start_time.elapsed());
+                    }
                     module
                 };
                 // This will unwind if there are errors, which triggers our `AbortCodegenOnDrop`
@@ -955,36 +988,65 @@ pub fn new(tcx: TyCtxt<'_>, target_cpu: String) -> CrateInfo {
 pub fn provide(providers: &mut Providers) {
     providers.backend_optimization_level = |tcx, cratenum| {
         let for_speed = match tcx.sess.opts.optimize {
-            // If globally no optimisation is done, #[optimize] has no effect.
+            // If globally no optimisation is done, #[optimize(speed)] has no effect.
             //
             // This is done because if we ended up "upgrading" to `-O2` here, we’d populate the
             // pass manager and it is likely that some module-wide passes (such as inliner or
             // cross-function constant propagation) would ignore the `optnone` annotation we put
             // on the functions, thus necessarily involving these functions into optimisations.
-            config::OptLevel::No => return config::OptLevel::No,
+            // `#[optimize(level = "...")]` explicitly asks for this though.
+            config::OptLevel::No => None,
             // If globally optimise-speed is already specified, just use that level.
-            config::OptLevel::Less => return config::OptLevel::Less,
-            config::OptLevel::Default => return config::OptLevel::Default,
-            config::OptLevel::Aggressive => return config::OptLevel::Aggressive,
+            config::OptLevel::Less => None,
+            config::OptLevel::Default => None,
+            config::OptLevel::Aggressive => None,
             // If globally optimize-for-size has been requested, use -O2 instead (if optimize(size)
             // are present).
-            config::OptLevel::Size => config::OptLevel::Default,
-            config::OptLevel::SizeMin => config::OptLevel::Default,
+            config::OptLevel::Size => Some(config::OptLevel::Default),
+            config::OptLevel::SizeMin => Some(config::OptLevel::Default),
         };
 
+        let mut opt_level = tcx.sess.opts.optimize;
         let (defids, _) = tcx.collect_and_partition_mono_items(cratenum);
         for id in &*defids {
             let CodegenFnAttrs { optimize, .. } = tcx.codegen_fn_attrs(*id);
-            match optimize {
+            let requested = match *optimize {
                 attr::OptimizeAttr::None => continue,
                 attr::OptimizeAttr::Size => continue,
-                attr::OptimizeAttr::Speed => {
-                    return for_speed;
-                }
-            }
+                attr::OptimizeAttr::Speed => match for_speed {
+                    Some(for_speed) => for_speed,
+                    None => continue,
+                },
+                attr::OptimizeAttr::Level(level) => level,
+            };
+            opt_level = pipeline_opt_level(opt_level, requested);
         }
-        tcx.sess.opts.optimize
+        opt_level
+    };
+}
+
+/// Returns the optimization level of the pass pipeline for a module optimized at `current` which
+/// also contains a function optimized at `requested`. Functions are optimized for size by the
+/// `-O2` pipeline with size attributes on these functions, and at lower levels than the pipeline
+/// with the `optnone` attribute, which only exists for `-O0`. Within `-O1` to `-O3` a function
+/// is therefore optimized at the highest level requested in its crate.
+fn pipeline_opt_level(current: config::OptLevel, requested: config::OptLevel) -> config::OptLevel {
+    use config::OptLevel::*;
+
+    let rank = |opt_level| match opt_level {
+        No => 0,
+        Less => 1,
+        Size | SizeMin => 2,
+        Default => 3,
+        Aggressive => 4,
     };
+    if rank(requested) <= rank(current) {
+        current
+    } else if let Size | SizeMin = requested {
+        Default
+    } else {
+        requested
+    }
 }
 
 fn determine_cgu_reuse<'tcx>(tcx: TyCtxt<'tcx>, cgu: &CodegenUnit<'tcx>) -> CguReuse {
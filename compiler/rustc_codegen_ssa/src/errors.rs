@@ -468,6 +468,20 @@ pub struct FailedToWrite {
     pub error: Error,
 }
 
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_size_report_read_failure)]
+pub struct SizeReportReadFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_size_report_no_symbols)]
+#[help]
+pub struct SizeReportNoSymbols {
+    pub path: PathBuf,
+}
+
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_unable_to_write_debugger_visualizer)]
 pub struct UnableToWriteDebuggerVisualizer {
@@ -534,3 +548,10 @@ pub struct ReadFileError {
 #[derive(Diagnostic)]
 #[diag(codegen_ssa_unsupported_link_self_contained)]
 pub struct UnsupportedLinkSelfContained;
+
+#[derive(Diagnostic)]
+#[diag(codegen_ssa_cgu_timings_write_failure)]
+pub struct CguTimingsWriteFailure {
+    pub path: PathBuf,
+    pub error: Error,
+}
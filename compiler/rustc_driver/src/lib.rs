@@ -667,6 +667,7 @@ fn print_crate_info(
             TargetSpec => {
                 println!("{}", serde_json::to_string_pretty(&sess.target.to_json()).unwrap());
             }
+            TargetSpecDiff => print_target_spec_diff(sess),
             FileNames | CrateName => {
                 let input = input.unwrap_or_else(|| {
                     early_error(ErrorOutputType::default(), "no input file provided")
@@ -753,6 +754,60 @@ fn print_crate_info(
     Compilation::Stop
 }
 
+/// Prints the keys in which the current target spec differs from a built-in target, either the one
+/// passed with `-Z target-spec-diff-base` or the one with the same `llvm-target`.
+fn print_target_spec_diff(sess: &Session) {
+    use rustc_target::spec::{Target, TargetTriple, TARGETS};
+
+    let base_name = match sess.opts.unstable_opts.target_spec_diff_base {
+        Some(ref name) => {
+            if !TARGETS.contains(&&**name) {
+                early_error(
+                    ErrorOutputType::default(),
+                    &format!("`{}` is not a built-in target", name),
+                );
+            }
+            name.clone()
+        }
+        None => {
+            let llvm_target = &sess.target.llvm_target;
+            // Prefer the built-in target named like the LLVM target, if there are several.
+            let mut candidates = TARGETS
+                .iter()
+                .copied()
+                .filter(|&name| {
+                    Target::expect_builtin(&TargetTriple::from_triple(name)).llvm_target
+                        == *llvm_target
+                })
+                .collect::<Vec<_>>();
+            candidates.sort_unstable_by_key(|&name| (name != &**llvm_target, name));
+            match candidates.first() {
+                Some(name) => name.to_string(),
+                None => early_error(
+                    ErrorOutputType::default(),
+                    &format!(
+                        "no built-in target has the `llvm-target` `{}`, pass the built-in target \
+                         to compare against with `-Z target-spec-diff-base`",
+                        llvm_target
+                    ),
+                ),
+            }
+        }
+    };
+
+    let base = Target::expect_builtin(&TargetTriple::from_triple(&base_name));
+    println!("--- built-in target `{}`", base_name);
+    println!("+++ {}", sess.opts.target_triple);
+    for (key, (base_value, value)) in sess.target.json_diff(&base) {
+        if let Some(base_value) = base_value {
+            println!("-{}: {}", key, base_value);
+        }
+        if let Some(value) = value {
+            println!("+{}: {}", key, value);
+        }
+    }
+}
+
 /// Prints version information
 ///
 /// NOTE: this is a macro to support drivers built at a different time than the main `rustc_driver` crate.
@@ -165,6 +165,11 @@ codegen_ssa_link_script_write_failure = failed to write link script to {$path}:
 
 codegen_ssa_failed_to_write = failed to write {$path}: {$error}
 
+codegen_ssa_size_report_read_failure = failed to read {$path} for the size report: {$error}
+
+codegen_ssa_size_report_no_symbols = {$path} has no symbol table, so the size report can't attribute any bytes to symbols
+    .help = don't strip symbols from the output when using `-Z link-size-report`
+
 codegen_ssa_unable_to_write_debugger_visualizer = Unable to write debugger visualizer file `{$path}`: {$error}
 
 codegen_ssa_rlib_archive_build_failure = failed to build archive from rlib: {$error}
@@ -186,3 +191,5 @@ codegen_ssa_apple_sdk_error_sdk_path = failed to get {$sdk_name} SDK path: {erro
 codegen_ssa_read_file = failed to read file: {message}
 
 codegen_ssa_unsupported_link_self_contained = option `-C link-self-contained` is not supported on this target
+
+codegen_ssa_cgu_timings_write_failure = failed to write codegen unit timings to {$path}: {$error}
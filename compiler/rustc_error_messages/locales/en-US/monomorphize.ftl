@@ -13,6 +13,15 @@ monomorphize_fatal_error = {$error_message}
 
 monomorphize_unknown_partition_strategy = unknown partitioning strategy
 
+monomorphize_couldnt_read_partitioning_profile =
+    couldn't read partitioning profile `{$path}`: {$error}
+
+monomorphize_invalid_partitioning_profile =
+    invalid partitioning profile `{$path}` at line {$line}: {$error}
+
+monomorphize_couldnt_dump_mono_stats =
+    unexpected error occurred while dumping monomorphization stats: {$error}
+
 monomorphize_symbol_already_defined = symbol `{$symbol}` is already defined
 
 monomorphize_unused_generic_params = item has unused generic parameters
@@ -312,6 +312,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, async_closure, "1.37.0", Some(62290), None),
     /// Alows async functions to be declared, implemented, and used in traits.
     (incomplete, async_fn_in_trait, "1.66.0", Some(91611), None),
+    /// Allows `for await` loops over async iterators.
+    (active, async_for_loop, "1.67.0", Some(79024), None),
     /// Allows `extern "C-unwind" fn` to enable unwinding across ABI boundaries.
     (active, c_unwind, "1.52.0", Some(74990), None),
     /// Allows using C-variadics.
@@ -382,6 +384,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, doc_cfg, "1.21.0", Some(43781), None),
     /// Allows `#[doc(cfg_hide(...))]`.
     (active, doc_cfg_hide, "1.57.0", Some(43781), None),
+    /// Allows `#[doc(example = "path")]`.
+    (active, doc_example, "1.67.0", None, None),
     /// Allows `#[doc(masked)]`.
     (active, doc_masked, "1.21.0", Some(44027), None),
     /// Allows `dyn* Trait` objects.
@@ -450,7 +450,7 @@ pub struct BuiltinAttribute {
     gated!(no_core, CrateLevel, template!(Word), WarnFollowing, experimental!(no_core)),
     // RFC 2412
     gated!(
-        optimize, Normal, template!(List: "size|speed"), ErrorPreceding, optimize_attribute,
+        optimize, Normal, template!(List: r#"size|speed|level = "0|1|2|3|s|z""#), ErrorPreceding, optimize_attribute,
         experimental!(optimize),
     ),
 
@@ -36,6 +36,7 @@
 use rustc_middle::ty::util::{Discr, IntTypeExt};
 use rustc_middle::ty::ReprOptions;
 use rustc_middle::ty::{self, AdtKind, Const, DefIdTree, IsSuggestable, Ty, TyCtxt};
+use rustc_session::config::OptLevel;
 use rustc_session::lint;
 use rustc_session::parse::feature_err;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
@@ -2037,6 +2038,24 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, did: DefId) -> CodegenFnAttrs {
                     OptimizeAttr::Size
                 } else if list_contains_name(&items, sym::speed) {
                     OptimizeAttr::Speed
+                } else if let Some(level) =
+                    items[0].meta_item().filter(|item| item.has_name(sym::level))
+                {
+                    match level.value_str().as_ref().map(Symbol::as_str) {
+                        Some("0") => OptimizeAttr::Level(OptLevel::No),
+                        Some("1") => OptimizeAttr::Level(OptLevel::Less),
+                        Some("2") => OptimizeAttr::Level(OptLevel::Default),
+                        Some("3") => OptimizeAttr::Level(OptLevel::Aggressive),
+                        Some("s") => OptimizeAttr::Level(OptLevel::Size),
+                        Some("z") => OptimizeAttr::Level(OptLevel::SizeMin),
+                        _ => {
+                            err(
+                                level.span,
+                                "expected `level = \"0\"`, `\"1\"`, `\"2\"`, `\"3\"`, `\"s\"` or `\"z\"`",
+                            );
+                            OptimizeAttr::None
+                        }
+                    }
                 } else {
                     err(items[0].span(), "invalid argument");
                     OptimizeAttr::None
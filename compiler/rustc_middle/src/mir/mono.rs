@@ -56,6 +56,15 @@ pub fn is_user_defined(&self) -> bool {
         }
     }
 
+    /// Returns the definition this item is an instance of.
+    pub fn def_id(&self) -> DefId {
+        match *self {
+            MonoItem::Fn(instance) => instance.def_id(),
+            MonoItem::Static(def_id) => def_id,
+            MonoItem::GlobalAsm(item_id) => item_id.owner_id.to_def_id(),
+        }
+    }
+
     pub fn size_estimate(&self, tcx: TyCtxt<'tcx>) -> usize {
         match *self {
             MonoItem::Fn(instance) => {
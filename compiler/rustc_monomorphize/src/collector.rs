@@ -221,6 +221,11 @@ pub struct InliningMap<'tcx> {
     // Contains one bit per mono item in the `targets` field. That bit
     // is true if that mono item needs to be inlined into every CGU.
     inlines: GrowableBitSet<usize>,
+
+    // Maps a mono item to the mono item which referenced it when it was
+    // first collected. Roots have no entry. This is only recorded for
+    // `-Zdump-mono-stats`.
+    requested_by: FxHashMap<MonoItem<'tcx>, MonoItem<'tcx>>,
 }
 
 /// Struct to store mono items in each collecting and if they should
@@ -267,6 +272,7 @@ fn new() -> InliningMap<'tcx> {
             index: FxHashMap::default(),
             targets: Vec::new(),
             inlines: GrowableBitSet::with_capacity(1024),
+            requested_by: FxHashMap::default(),
         }
     }
 
@@ -310,6 +316,12 @@ pub fn with_inlining_candidates<F>(&self, source: MonoItem<'tcx>, mut f: F)
         }
     }
 
+    /// Returns the mono item which caused `item` to be collected, or `None` if `item` is a root
+    /// or `-Zdump-mono-stats` is disabled.
+    pub fn requested_by(&self, item: MonoItem<'tcx>) -> Option<MonoItem<'tcx>> {
+        self.requested_by.get(&item).copied()
+    }
+
     // Internally iterate over all items and the things each accesses.
     pub fn iter_accesses<F>(&self, mut f: F)
     where
@@ -347,6 +359,7 @@ pub fn collect_crate_mono_items(
                 collect_items_rec(
                     tcx,
                     dummy_spanned(root),
+                    None,
                     visited,
                     &mut recursion_depths,
                     recursion_limit,
@@ -400,10 +413,14 @@ fn collect_roots(tcx: TyCtxt<'_>, mode: MonoItemCollectionMode) -> Vec<MonoItem<
 
 /// Collect all monomorphized items reachable from `starting_point`, and emit a note diagnostic if a
 /// post-monorphization error is encountered during a collection step.
-#[instrument(skip(tcx, visited, recursion_depths, recursion_limit, inlining_map), level = "debug")]
+#[instrument(
+    skip(tcx, requested_by, visited, recursion_depths, recursion_limit, inlining_map),
+    level = "debug"
+)]
 fn collect_items_rec<'tcx>(
     tcx: TyCtxt<'tcx>,
     starting_point: Spanned<MonoItem<'tcx>>,
+    requested_by: Option<MonoItem<'tcx>>,
     visited: MTRef<'_, MTLock<FxHashSet<MonoItem<'tcx>>>>,
     recursion_depths: &mut DefIdMap<usize>,
     recursion_limit: Limit,
@@ -414,6 +431,12 @@ fn collect_items_rec<'tcx>(
         return;
     }
 
+    if let Some(requested_by) = requested_by {
+        if tcx.sess.opts.unstable_opts.dump_mono_stats.enabled() {
+            inlining_map.lock_mut().requested_by.insert(starting_point.node, requested_by);
+        }
+    }
+
     let mut neighbors = MonoItems { compute_inlining: true, tcx, items: Vec::new() };
     let recursion_depth_reset;
 
@@ -532,7 +555,15 @@ fn collect_items_rec<'tcx>(
     inlining_map.lock_mut().record_accesses(starting_point.node, &neighbors.items);
 
     for (neighbour, _) in neighbors.items {
-        collect_items_rec(tcx, neighbour, visited, recursion_depths, recursion_limit, inlining_map);
+        collect_items_rec(
+            tcx,
+            neighbour,
+            Some(starting_point.node),
+            visited,
+            recursion_depths,
+            recursion_limit,
+            inlining_map,
+        );
     }
 
     if let Some((def_id, depth)) = recursion_depth_reset {
@@ -70,6 +70,27 @@ pub struct LargeAssignmentsLint {
 #[diag(monomorphize_unknown_partition_strategy)]
 pub struct UnknownPartitionStrategy;
 
+#[derive(Diagnostic)]
+#[diag(monomorphize_couldnt_read_partitioning_profile)]
+pub struct CouldntReadPartitioningProfile {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_invalid_partitioning_profile)]
+pub struct InvalidPartitioningProfile {
+    pub path: PathBuf,
+    pub line: usize,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(monomorphize_couldnt_dump_mono_stats)]
+pub struct CouldntDumpMonoStats {
+    pub error: String,
+}
+
 #[derive(Diagnostic)]
 #[diag(monomorphize_symbol_already_defined)]
 pub struct SymbolAlreadyDefined {
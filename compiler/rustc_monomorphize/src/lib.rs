@@ -18,6 +18,7 @@
 
 mod collector;
 mod errors;
+mod mono_stats;
 mod partitioning;
 mod polymorphize;
 mod util;
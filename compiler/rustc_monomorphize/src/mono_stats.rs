@@ -0,0 +1,184 @@
+//! The report written by `-Zdump-mono-stats`.
+//!
+//! Every collected mono item is grouped with the other instantiations of the same definition, so
+//! it can be seen which generic definitions contribute the most code to the crate. For each
+//! instantiation the report also names the crate whose code caused it to be instantiated and the
+//! chain of mono items which first referenced it during collection.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_middle::mir::mono::MonoItem;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::{DumpMonoStatsFormat, SwitchWithOptPath};
+use serde::Serialize;
+
+use crate::collector::InliningMap;
+use crate::errors::CouldntDumpMonoStats;
+
+#[derive(Serialize)]
+struct DefinitionStats {
+    /// The path of the definition.
+    name: String,
+    /// The crate containing the definition.
+    #[serde(rename = "crate")]
+    krate: String,
+    instantiation_count: usize,
+    /// The sum of the size estimates of all instantiations.
+    total_size_estimate: usize,
+    instantiations: Vec<InstantiationStats>,
+}
+
+#[derive(Serialize)]
+struct InstantiationStats {
+    name: String,
+    size_estimate: usize,
+    /// The crate whose code caused this instantiation.
+    caused_by: String,
+    /// The mono items which first referenced this instantiation, starting at a root.
+    requested_by: Vec<String>,
+}
+
+pub(crate) fn dump_mono_stats<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+) {
+    let SwitchWithOptPath::Enabled(ref output_dir) = tcx.sess.opts.unstable_opts.dump_mono_stats
+    else {
+        return;
+    };
+
+    let _prof_timer = tcx.prof.generic_activity("dump_mono_stats");
+
+    let stats = collect_stats(tcx, items, inlining_map);
+    let output_dir = output_dir.as_deref().unwrap_or(Path::new("."));
+    if let Err(err) = write_stats(tcx, &stats, output_dir) {
+        tcx.sess.emit_fatal(CouldntDumpMonoStats { error: err.to_string() });
+    }
+}
+
+fn collect_stats<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+) -> Vec<DefinitionStats> {
+    let mut items_per_def_id: FxHashMap<DefId, Vec<MonoItem<'tcx>>> = Default::default();
+    for &item in items {
+        items_per_def_id.entry(item.def_id()).or_default().push(item);
+    }
+
+    let mut stats: Vec<_> = items_per_def_id
+        .into_iter()
+        .map(|(def_id, items)| {
+            let mut instantiations: Vec<_> = items
+                .into_iter()
+                .map(|item| InstantiationStats {
+                    name: with_no_trimmed_paths!(item.to_string()),
+                    size_estimate: item.size_estimate(tcx),
+                    caused_by: tcx.crate_name(causing_crate(inlining_map, item)).to_string(),
+                    requested_by: request_chain(inlining_map, item),
+                })
+                .collect();
+            instantiations.sort_by(|a, b| {
+                b.size_estimate.cmp(&a.size_estimate).then_with(|| a.name.cmp(&b.name))
+            });
+
+            DefinitionStats {
+                name: with_no_trimmed_paths!(tcx.def_path_str(def_id)),
+                krate: tcx.crate_name(def_id.krate).to_string(),
+                instantiation_count: instantiations.len(),
+                total_size_estimate: instantiations.iter().map(|inst| inst.size_estimate).sum(),
+                instantiations,
+            }
+        })
+        .collect();
+
+    // Put the biggest contributors first.
+    stats.sort_by(|a, b| {
+        b.total_size_estimate.cmp(&a.total_size_estimate).then_with(|| a.name.cmp(&b.name))
+    });
+
+    stats
+}
+
+/// Walks up the chain of mono items which caused `item` to be collected and returns the first
+/// crate other than the one defining `item`. For example a `Vec::<T>::push` instantiation which
+/// was requested by `Vec::<T>::extend`, which in turn was requested by a function in some crate
+/// `foo`, was caused by `foo`.
+fn causing_crate<'tcx>(inlining_map: &InliningMap<'tcx>, item: MonoItem<'tcx>) -> CrateNum {
+    let defining_crate = item.def_id().krate;
+    let mut current = item;
+    while let Some(parent) = inlining_map.requested_by(current) {
+        let krate = parent.def_id().krate;
+        if krate != defining_crate {
+            return krate;
+        }
+        current = parent;
+    }
+    // Collection always starts at items of the local crate.
+    LOCAL_CRATE
+}
+
+/// Returns the mono items which lead to `item` being collected, starting with a root.
+fn request_chain<'tcx>(inlining_map: &InliningMap<'tcx>, item: MonoItem<'tcx>) -> Vec<String> {
+    let mut chain = vec![];
+    let mut current = item;
+    while let Some(parent) = inlining_map.requested_by(current) {
+        chain.push(with_no_trimmed_paths!(parent.to_string()));
+        current = parent;
+    }
+    chain.reverse();
+    chain
+}
+
+fn write_stats(tcx: TyCtxt<'_>, stats: &[DefinitionStats], output_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(output_dir)?;
+
+    let format = tcx.sess.opts.unstable_opts.dump_mono_stats_format;
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    let output_path = output_dir.join(format!("{}.mono_stats.{}", crate_name, format.extension()));
+    let mut file = BufWriter::new(File::create(&output_path)?);
+
+    match format {
+        DumpMonoStatsFormat::Json => serde_json::to_writer_pretty(&mut file, stats)?,
+        DumpMonoStatsFormat::Text => {
+            let instantiation_count: usize = stats.iter().map(|def| def.instantiation_count).sum();
+            let total_size_estimate: usize = stats.iter().map(|def| def.total_size_estimate).sum();
+            writeln!(
+                file,
+                "monomorphization statistics for `{}`: {} instantiations of {} definitions, \
+                 total estimated size {}",
+                crate_name,
+                instantiation_count,
+                stats.len(),
+                total_size_estimate,
+            )?;
+
+            for def in stats {
+                writeln!(file)?;
+                writeln!(
+                    file,
+                    "{} (defined in `{}`): {} instantiations, total estimated size {}",
+                    def.name, def.krate, def.instantiation_count, def.total_size_estimate,
+                )?;
+                for inst in &def.instantiations {
+                    writeln!(
+                        file,
+                        "    {} estimated size {}, caused by `{}`",
+                        inst.name, inst.size_estimate, inst.caused_by,
+                    )?;
+                    if !inst.requested_by.is_empty() {
+                        writeln!(file, "        requested by {}", inst.requested_by.join(" -> "))?;
+                    }
+                }
+            }
+        }
+    }
+
+    file.flush()
+}
@@ -1,4 +1,5 @@
 use std::cmp;
+use std::fmt;
 
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def_id::LOCAL_CRATE;
@@ -7,6 +8,7 @@
 
 use super::PartitioningCx;
 use crate::partitioning::PreInliningPartitioning;
+use crate::partitioning::profile::CguAffinity;
 
 pub fn merge_codegen_units<'tcx>(
     cx: &PartitioningCx<'_, 'tcx>,
@@ -30,31 +32,88 @@ pub fn merge_codegen_units<'tcx>(
     let mut cgu_contents: FxHashMap<Symbol, Vec<Symbol>> =
         codegen_units.iter().map(|cgu| (cgu.name(), vec![cgu.name()])).collect();
 
-    // Merge the two smallest codegen units until the target size is reached.
+    // What the profile says about the items of each codegen unit, if there is a profile.
+    let mut affinities: FxHashMap<Symbol, CguAffinity> = match &cx.profile {
+        Some(profile) => codegen_units
+            .iter()
+            .map(|cgu| (cgu.name(), profile.cgu_affinity(cx.tcx, cgu)))
+            .collect(),
+        None => Default::default(),
+    };
+
+    // How long each codegen unit is expected to take to compile, if the profile
+    // contains timings of an earlier compilation. Otherwise codegen units are
+    // compared by their size estimates.
+    let mut costs: Option<FxHashMap<Symbol, u64>> =
+        cx.profile.as_ref().and_then(|profile| profile.cgu_costs(cx.tcx, codegen_units));
+
+    // The merges done below and why they were done, printed with `-Zprint-mono-items`.
+    let mut decisions = vec![];
+
+    // Merge the smallest codegen unit into another one until the target size
+    // is reached. Without a profile this is always the second smallest one. If
+    // the profile contains timings, the codegen units expected to compile the
+    // fastest are considered the smallest.
     while codegen_units.len() > cx.target_cgu_count {
         // Sort small cgus to the back
-        codegen_units.sort_by_cached_key(|cgu| cmp::Reverse(cgu.size_estimate()));
+        codegen_units.sort_by_cached_key(|cgu| {
+            cmp::Reverse(match &costs {
+                Some(costs) => costs[&cgu.name()],
+                None => cgu.size_estimate() as u64,
+            })
+        });
         let mut smallest = codegen_units.pop().unwrap();
-        let second_smallest = codegen_units.last_mut().unwrap();
-
-        // Move the mono-items from `smallest` to `second_smallest`
-        second_smallest.modify_size_estimate(smallest.size_estimate());
+        let (partner_index, reason) = affinities
+            .get(&smallest.name())
+            .and_then(|affinity| merge_partner(codegen_units, &affinities, affinity))
+            .unwrap_or_else(|| {
+                let reason =
+                    if costs.is_some() { MergeReason::Cheapest } else { MergeReason::Smallest };
+                (codegen_units.len() - 1, reason)
+            });
+        let partner = &mut codegen_units[partner_index];
+
+        // Move the mono-items from `smallest` to `partner`
+        partner.modify_size_estimate(smallest.size_estimate());
         for (k, v) in smallest.items_mut().drain() {
-            second_smallest.items_mut().insert(k, v);
+            partner.items_mut().insert(k, v);
         }
 
-        // Record that `second_smallest` now contains all the stuff that was in
+        // Record that `partner` now contains all the stuff that was in
         // `smallest` before.
         let mut consumed_cgu_names = cgu_contents.remove(&smallest.name()).unwrap();
-        cgu_contents.get_mut(&second_smallest.name()).unwrap().append(&mut consumed_cgu_names);
+        cgu_contents.get_mut(&partner.name()).unwrap().append(&mut consumed_cgu_names);
+        if let Some(consumed_affinity) = affinities.remove(&smallest.name()) {
+            affinities.get_mut(&partner.name()).unwrap().merge(consumed_affinity);
+        }
+        if let Some(costs) = &mut costs {
+            let consumed_cost = costs.remove(&smallest.name()).unwrap();
+            *costs.get_mut(&partner.name()).unwrap() += consumed_cost;
+        }
 
         debug!(
-            "CodegenUnit {} merged into CodegenUnit {}",
+            "CodegenUnit {} merged into CodegenUnit {} ({})",
             smallest.name(),
-            second_smallest.name()
+            partner.name(),
+            reason
         );
+        decisions.push(format!("{} into {}: {}", smallest.name(), partner.name(), reason));
     }
 
+    // The original codegen units contained in each merged one, so they can be
+    // named after the codegen units got their final names.
+    let merged_contents: Vec<Option<String>> = codegen_units
+        .iter()
+        .map(|cgu| {
+            let contents = &cgu_contents[&cgu.name()];
+            (contents.len() > 1).then(|| {
+                let mut contents: Vec<&str> = contents.iter().map(|s| s.as_str()).collect();
+                contents.sort_unstable();
+                contents.join(", ")
+            })
+        })
+        .collect();
+
     let cgu_name_builder = &mut CodegenUnitNameBuilder::new(cx.tcx);
 
     if cx.tcx.sess.opts.incremental.is_some() {
@@ -101,6 +160,86 @@ pub fn merge_codegen_units<'tcx>(
             cgu.set_name(numbered_codegen_unit_name(cgu_name_builder, index));
         }
     }
+
+    if cx.tcx.sess.opts.unstable_opts.print_mono_items.is_some() {
+        for decision in decisions {
+            println!("MERGE_CGU {}", decision);
+        }
+        for (cgu, contents) in codegen_units.iter().zip(merged_contents) {
+            if let Some(contents) = contents {
+                println!("MERGED_CGU {} contains {}", cgu.name(), contents);
+            }
+        }
+    }
+}
+
+/// Why a codegen unit was merged into another one.
+enum MergeReason {
+    /// Both contain items of these groups of items which change together.
+    SharedGroups(Vec<Symbol>),
+    /// Both contain hot items.
+    Hot,
+    /// Nothing in the profile relates the two codegen units, so the smallest
+    /// ones were merged.
+    Smallest,
+    /// Nothing in the profile relates the two codegen units, so the ones which
+    /// took the least time to compile according to the profile were merged.
+    Cheapest,
+}
+
+impl fmt::Display for MergeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeReason::SharedGroups(groups) => {
+                let groups: Vec<String> =
+                    groups.iter().map(|group| format!("`{}`", group)).collect();
+                write!(f, "both contain items of the change group(s) {}", groups.join(", "))
+            }
+            MergeReason::Hot => write!(f, "both contain hot items"),
+            MergeReason::Smallest => write!(f, "they are the smallest codegen units"),
+            MergeReason::Cheapest => write!(f, "they are the fastest codegen units to compile"),
+        }
+    }
+}
+
+/// Picks the codegen unit the smallest one is merged into. The one sharing the
+/// most change groups is preferred, as that keeps items which change together in
+/// the same codegen unit, which maximizes reuse in incremental compilation. Next
+/// hot codegen units are kept together, so the hot items can be inlined into each
+/// other. Ties are broken in favor of the smaller codegen unit. Returns `None`
+/// if the profile doesn't relate the smallest codegen unit to any other.
+fn merge_partner(
+    codegen_units: &[CodegenUnit<'_>],
+    affinities: &FxHashMap<Symbol, CguAffinity>,
+    affinity: &CguAffinity,
+) -> Option<(usize, MergeReason)> {
+    // `codegen_units` is sorted by decreasing size, so searching from the back
+    // finds the smallest of equally good candidates first.
+    let mut best: Option<(usize, Vec<Symbol>, bool)> = None;
+    for (index, cgu) in codegen_units.iter().enumerate().rev() {
+        let other = &affinities[&cgu.name()];
+        let mut shared_groups: Vec<Symbol> =
+            affinity.groups.intersection(&other.groups).copied().collect();
+        let both_hot = affinity.hot && other.hot;
+        let is_better = match best {
+            Some((_, ref best_groups, best_hot)) => {
+                (shared_groups.len(), both_hot) > (best_groups.len(), best_hot)
+            }
+            None => true,
+        };
+        if is_better {
+            shared_groups.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+            best = Some((index, shared_groups, both_hot));
+        }
+    }
+
+    match best {
+        Some((index, shared_groups, _)) if !shared_groups.is_empty() => {
+            Some((index, MergeReason::SharedGroups(shared_groups)))
+        }
+        Some((index, _, true)) => Some((index, MergeReason::Hot)),
+        _ => None,
+    }
 }
 
 fn numbered_codegen_unit_name(
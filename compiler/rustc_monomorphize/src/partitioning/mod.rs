@@ -94,6 +94,7 @@
 
 mod default;
 mod merging;
+mod profile;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync;
@@ -109,11 +110,13 @@
 use crate::collector::InliningMap;
 use crate::collector::{self, MonoItemCollectionMode};
 use crate::errors::{SymbolAlreadyDefined, UnknownPartitionStrategy};
+use crate::partitioning::profile::PartitioningProfile;
 
 pub struct PartitioningCx<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     target_cgu_count: usize,
     inlining_map: &'a InliningMap<'tcx>,
+    profile: Option<PartitioningProfile>,
 }
 
 trait Partitioner<'tcx> {
@@ -165,7 +168,14 @@ pub fn partition<'tcx>(
     let _prof_timer = tcx.prof.generic_activity("cgu_partitioning");
 
     let mut partitioner = get_partitioner(tcx);
-    let cx = &PartitioningCx { tcx, target_cgu_count: max_cgu_count, inlining_map };
+    let profile = tcx
+        .sess
+        .opts
+        .unstable_opts
+        .cgu_partitioning_profile
+        .as_deref()
+        .map(|path| PartitioningProfile::load(tcx, path));
+    let cx = &PartitioningCx { tcx, target_cgu_count: max_cgu_count, inlining_map, profile };
     // In the first step, we place all regular monomorphizations into their
     // respective 'home' codegen unit. Regular monomorphizations are all
     // functions and statics defined in the local crate.
@@ -391,6 +401,8 @@ fn collect_and_partition_mono_items<'tcx>(
         )
     });
 
+    crate::mono_stats::dump_mono_stats(tcx, &items, &inlining_map);
+
     if tcx.prof.enabled() {
         // Record CGU size estimates for self-profiling.
         for cgu in codegen_units {
@@ -0,0 +1,229 @@
+//! Profiles guiding the merging of codegen units, as passed with `-Zcgu-partitioning-profile`.
+//!
+//! A profile is a text file with one directive per line. Empty lines and lines starting with `#`
+//! are ignored. Items are named by the path of their definition as printed by
+//! `-Zdump-mono-stats`, so a directive applies to all instantiations of a definition.
+//!
+//! ```text
+//! # `parse_expr` is hot and should be available for inlining into other hot code.
+//! hot parser::parse_expr
+//! # `Lexer::next_token` and `Lexer::peek` usually change together.
+//! group lexer lexer::Lexer::next_token lexer::Lexer::peek
+//! ```
+//!
+//! Codegen units containing items of the same group are preferably merged with each other, so a
+//! change to the group invalidates as few codegen units as possible. Codegen units containing hot
+//! items are preferably merged with each other, so LLVM can inline hot items into each other.
+//!
+//! The profile may also contain the time it took to compile each codegen unit in an earlier
+//! compilation, as written by `-Zdump-cgu-timings`. A `time` directive has the time in seconds,
+//! the name of the codegen unit and the paths of the items in it, separated by tabs as paths may
+//! contain spaces. Codegen units are then compared by how long their items took to compile instead
+//! of by their size estimates.
+//!
+//! ```text
+//! time	0.012000	foo.7rcbfp3g-cgu.0	parser::parse_expr	parser::parse_stmt
+//! ```
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_middle::mir::mono::{CodegenUnit, MonoItem};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::Symbol;
+
+use crate::errors::{CouldntReadPartitioningProfile, InvalidPartitioningProfile};
+
+pub struct PartitioningProfile {
+    hot: FxHashSet<String>,
+    /// The groups of items which change together, indexed by the path of their members.
+    groups: FxHashMap<String, Vec<Symbol>>,
+    /// The time spent on each codegen unit of an earlier compilation and the paths of the items
+    /// in it.
+    cgu_timings: Vec<(Duration, Vec<String>)>,
+}
+
+/// What the profile says about the items of a codegen unit.
+#[derive(Default)]
+pub struct CguAffinity {
+    pub groups: FxHashSet<Symbol>,
+    pub hot: bool,
+}
+
+impl CguAffinity {
+    pub fn merge(&mut self, other: CguAffinity) {
+        self.groups.extend(other.groups);
+        self.hot |= other.hot;
+    }
+}
+
+impl PartitioningProfile {
+    pub fn load(tcx: TyCtxt<'_>, path: &Path) -> PartitioningProfile {
+        let contents = fs::read_to_string(path).unwrap_or_else(|error| {
+            tcx.sess.emit_fatal(CouldntReadPartitioningProfile {
+                path: path.to_owned(),
+                error: error.to_string(),
+            })
+        });
+
+        let mut profile = PartitioningProfile {
+            hot: Default::default(),
+            groups: Default::default(),
+            cgu_timings: Default::default(),
+        };
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let result = match words.next().unwrap() {
+                "hot" => match (words.next(), words.next()) {
+                    (Some(path), None) => {
+                        profile.hot.insert(path.to_owned());
+                        Ok(())
+                    }
+                    _ => Err("expected `hot <path>`".to_owned()),
+                },
+                "group" => match words.next() {
+                    Some(group) => {
+                        let group = Symbol::intern(group);
+                        let mut any_members = false;
+                        for path in words {
+                            profile.groups.entry(path.to_owned()).or_default().push(group);
+                            any_members = true;
+                        }
+                        if any_members {
+                            Ok(())
+                        } else {
+                            Err("expected `group <name> <path>...`".to_owned())
+                        }
+                    }
+                    None => Err("expected `group <name> <path>...`".to_owned()),
+                },
+                "time" => match parse_cgu_timing(line) {
+                    Some(cgu_timing) => {
+                        profile.cgu_timings.push(cgu_timing);
+                        Ok(())
+                    }
+                    None => {
+                        Err("expected `time<TAB><seconds><TAB><codegen unit><TAB><path>...`"
+                            .to_owned())
+                    }
+                },
+                directive => Err(format!("unknown directive `{}`", directive)),
+            };
+
+            if let Err(error) = result {
+                tcx.sess.emit_err(InvalidPartitioningProfile {
+                    path: path.to_owned(),
+                    line: index + 1,
+                    error,
+                });
+            }
+        }
+        tcx.sess.abort_if_errors();
+
+        profile
+    }
+
+    pub fn cgu_affinity<'tcx>(&self, tcx: TyCtxt<'tcx>, cgu: &CodegenUnit<'tcx>) -> CguAffinity {
+        let mut affinity = CguAffinity::default();
+        for &item in cgu.items().keys() {
+            let path = item_path(tcx, item);
+            affinity.hot |= self.hot.contains(&path);
+            if let Some(groups) = self.groups.get(&path) {
+                affinity.groups.extend(groups.iter().copied());
+            }
+        }
+        affinity
+    }
+
+    /// Estimates how long each codegen unit takes to compile, in nanoseconds, from the timings of
+    /// an earlier compilation. Returns `None` if the profile has no timings for any of the items.
+    ///
+    /// The time spent on a codegen unit is attributed to the definitions of the items in it in
+    /// proportion to their size estimates. The items of definitions without a timing, like new
+    /// ones, are assumed to take as long per size estimate as the others do on average.
+    pub fn cgu_costs<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        codegen_units: &[CodegenUnit<'tcx>],
+    ) -> Option<FxHashMap<Symbol, u64>> {
+        if self.cgu_timings.is_empty() {
+            return None;
+        }
+
+        let mut def_sizes: FxHashMap<String, usize> = Default::default();
+        let cgu_items: Vec<(Symbol, Vec<(String, usize)>)> = codegen_units
+            .iter()
+            .map(|cgu| {
+                let items: Vec<(String, usize)> = cgu
+                    .items()
+                    .keys()
+                    .map(|&item| (item_path(tcx, item), item.size_estimate(tcx).max(1)))
+                    .collect();
+                for (path, size) in &items {
+                    *def_sizes.entry(path.clone()).or_default() += size;
+                }
+                (cgu.name(), items)
+            })
+            .collect();
+
+        let mut def_times: FxHashMap<&str, f64> = Default::default();
+        for (time, paths) in &self.cgu_timings {
+            let total_size: usize = paths.iter().filter_map(|path| def_sizes.get(path)).sum();
+            for path in paths {
+                if let Some(&size) = def_sizes.get(path) {
+                    *def_times.entry(path.as_str()).or_default() +=
+                        time.as_nanos() as f64 * size as f64 / total_size as f64;
+                }
+            }
+        }
+
+        let timed_size: usize = def_times.keys().map(|&path| def_sizes[path]).sum();
+        if timed_size == 0 {
+            return None;
+        }
+        let average_time = def_times.values().sum::<f64>() / timed_size as f64;
+
+        Some(
+            cgu_items
+                .into_iter()
+                .map(|(name, items)| {
+                    let cost: f64 = items
+                        .iter()
+                        .map(|(path, size)| match def_times.get(path.as_str()) {
+                            Some(def_time) => def_time * *size as f64 / def_sizes[path] as f64,
+                            None => average_time * *size as f64,
+                        })
+                        .sum();
+                    (name, cost as u64)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Parses a `time` directive, returning the time and the items of the codegen unit.
+fn parse_cgu_timing(line: &str) -> Option<(Duration, Vec<String>)> {
+    let mut fields = line.split('\t');
+    if fields.next()? != "time" {
+        return None;
+    }
+    let secs: f64 = fields.next()?.parse().ok()?;
+    let _cgu_name = fields.next()?;
+    let paths: Vec<String> = fields.map(|path| path.to_owned()).collect();
+    if paths.is_empty() {
+        return None;
+    }
+    Some((Duration::try_from_secs_f64(secs).ok()?, paths))
+}
+
+fn item_path<'tcx>(tcx: TyCtxt<'tcx>, item: MonoItem<'tcx>) -> String {
+    with_no_trimmed_paths!(tcx.def_path_str(item.def_id()))
+}
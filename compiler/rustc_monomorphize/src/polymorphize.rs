@@ -4,16 +4,24 @@
 //! This module implements an analysis of functions, methods and closures to determine which
 //! generic parameters are unused (and eventually, in what ways generic parameters are used - only
 //! for their size, offset of a field, etc.).
+//!
+//! A closure or generator which doesn't use a generic parameter of its parent is shared between
+//! all instantiations of the parent which only differ in that parameter. Its type however still
+//! contains the parameter, so the parent has to consider the parameter used whenever the identity
+//! of the closure type can be observed. This is the case when the closure type is passed to
+//! another function, which may call `type_id` or `type_name` on it, or when a vtable is created
+//! for it.
 
 use rustc_hir::{def::DefKind, def_id::DefId, ConstContext};
 use rustc_index::bit_set::FiniteBitSet;
 use rustc_middle::mir::{
     self,
     visit::{TyContext, Visitor},
-    Constant, ConstantKind, Local, LocalDecl, Location,
+    CastKind, Constant, ConstantKind, Local, LocalDecl, Location, Rvalue, StatementKind,
 };
 use rustc_middle::ty::{
     self,
+    adjustment::PointerCast,
     query::Providers,
     subst::SubstsRef,
     visit::{TypeSuperVisitable, TypeVisitable, TypeVisitor},
@@ -74,8 +82,21 @@ fn unused_generic_params<'tcx>(
         Some(ConstContext::ConstFn) | None => tcx.optimized_mir(def_id),
         Some(_) => tcx.mir_for_ctfe(def_id),
     };
-    let mut vis = MarkUsedGenericParams { tcx, def_id, unused_parameters: &mut unused_parameters };
+    let mut vis = MarkUsedGenericParams {
+        tcx,
+        def_id,
+        unused_parameters: &mut unused_parameters,
+        observes_identity: false,
+    };
     vis.visit_body(body);
+
+    // The layout of a generator contains every local which is live across a suspension point,
+    // even if the body only moves it around.
+    if let Some(generator_layout) = body.generator_layout() {
+        for ty in &generator_layout.field_tys {
+            ty.visit_with(&mut vis);
+        }
+    }
     debug!(?unused_parameters, "(end)");
 
     // Emit errors for debugging and testing if enabled.
@@ -233,6 +254,9 @@ struct MarkUsedGenericParams<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
     def_id: DefId,
     unused_parameters: &'a mut FiniteBitSet<u32>,
+    /// Whether the identity of the types currently being visited can be observed, in which case
+    /// all parameters of closures and generators within them are considered used.
+    observes_identity: bool,
 }
 
 impl<'a, 'tcx> MarkUsedGenericParams<'a, 'tcx> {
@@ -251,9 +275,59 @@ fn visit_child_body(&mut self, def_id: DefId, substs: SubstsRef<'tcx>) {
         }
         debug!(?self.unused_parameters);
     }
+
+    /// Visit `value` in a position where the identity of its types can be observed.
+    fn visit_observed<T: TypeVisitable<'tcx>>(&mut self, value: T) {
+        let observes_identity = std::mem::replace(&mut self.observes_identity, true);
+        value.visit_with(self);
+        self.observes_identity = observes_identity;
+    }
+
+    /// Visit the substitutions of a function which is called or referenced by the current item.
+    #[instrument(level = "debug", skip(self))]
+    fn visit_callee(&mut self, def_id: DefId, substs: SubstsRef<'tcx>) {
+        if !substs.has_non_region_param() {
+            return;
+        }
+
+        // Calling a closure or generator only depends on the parameters it uses itself, so its
+        // identity isn't observed. Any other function could observe the identity of the types in
+        // its substitutions, e.g. using `TypeId::of` or `type_name`.
+        let param_env = self.tcx.param_env_reveal_all_normalized(self.def_id);
+        let calls_closure = match ty::Instance::resolve(self.tcx, param_env, def_id, substs) {
+            Ok(Some(instance)) => {
+                self.tcx.is_closure(instance.def_id())
+                    || matches!(instance.def, ty::InstanceDef::ClosureOnceShim { .. })
+            }
+            _ => false,
+        };
+        debug!(?calls_closure);
+
+        if calls_closure {
+            substs.visit_with(self);
+        } else {
+            self.visit_observed(substs);
+        }
+    }
 }
 
 impl<'a, 'tcx> Visitor<'tcx> for MarkUsedGenericParams<'a, 'tcx> {
+    fn visit_body(&mut self, body: &mir::Body<'tcx>) {
+        // Unsizing a closure or generator creates a vtable for it, which may be used to observe
+        // its identity (e.g. `<dyn Any>::type_id`).
+        for data in body.basic_blocks.iter() {
+            for statement in &data.statements {
+                let StatementKind::Assign(assign) = &statement.kind else { continue };
+                let (_, rvalue) = &**assign;
+                if let Rvalue::Cast(CastKind::Pointer(PointerCast::Unsize), operand, _) = rvalue {
+                    self.visit_observed(operand.ty(body, self.tcx));
+                }
+            }
+        }
+
+        self.super_body(body);
+    }
+
     #[instrument(level = "debug", skip(self, local))]
     fn visit_local_decl(&mut self, local: Local, local_decl: &LocalDecl<'tcx>) {
         if local == Local::from_usize(1) {
@@ -290,7 +364,10 @@ fn visit_constant(&mut self, ct: &Constant<'tcx>, location: Location) {
 
                 Visitor::visit_ty(self, ty, TyContext::Location(location));
             }
-            ConstantKind::Val(_, ty) => Visitor::visit_ty(self, ty, TyContext::Location(location)),
+            ConstantKind::Val(_, ty) => match *ty.kind() {
+                ty::FnDef(def_id, substs) => self.visit_callee(def_id, substs),
+                _ => Visitor::visit_ty(self, ty, TyContext::Location(location)),
+            },
         }
     }
 
@@ -336,6 +413,12 @@ fn visit_ty(&mut self, ty: Ty<'tcx>) -> ControlFlow<Self::BreakTy> {
                     return ControlFlow::CONTINUE;
                 }
 
+                if self.observes_identity {
+                    // Instantiations of the closure/generator which only differ in parameters it
+                    // doesn't use are still distinct types.
+                    return substs.visit_with(self);
+                }
+
                 // Consider any generic parameters used by any closures/generators as used in the
                 // parent.
                 self.visit_child_body(def_id, substs);
@@ -744,6 +744,14 @@ fn check_doc_alias(
         }
     }
 
+    fn check_doc_example(&self, meta: &NestedMetaItem) -> bool {
+        if meta.value_str().unwrap_or(kw::Empty) == kw::Empty {
+            self.doc_attr_str_error(meta, "example");
+            return false;
+        }
+        true
+    }
+
     fn check_doc_keyword(&self, meta: &NestedMetaItem, hir_id: HirId) -> bool {
         let doc_keyword = meta.value_str().unwrap_or(kw::Empty);
         if doc_keyword == kw::Empty {
@@ -984,6 +992,13 @@ fn check_doc_attrs(
                             is_valid = false
                         }
 
+                        sym::example
+                            if !self.check_attr_not_crate_level(meta, hir_id, "example")
+                                || !self.check_doc_example(meta) =>
+                        {
+                            is_valid = false
+                        }
+
                         sym::fake_variadic
                             if !self.check_attr_not_crate_level(meta, hir_id, "fake_variadic")
                                 || !self.check_doc_fake_variadic(meta, hir_id) =>
@@ -1028,6 +1043,7 @@ fn check_doc_attrs(
                         sym::alias
                         | sym::cfg
                         | sym::cfg_hide
+                        | sym::example
                         | sym::hidden
                         | sym::html_favicon_url
                         | sym::html_logo_url
@@ -80,7 +80,7 @@ pub enum CFProtection {
     Full,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Hash, HashStable_Generic)]
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Encodable, Decodable, HashStable_Generic)]
 pub enum OptLevel {
     No,         // -O0
     Less,       // -O1
@@ -545,6 +545,7 @@ pub enum PrintRequest {
     CodeModels,
     TlsModels,
     TargetSpec,
+    TargetSpecDiff,
     NativeStaticLibs,
     StackProtectorStrategies,
     LinkArgs,
@@ -1806,20 +1807,26 @@ fn collect_print_requests(
         ("native-static-libs", PrintRequest::NativeStaticLibs),
         ("stack-protector-strategies", PrintRequest::StackProtectorStrategies),
         ("target-spec-json", PrintRequest::TargetSpec),
+        ("target-spec-json-diff", PrintRequest::TargetSpecDiff),
         ("link-args", PrintRequest::LinkArgs),
         ("split-debuginfo", PrintRequest::SplitDebuginfo),
     ];
 
     prints.extend(matches.opt_strs("print").into_iter().map(|req| {
         match PRINT_REQUESTS.iter().find(|&&(name, _)| name == req) {
-            Some((_, PrintRequest::TargetSpec)) => {
+            Some(&(
+                name,
+                print_request @ (PrintRequest::TargetSpec | PrintRequest::TargetSpecDiff),
+            )) => {
                 if unstable_opts.unstable_options {
-                    PrintRequest::TargetSpec
+                    print_request
                 } else {
                     early_error(
                         error_format,
-                        "the `-Z unstable-options` flag must also be passed to \
-                     enable the target-spec-json print option",
+                        &format!(
+                            "the `-Z unstable-options` flag must also be passed to \
+                             enable the {name} print option"
+                        ),
                     );
                 }
             }
@@ -2972,6 +2979,24 @@ pub fn should_panic(self) -> u8 {
     }
 }
 
+/// The format of the report written by `-Zdump-mono-stats`.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum DumpMonoStatsFormat {
+    /// A human readable table.
+    Text,
+    /// Machine readable JSON.
+    Json,
+}
+
+impl DumpMonoStatsFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Json => "json",
+        }
+    }
+}
+
 /// How to run proc-macro code when building this crate
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum ProcMacroExecutionStrategy {
@@ -2,12 +2,19 @@
 
 use smallvec::{smallvec, SmallVec};
 use std::env;
+use std::ffi::OsString;
 use std::fs;
+use std::hash::Hash;
 use std::iter::FromIterator;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use crate::search_paths::{PathKind, SearchPath};
+use rustc_data_structures::flock;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_fs_util::fix_windows_verbatim_for_gcc;
+use rustc_target::json::ToJson;
+use rustc_target::spec::Target;
 
 #[derive(Copy, Clone)]
 pub enum FileMatch {
@@ -63,6 +70,217 @@ pub fn make_target_lib_path(sysroot: &Path, target_triple: &str) -> PathBuf {
     PathBuf::from_iter([sysroot, Path::new(&rustlib_path), Path::new("lib")])
 }
 
+/// Returns the sysroot with `core`, `compiler_builtins` and `alloc` for `target` built by
+/// `-Z build-sysroot`, building them from `rust-src` first unless they are already cached.
+///
+/// The built sysroots are kept in `cache_dir`, keyed by the hash of the target spec, the compiler
+/// version and the sources they are built from, so all compilations for the same target share
+/// them.
+pub fn built_sysroot(
+    rust_src: Option<&Path>,
+    cache_dir: &Path,
+    target: &Target,
+    target_triple: &str,
+) -> Result<PathBuf, String> {
+    let rust_src = rust_src.ok_or_else(|| {
+        "`-Z build-sysroot` needs the `rust-src` component, which is not installed".to_owned()
+    })?;
+    let library = rust_src.join("library");
+    let compiler_builtins = compiler_builtins_dir(rust_src)?;
+
+    let spec = target.to_json().to_string();
+    let mut hasher = StableHasher::new();
+    spec.hash(&mut hasher);
+    option_env!("CFG_VERSION").hash(&mut hasher);
+    for dir in [&library, &compiler_builtins] {
+        hash_sources(dir, &mut hasher).map_err(|error| {
+            format!("failed to read the sources in `{}`: {}", dir.display(), error)
+        })?;
+    }
+    let hash: u64 = hasher.finish();
+
+    let sysroot = cache_dir.join(format!("{}-{:016x}", target_triple, hash));
+    let lib_path = make_target_lib_path(&sysroot, target_triple);
+    let stamp = sysroot.join("built");
+    if stamp.is_file() {
+        return Ok(sysroot);
+    }
+
+    let io_error = |error: std::io::Error| {
+        format!("failed to create the sysroot in `{}`: {}", sysroot.display(), error)
+    };
+    fs::create_dir_all(&lib_path).map_err(io_error)?;
+    // Another rustc may be building the same sysroot, so wait for it and reuse its result.
+    let _lock = flock::Lock::new(&sysroot.join("lock"), true, true, true).map_err(io_error)?;
+    if stamp.is_file() {
+        return Ok(sysroot);
+    }
+
+    // Custom targets are passed to the child compilers as the spec they were loaded from, named
+    // like the target so the libraries end up in the right directory of the sysroot.
+    let target_arg: OsString = if target.is_builtin {
+        target_triple.into()
+    } else {
+        let spec_path = sysroot.join(format!("{}.json", target_triple));
+        fs::write(&spec_path, &spec).map_err(io_error)?;
+        spec_path.into_os_string()
+    };
+    let rustc = env::current_exe().map_err(io_error)?;
+
+    let crates = [
+        ("core", library.join("core/src/lib.rs"), "2021", vec![]),
+        (
+            "compiler_builtins",
+            compiler_builtins.join("src/lib.rs"),
+            "2018",
+            compiler_builtins_cfgs(target),
+        ),
+        ("alloc", library.join("alloc/src/lib.rs"), "2021", vec![]),
+    ];
+    for (name, src, edition, cfgs) in crates {
+        let mut cmd = Command::new(&rustc);
+        cmd.arg("--crate-name")
+            .arg(name)
+            .arg("--crate-type=rlib")
+            .arg(format!("--edition={}", edition))
+            .arg("--target")
+            .arg(&target_arg)
+            .arg("--sysroot")
+            .arg(&sysroot)
+            .arg("--out-dir")
+            .arg(&lib_path)
+            .arg("--cap-lints=allow")
+            .arg("-Copt-level=3")
+            .arg(format!("-Cmetadata={:016x}", hash))
+            .arg("-Zforce-unstable-if-unmarked");
+        for cfg in cfgs {
+            cmd.arg("--cfg").arg(cfg);
+        }
+        cmd.arg(&src);
+
+        debug!("building sysroot crate: {:?}", cmd);
+        let status = cmd
+            .status()
+            .map_err(|error| format!("failed to run `{}`: {}", rustc.display(), error))?;
+        if !status.success() {
+            return Err(format!(
+                "failed to build `{}` for the sysroot of `{}` ({})",
+                name, target_triple, status
+            ));
+        }
+    }
+
+    fs::write(&stamp, "").map_err(io_error)?;
+    Ok(sysroot)
+}
+
+/// Hashes the path, size and modification time of every file in `dir`, like Cargo does to notice
+/// changes to path dependencies, without reading all of the sources on every compilation.
+fn hash_sources(dir: &Path, hasher: &mut StableHasher) -> std::io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let metadata = fs::metadata(&path)?;
+        if metadata.is_dir() {
+            hash_sources(&path, hasher)?;
+        } else {
+            path.hash(hasher);
+            metadata.len().hash(hasher);
+            metadata.modified()?.hash(hasher);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the cfgs the build script of `compiler_builtins` sets for `target`, which can't be run
+/// here. Unlike the build script, which looks at the name of the target, this looks at the
+/// target spec, as custom targets can be named arbitrarily.
+fn compiler_builtins_cfgs(target: &Target) -> Vec<&'static str> {
+    // `compiler-builtins` is enabled by the standard library, `unstable` by the build script.
+    let mut cfgs = vec![r#"feature="compiler-builtins""#, r#"feature="unstable""#];
+
+    // Emscripten's runtime includes all the builtins and OpenBSD provides compiler-rt.
+    if target.os == "emscripten" || target.os == "openbsd" {
+        return cfgs;
+    }
+
+    // Without a libc, `compiler_builtins` has to provide `memcpy` and friends.
+    if (target.arch.starts_with("wasm") && target.os != "wasi")
+        || (target.vendor == "fortanix" && target.env == "sgx")
+        || target.os == "none"
+        || target.arch == "nvptx64"
+        || target.os == "uefi"
+    {
+        cfgs.push(r#"feature="mem""#);
+    }
+
+    // The architecture as LLVM names it, which tells Thumb and ARM versions apart.
+    let llvm_arch = target.llvm_target.split('-').next().unwrap_or_default();
+
+    // These targets support unaligned memory accesses in hardware.
+    if matches!(&*target.arch, "x86_64" | "aarch64" | "bpf") || llvm_arch == "i686" {
+        cfgs.push(r#"feature="mem-unaligned""#);
+    }
+
+    if llvm_arch.starts_with("thumb") {
+        cfgs.push("thumb");
+    }
+    // ARMv6-M and ARMv8-M Baseline only support the original Thumb-1 instructions.
+    if llvm_arch == "thumbv6m" || llvm_arch == "thumbv8m.base" {
+        cfgs.push("thumb_1");
+    }
+    // Pre-ARMv6 Linux targets use the kernel's helpers to emulate atomics.
+    if matches!(llvm_arch, "armv4t" | "armv5te") || target.llvm_target == "arm-linux-androideabi" {
+        cfgs.push("kernel_user_helpers");
+    }
+
+    cfgs
+}
+
+/// Finds the source of `compiler_builtins`, which isn't part of `library`. The `rust-src`
+/// component ships it in `vendor`. The source root of a local build of the compiler only has it
+/// there when dependencies are vendored, otherwise it is taken from Cargo's registry, which has
+/// the version locked in `Cargo.lock` after building the standard library.
+fn compiler_builtins_dir(rust_src: &Path) -> Result<PathBuf, String> {
+    let vendored = rust_src.join("vendor").join("compiler_builtins");
+    if vendored.join("src/lib.rs").is_file() {
+        return Ok(vendored);
+    }
+
+    let not_found = || {
+        format!(
+            "`-Z build-sysroot` needs the source of `compiler_builtins`, which is neither in `{}` \
+             nor in Cargo's registry",
+            vendored.display()
+        )
+    };
+    let lockfile = fs::read_to_string(rust_src.join("Cargo.lock")).map_err(|_| not_found())?;
+    let version = lockfile
+        .split("[[package]]")
+        .find(|package| package.lines().any(|line| line == r#"name = "compiler_builtins""#))
+        .and_then(|package| {
+            package.lines().find_map(|line| line.strip_prefix("version = \"")?.strip_suffix('"'))
+        })
+        .ok_or_else(not_found)?;
+
+    let cargo_home = match env::var_os("CARGO_HOME") {
+        Some(cargo_home) => PathBuf::from(cargo_home),
+        None => {
+            let home = env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })
+                .ok_or_else(not_found)?;
+            PathBuf::from(home).join(".cargo")
+        }
+    };
+    // There is a directory for every registry index Cargo has used.
+    let registry_src = cargo_home.join("registry").join("src");
+    fs::read_dir(&registry_src)
+        .map_err(|_| not_found())?
+        .filter_map(|index| Some(index.ok()?.path().join(format!("compiler_builtins-{}", version))))
+        .find(|dir| dir.join("src/lib.rs").is_file())
+        .ok_or_else(not_found)
+}
+
 #[cfg(unix)]
 fn current_dll_path() -> Result<PathBuf, String> {
     use std::ffi::{CStr, OsStr};
@@ -406,6 +406,7 @@ mod desc {
         "one of (`none` (default), `basic`, `strong`, or `all`)";
     pub const parse_branch_protection: &str =
         "a `,` separated combination of `bti`, `b-key`, `pac-ret`, or `leaf`";
+    pub const parse_dump_mono_stats: &str = "either `text` or `json` (default: `text`)";
     pub const parse_proc_macro_execution_strategy: &str =
         "one of supported execution strategies (`same-thread`, or `cross-thread`)";
 }
@@ -1059,6 +1060,15 @@ pub(crate) fn parse_branch_protection(
         true
     }
 
+    pub(crate) fn parse_dump_mono_stats(slot: &mut DumpMonoStatsFormat, v: Option<&str>) -> bool {
+        *slot = match v {
+            None | Some("text") => DumpMonoStatsFormat::Text,
+            Some("json") => DumpMonoStatsFormat::Json,
+            _ => return false,
+        };
+        true
+    }
+
     pub(crate) fn parse_proc_macro_execution_strategy(
         slot: &mut ProcMacroExecutionStrategy,
         v: Option<&str>,
@@ -1225,12 +1235,20 @@ pub(crate) fn parse_proc_macro_execution_strategy(
     binary_dep_depinfo: bool = (false, parse_bool, [TRACKED],
         "include artifacts (sysroot, crate dependencies) used during compilation in dep-info \
         (default: no)"),
+    build_sysroot: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "build `core`, `compiler_builtins` and `alloc` from `rust-src` for the target and use \
+        them instead of the libraries of the sysroot; the built libraries are cached in the given \
+        directory (default: the temporary directory)"),
     box_noalias: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "emit noalias metadata for box (default: yes)"),
     branch_protection: Option<BranchProtection> = (None, parse_branch_protection, [TRACKED],
         "set options for branch target identification and pointer authentication on AArch64"),
     cf_protection: CFProtection = (CFProtection::None, parse_cfprotection, [TRACKED],
         "instrument control-flow architecture protection"),
+    cgu_partitioning_profile: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
+        "use a profile listing hot items, items which change together and codegen unit timings \
+        to guide how codegen units are merged"),
     cgu_partitioning_strategy: Option<String> = (None, parse_opt_string, [TRACKED],
         "the codegen unit partitioning strategy to use"),
     chalk: bool = (false, parse_bool, [TRACKED],
@@ -1264,6 +1282,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "enables drop tracking in generators (default: no)"),
     dual_proc_macros: bool = (false, parse_bool, [TRACKED],
         "load proc macros for both target and host, but only link to the target (default: no)"),
+    dump_cgu_timings: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write the time spent compiling each codegen unit and the items in it to the given file, \
+        which can be passed to `-Z cgu-partitioning-profile`"),
     dump_dep_graph: bool = (false, parse_bool, [UNTRACKED],
         "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv) \
         (default: no)"),
@@ -1293,6 +1314,13 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         computed `block` spans (one span encompassing a block's terminator and \
         all statements). If `-Z instrument-coverage` is also enabled, create \
         an additional `.html` file showing the computed coverage spans."),
+    dump_mono_stats: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "output statistics about monomorphization collection, grouped by generic definition, \
+        to the given directory (default: the current directory)"),
+    dump_mono_stats_format: DumpMonoStatsFormat = (DumpMonoStatsFormat::Text,
+        parse_dump_mono_stats, [UNTRACKED],
+        "the format to use for -Z dump-mono-stats (`text` or `json`) (default: `text`)"),
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
     dylib_lto: bool = (false, parse_bool, [UNTRACKED],
@@ -1370,6 +1398,10 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    link_size_report: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
+        parse_switch_with_opt_path, [UNTRACKED],
+        "write a JSON report attributing the section sizes of the linked output to crates and \
+        symbols to the given directory (default: the directory of the output)"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1559,6 +1591,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
     symbol_mangling_version: Option<SymbolManglingVersion> = (None,
         parse_symbol_mangling_version, [TRACKED],
         "which mangling version to use for symbol names ('legacy' (default) or 'v0')"),
+    target_spec_diff_base: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "the built-in target to compare against with `--print target-spec-json-diff` \
+        (default: the built-in target with the same `llvm-target`)"),
     #[rustc_lint_opt_deny_field_access("use `Session::teach` instead of this field")]
     teach: bool = (false, parse_bool, [TRACKED],
         "show extended diagnostic help (default: no)"),
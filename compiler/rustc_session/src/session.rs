@@ -1374,7 +1374,23 @@ pub fn build_session(
     let host_triple = config::host_triple();
     let target_triple = sopts.target_triple.triple();
     let host_tlib_path = Lrc::new(SearchPath::from_sysroot_and_triple(&sysroot, host_triple));
-    let target_tlib_path = if host_triple == target_triple {
+    let sysroot_cache_dir = match sopts.unstable_opts.build_sysroot {
+        SwitchWithOptPath::Enabled(Some(ref cache_dir)) => Some(cache_dir.clone()),
+        SwitchWithOptPath::Enabled(None) => Some(std::env::temp_dir().join("rustc-sysroot-cache")),
+        SwitchWithOptPath::Disabled => None,
+    };
+    let target_tlib_path = if let Some(cache_dir) = sysroot_cache_dir {
+        // The libraries for the target are built from source, but the host libraries still come
+        // from the regular sysroot.
+        let built_sysroot = filesearch::built_sysroot(
+            sopts.real_rust_source_base_dir.as_deref(),
+            &cache_dir,
+            &target_cfg,
+            target_triple,
+        )
+        .unwrap_or_else(|e| early_error(sopts.error_format, &e));
+        Lrc::new(SearchPath::from_sysroot_and_triple(&built_sysroot, target_triple))
+    } else if host_triple == target_triple {
         // Use the same `SearchPath` if host and target triple are identical to avoid unnecessary
         // rescanning of the target lib path and an unnecessary allocation.
         host_tlib_path.clone()
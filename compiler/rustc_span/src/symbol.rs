@@ -391,6 +391,9 @@
         async_await,
         async_closure,
         async_fn_in_trait,
+        async_for_loop,
+        async_iterator,
+        async_iterator_poll_next,
         atomic,
         atomic_mod,
         atomics,
@@ -616,6 +619,7 @@
         doc_auto_cfg,
         doc_cfg,
         doc_cfg_hide,
+        doc_example,
         doc_keyword,
         doc_masked,
         doc_notable_trait,
@@ -656,6 +660,7 @@
         eq,
         ermsb_target_feature,
         exact_div,
+        example,
         except,
         exchange_malloc,
         exclusive_range_pattern,
@@ -842,6 +847,7 @@
         len,
         let_chains,
         let_else,
+        level,
         lhs,
         lib,
         libc,
@@ -40,6 +40,7 @@
 use crate::spec::crt_objects::{CrtObjects, LinkSelfContainedDefault};
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
+use rustc_span::lev_distance::lev_distance;
 use rustc_span::symbol::{sym, Symbol};
 use serde_json::Value;
 use std::borrow::Cow;
@@ -50,7 +51,7 @@
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::{fmt, io};
+use std::{cmp, fmt, io, mem};
 
 use rustc_macros::HashStable_Generic;
 
@@ -1266,16 +1267,18 @@ macro_rules! cvs {
 
 /// Warnings encountered when parsing the target `json`.
 ///
-/// Includes fields that weren't recognized and fields that don't have the expected type.
+/// Includes fields that weren't recognized, fields that don't have the expected type and options
+/// which are inconsistent with each other.
 #[derive(Debug, PartialEq)]
 pub struct TargetWarnings {
     unused_fields: Vec<String>,
     incorrect_type: Vec<String>,
+    inconsistencies: Vec<String>,
 }
 
 impl TargetWarnings {
     pub fn empty() -> Self {
-        Self { unused_fields: Vec::new(), incorrect_type: Vec::new() }
+        Self { unused_fields: Vec::new(), incorrect_type: Vec::new(), inconsistencies: Vec::new() }
     }
 
     pub fn warning_messages(&self) -> Vec<String> {
@@ -1292,6 +1295,9 @@ pub fn warning_messages(&self) -> Vec<String> {
                 self.incorrect_type.join(", ")
             ));
         }
+        for inconsistency in &self.inconsistencies {
+            warnings.push(format!("target json file is inconsistent: {}", inconsistency));
+        }
         warnings
     }
 }
@@ -2039,41 +2045,51 @@ pub fn from_json(obj: Json) -> Result<(Target, TargetWarnings), String> {
         };
 
         let mut incorrect_type = vec![];
+        // Every key which is read, to suggest a replacement for misspelled keys.
+        let mut known_keys: Vec<String> = vec![];
+
+        macro_rules! take {
+            ($name:expr) => {{
+                let name: &str = $name;
+                known_keys.push(name.to_owned());
+                obj.remove(name)
+            }};
+        }
 
         macro_rules! key {
             ($key_name:ident) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(s) = obj.remove(&name).and_then(|s| s.as_str().map(str::to_string).map(Cow::from)) {
+                if let Some(s) = take!(&name).and_then(|s| s.as_str().map(str::to_string).map(Cow::from)) {
                     base.$key_name = s;
                 }
             } );
             ($key_name:ident = $json_name:expr) => ( {
                 let name = $json_name;
-                if let Some(s) = obj.remove(name).and_then(|s| s.as_str().map(str::to_string).map(Cow::from)) {
+                if let Some(s) = take!(name).and_then(|s| s.as_str().map(str::to_string).map(Cow::from)) {
                     base.$key_name = s;
                 }
             } );
             ($key_name:ident, bool) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(s) = obj.remove(&name).and_then(|b| b.as_bool()) {
+                if let Some(s) = take!(&name).and_then(|b| b.as_bool()) {
                     base.$key_name = s;
                 }
             } );
             ($key_name:ident = $json_name:expr, bool) => ( {
                 let name = $json_name;
-                if let Some(s) = obj.remove(name).and_then(|b| b.as_bool()) {
+                if let Some(s) = take!(name).and_then(|b| b.as_bool()) {
                     base.$key_name = s;
                 }
             } );
             ($key_name:ident, u64) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(s) = obj.remove(&name).and_then(|j| Json::as_u64(&j)) {
+                if let Some(s) = take!(&name).and_then(|j| Json::as_u64(&j)) {
                     base.$key_name = s;
                 }
             } );
             ($key_name:ident, u32) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(s) = obj.remove(&name).and_then(|b| b.as_u64()) {
+                if let Some(s) = take!(&name).and_then(|b| b.as_u64()) {
                     if s < 1 || s > 5 {
                         return Err("Not a valid DWARF version number".into());
                     }
@@ -2082,13 +2098,13 @@ macro_rules! key {
             } );
             ($key_name:ident, Option<u64>) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(s) = obj.remove(&name).and_then(|b| b.as_u64()) {
+                if let Some(s) = take!(&name).and_then(|b| b.as_u64()) {
                     base.$key_name = Some(s);
                 }
             } );
             ($key_name:ident, MergeFunctions) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<MergeFunctions>() {
                         Ok(mergefunc) => base.$key_name = mergefunc,
                         _ => return Some(Err(format!("'{}' is not a valid value for \
@@ -2101,7 +2117,7 @@ macro_rules! key {
             } );
             ($key_name:ident, RelocModel) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<RelocModel>() {
                         Ok(relocation_model) => base.$key_name = relocation_model,
                         _ => return Some(Err(format!("'{}' is not a valid relocation model. \
@@ -2113,7 +2129,7 @@ macro_rules! key {
             } );
             ($key_name:ident, CodeModel) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<CodeModel>() {
                         Ok(code_model) => base.$key_name = Some(code_model),
                         _ => return Some(Err(format!("'{}' is not a valid code model. \
@@ -2125,7 +2141,7 @@ macro_rules! key {
             } );
             ($key_name:ident, TlsModel) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<TlsModel>() {
                         Ok(tls_model) => base.$key_name = tls_model,
                         _ => return Some(Err(format!("'{}' is not a valid TLS model. \
@@ -2137,7 +2153,7 @@ macro_rules! key {
             } );
             ($key_name:ident, PanicStrategy) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s {
                         "unwind" => base.$key_name = PanicStrategy::Unwind,
                         "abort" => base.$key_name = PanicStrategy::Abort,
@@ -2150,7 +2166,7 @@ macro_rules! key {
             } );
             ($key_name:ident, RelroLevel) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<RelroLevel>() {
                         Ok(level) => base.$key_name = level,
                         _ => return Some(Err(format!("'{}' is not a valid value for \
@@ -2162,7 +2178,7 @@ macro_rules! key {
             } );
             ($key_name:ident, DebuginfoKind) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<DebuginfoKind>() {
                         Ok(level) => base.$key_name = level,
                         _ => return Some(Err(
@@ -2175,7 +2191,7 @@ macro_rules! key {
             } );
             ($key_name:ident, SplitDebuginfo) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<SplitDebuginfo>() {
                         Ok(level) => base.$key_name = level,
                         _ => return Some(Err(format!("'{}' is not a valid value for \
@@ -2187,7 +2203,7 @@ macro_rules! key {
             } );
             ($key_name:ident, list) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(j) = obj.remove(&name) {
+                if let Some(j) = take!(&name) {
                     if let Some(v) = j.as_array() {
                         base.$key_name = v.iter()
                             .map(|a| a.as_str().unwrap().to_string().into())
@@ -2199,7 +2215,7 @@ macro_rules! key {
             } );
             ($key_name:ident, opt_list) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(j) = obj.remove(&name) {
+                if let Some(j) = take!(&name) {
                     if let Some(v) = j.as_array() {
                         base.$key_name = Some(v.iter()
                             .map(|a| a.as_str().unwrap().to_string().into())
@@ -2211,7 +2227,7 @@ macro_rules! key {
             } );
             ($key_name:ident, falliable_list) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|j| {
+                take!(&name).and_then(|j| {
                     if let Some(v) = j.as_array() {
                         match v.iter().map(|a| FromStr::from_str(a.as_str().unwrap())).collect() {
                             Ok(l) => { base.$key_name = l },
@@ -2230,7 +2246,7 @@ macro_rules! key {
             } );
             ($key_name:ident, optional) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(o) = obj.remove(&name) {
+                if let Some(o) = take!(&name) {
                     base.$key_name = o
                         .as_str()
                         .map(|s| s.to_string().into());
@@ -2238,7 +2254,7 @@ macro_rules! key {
             } );
             ($key_name:ident = $json_name:expr, LldFlavor) => ( {
                 let name = $json_name;
-                obj.remove(name).and_then(|o| o.as_str().and_then(|s| {
+                take!(name).and_then(|o| o.as_str().and_then(|s| {
                     if let Some(flavor) = LldFlavor::from_str(&s) {
                         base.$key_name = flavor;
                     } else {
@@ -2252,7 +2268,7 @@ macro_rules! key {
             } );
             ($key_name:ident = $json_name:expr, LinkerFlavor) => ( {
                 let name = $json_name;
-                obj.remove(name).and_then(|o| o.as_str().and_then(|s| {
+                take!(name).and_then(|o| o.as_str().and_then(|s| {
                     match LinkerFlavorCli::from_str(s) {
                         Some(linker_flavor) => base.$key_name = linker_flavor,
                         _ => return Some(Err(format!("'{}' is not a valid value for linker-flavor. \
@@ -2263,7 +2279,7 @@ macro_rules! key {
             } );
             ($key_name:ident, StackProbeType) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| match StackProbeType::from_json(&o) {
+                take!(&name).and_then(|o| match StackProbeType::from_json(&o) {
                     Ok(v) => {
                         base.$key_name = v;
                         Some(Ok(()))
@@ -2275,7 +2291,7 @@ macro_rules! key {
             } );
             ($key_name:ident, SanitizerSet) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(o) = obj.remove(&name) {
+                if let Some(o) = take!(&name) {
                     if let Some(a) = o.as_array() {
                         for s in a {
                             base.$key_name |= match s.as_str() {
@@ -2300,7 +2316,7 @@ macro_rules! key {
 
             ($key_name:ident = $json_name:expr, link_self_contained) => ( {
                 let name = $json_name;
-                obj.remove(name).and_then(|o| o.as_str().and_then(|s| {
+                take!(name).and_then(|o| o.as_str().and_then(|s| {
                     match s.parse::<LinkSelfContainedDefault>() {
                         Ok(lsc_default) => base.$key_name = lsc_default,
                         _ => return Some(Err(format!("'{}' is not a valid `-Clink-self-contained` default. \
@@ -2311,7 +2327,7 @@ macro_rules! key {
             } );
             ($key_name:ident = $json_name:expr, link_objects) => ( {
                 let name = $json_name;
-                if let Some(val) = obj.remove(name) {
+                if let Some(val) = take!(name) {
                     let obj = val.as_object().ok_or_else(|| format!("{}: expected a \
                         JSON object with fields per CRT object kind.", name))?;
                     let mut args = CrtObjects::new();
@@ -2339,7 +2355,7 @@ macro_rules! key {
             } );
             ($key_name:ident = $json_name:expr, link_args) => ( {
                 let name = $json_name;
-                if let Some(val) = obj.remove(name) {
+                if let Some(val) = take!(name) {
                     let obj = val.as_object().ok_or_else(|| format!("{}: expected a \
                         JSON object with fields per linker-flavor.", name))?;
                     let mut args = LinkArgsCli::new();
@@ -2366,7 +2382,7 @@ macro_rules! key {
             } );
             ($key_name:ident, env) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                if let Some(o) = obj.remove(&name) {
+                if let Some(o) = take!(&name) {
                     if let Some(a) = o.as_array() {
                         for o in a {
                             if let Some(s) = o.as_str() {
@@ -2385,7 +2401,7 @@ macro_rules! key {
             } );
             ($key_name:ident, Option<Abi>) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
-                obj.remove(&name).and_then(|o| o.as_str().and_then(|s| {
+                take!(&name).and_then(|o| o.as_str().and_then(|s| {
                     match lookup_abi(s) {
                         Some(abi) => base.$key_name = Some(abi),
                         _ => return Some(Err(format!("'{}' is not a valid value for abi", s))),
@@ -2394,7 +2410,7 @@ macro_rules! key {
                 })).unwrap_or(Ok(()))
             } );
             ($key_name:ident, TargetFamilies) => ( {
-                if let Some(value) = obj.remove("target-family") {
+                if let Some(value) = take!("target-family") {
                     if let Some(v) = value.as_array() {
                         base.$key_name = v.iter()
                             .map(|a| a.as_str().unwrap().to_string().into())
@@ -2406,7 +2422,7 @@ macro_rules! key {
             } );
         }
 
-        if let Some(j) = obj.remove("target-endian") {
+        if let Some(j) = take!("target-endian") {
             if let Some(s) = j.as_str() {
                 base.endian = s.parse()?;
             } else {
@@ -2414,7 +2430,7 @@ macro_rules! key {
             }
         }
 
-        if let Some(fp) = obj.remove("frame-pointer") {
+        if let Some(fp) = take!("frame-pointer") {
             if let Some(s) = fp.as_str() {
                 base.frame_pointer = s
                     .parse()
@@ -2531,11 +2547,136 @@ macro_rules! key {
         base.update_from_cli();
 
         // Each field should have been read using `Json::remove` so any keys remaining are unused.
-        let remaining_keys = obj.keys();
-        Ok((
-            base,
-            TargetWarnings { unused_fields: remaining_keys.cloned().collect(), incorrect_type },
-        ))
+        let unused_fields = obj
+            .keys()
+            .map(|key| match closest_key(key, &known_keys) {
+                Some(known_key) => format!("{} (did you mean `{}`?)", key, known_key),
+                None => key.clone(),
+            })
+            .collect();
+        let inconsistencies = base.check_json_consistency();
+        Ok((base, TargetWarnings { unused_fields, incorrect_type, inconsistencies }))
+    }
+
+    /// Checks a target loaded from JSON for options which contradict each other or `llvm-target`.
+    /// Such targets would otherwise fail late, usually inside LLVM or the linker.
+    fn check_json_consistency(&self) -> Vec<String> {
+        let mut inconsistencies = vec![];
+        let llvm_arch = self.llvm_target.split('-').next().unwrap_or("");
+        let llvm_env = self.llvm_target.rsplit('-').next().unwrap_or("");
+
+        if let Some(arch) = arch_of_llvm_arch(llvm_arch).filter(|&arch| arch != self.arch) {
+            inconsistencies.push(format!(
+                "`arch` is `{}`, but `llvm-target` `{}` is for `{}`",
+                self.arch, self.llvm_target, arch
+            ));
+        }
+        if let Some(endian) = endian_of_llvm_arch(llvm_arch).filter(|&endian| endian != self.endian)
+        {
+            inconsistencies.push(format!(
+                "`target-endian` is `{}`, but `llvm-target` `{}` is {} endian",
+                self.endian.as_str(),
+                self.llvm_target,
+                endian.as_str()
+            ));
+        }
+        let (layout_endian, layout_pointer_width) =
+            layout_endian_and_pointer_width(&self.data_layout);
+        if layout_endian != self.endian {
+            inconsistencies.push(format!(
+                "`target-endian` is `{}`, but `data-layout` is {} endian",
+                self.endian.as_str(),
+                layout_endian.as_str()
+            ));
+        }
+        if layout_pointer_width != self.pointer_width {
+            inconsistencies.push(format!(
+                "`target-pointer-width` is `{}`, but `data-layout` has {}-bit pointers",
+                self.pointer_width, layout_pointer_width
+            ));
+        }
+        if self.abi == "eabihf" && !llvm_env.ends_with("hf") {
+            inconsistencies.push(format!(
+                "`abi` is `eabihf`, but `llvm-target` `{}` uses the soft-float ABI",
+                self.llvm_target
+            ));
+        }
+        if self.abi == "eabi" && llvm_env.ends_with("eabihf") {
+            inconsistencies.push(format!(
+                "`abi` is `eabi`, but `llvm-target` `{}` uses the hard-float ABI",
+                self.llvm_target
+            ));
+        }
+
+        let linker_flavor = self.linker_flavor.to_cli().desc();
+        if self.is_like_msvc && !self.is_like_windows {
+            inconsistencies.push("`is-like-msvc` is set without `is-like-windows`".to_owned());
+        }
+        for (is_like, flag, flavor_matches) in [
+            (
+                self.is_like_osx,
+                "is-like-osx",
+                matches!(self.linker_flavor, LinkerFlavor::Darwin(..)),
+            ),
+            (
+                self.is_like_msvc,
+                "is-like-msvc",
+                matches!(self.linker_flavor, LinkerFlavor::Msvc(..)),
+            ),
+            (
+                self.is_like_wasm && self.os != "emscripten",
+                "is-like-wasm",
+                matches!(self.linker_flavor, LinkerFlavor::WasmLld(..)),
+            ),
+        ] {
+            if is_like != flavor_matches {
+                inconsistencies.push(format!(
+                    "`{}` is {}set, but `linker-flavor` is `{}`",
+                    flag,
+                    if is_like { "" } else { "not " },
+                    linker_flavor
+                ));
+            }
+        }
+        for (args, name) in [
+            (&self.pre_link_args, "pre-link-args"),
+            (&self.late_link_args, "late-link-args"),
+            (&self.late_link_args_dynamic, "late-link-args-dynamic"),
+            (&self.late_link_args_static, "late-link-args-static"),
+            (&self.post_link_args, "post-link-args"),
+        ] {
+            for &flavor in args.keys() {
+                if mem::discriminant(&flavor) != mem::discriminant(&self.linker_flavor) {
+                    inconsistencies.push(format!(
+                        "`{}` has arguments for the `{}` linker flavor, which are never used \
+                         with `linker-flavor` `{}`",
+                        name,
+                        flavor.to_cli().desc(),
+                        linker_flavor
+                    ));
+                }
+            }
+        }
+
+        inconsistencies
+    }
+
+    /// Returns the differences between this target and `base` in the JSON format, as pairs of the
+    /// value in `base` and the value in this target. Keys missing in one of the targets have their
+    /// default value there.
+    pub fn json_diff(&self, base: &Target) -> BTreeMap<String, (Option<Json>, Option<Json>)> {
+        let Json::Object(base) = base.to_json() else { unreachable!() };
+        let Json::Object(target) = self.to_json() else { unreachable!() };
+
+        let mut diff = BTreeMap::new();
+        // Custom targets are never built-in, so this key would always differ.
+        for key in base.keys().chain(target.keys()).filter(|&key| key != "is-builtin") {
+            let (base_value, value) = (base.get(key), target.get(key));
+            if base_value != value && !diff.contains_key(key) {
+                diff.insert(key.clone(), (base_value.cloned(), value.cloned()));
+            }
+        }
+        diff
     }
 
     /// Load a built-in target
@@ -2617,6 +2758,72 @@ fn load_file(path: &Path) -> Result<(Target, TargetWarnings), String> {
     }
 }
 
+/// Returns the known key closest to a misspelled one, if any is close enough.
+fn closest_key<'a>(key: &str, known_keys: &'a [String]) -> Option<&'a str> {
+    let limit = cmp::max(key.len() / 3, 1);
+    known_keys
+        .iter()
+        .filter_map(|known_key| Some((lev_distance(key, known_key, limit)?, known_key)))
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, known_key)| &**known_key)
+}
+
+/// Returns the `arch` of targets whose `llvm-target` starts with `llvm_arch`.
+fn arch_of_llvm_arch(llvm_arch: &str) -> Option<&str> {
+    Some(match llvm_arch {
+        "x86_64" | "x86_64h" => "x86_64",
+        "i386" | "i486" | "i586" | "i686" => "x86",
+        "aarch64" | "aarch64_be" | "arm64" | "arm64_32" | "arm64e" => "aarch64",
+        _ if llvm_arch.starts_with("arm") || llvm_arch.starts_with("thumb") => "arm",
+        _ if llvm_arch.starts_with("mips64") || llvm_arch.starts_with("mipsisa64") => "mips64",
+        _ if llvm_arch.starts_with("mips") => "mips",
+        "powerpc64" | "powerpc64le" => "powerpc64",
+        "sparc64" | "sparcv9" => "sparc64",
+        "bpf" | "bpfeb" | "bpfel" => "bpf",
+        "powerpc" | "sparc" | "riscv32" | "riscv64" | "wasm32" | "wasm64" | "avr" | "msp430"
+        | "hexagon" | "m68k" | "s390x" | "nvptx64" => llvm_arch,
+        _ => return None,
+    })
+}
+
+/// Returns the endianness of targets whose `llvm-target` starts with `llvm_arch`.
+fn endian_of_llvm_arch(llvm_arch: &str) -> Option<Endian> {
+    arch_of_llvm_arch(llvm_arch)?;
+    let big_endian = if llvm_arch.ends_with("el") || llvm_arch.ends_with("le") {
+        false
+    } else {
+        llvm_arch.ends_with("_be")
+            || llvm_arch.starts_with("armeb")
+            || llvm_arch.starts_with("thumbeb")
+            || llvm_arch == "bpfeb"
+            || ["mips", "powerpc", "sparc", "s390x", "m68k"]
+                .iter()
+                .any(|prefix| llvm_arch.starts_with(prefix))
+    };
+    Some(if big_endian { Endian::Big } else { Endian::Little })
+}
+
+/// Returns the endianness and the width in bits of pointers in address space 0 described by an
+/// LLVM data layout string, using LLVM's defaults for the parts it leaves out.
+fn layout_endian_and_pointer_width(data_layout: &str) -> (Endian, u32) {
+    let mut endian = Endian::Little;
+    let mut pointer_width = 64;
+    for spec in data_layout.split('-') {
+        let mut parts = spec.split(':');
+        match parts.next().unwrap_or("") {
+            "e" => endian = Endian::Little,
+            "E" => endian = Endian::Big,
+            "p" | "p0" => {
+                if let Some(width) = parts.next().and_then(|width| width.parse().ok()) {
+                    pointer_width = width;
+                }
+            }
+            _ => {}
+        }
+    }
+    (endian, pointer_width)
+}
+
 impl ToJson for Target {
     fn to_json(&self) -> Json {
         let mut d = serde_json::Map::new();
@@ -3,7 +3,10 @@
 
 // Test target self-consistency and JSON encoding/decoding roundtrip.
 pub(super) fn test_target(mut target: Target) {
-    let recycled_target = Target::from_json(target.to_json()).map(|(j, _)| j);
+    let recycled_target = Target::from_json(target.to_json()).map(|(j, warnings)| {
+        assert_eq!(warnings.warning_messages(), Vec::<String>::new());
+        j
+    });
     target.update_to_cli();
     target.check_consistency();
     assert_eq!(recycled_target, Ok(target));
@@ -1,4 +1,4 @@
-use crate::spec::Target;
+use crate::spec::{Target, TargetTriple};
 
 #[test]
 fn report_unused_fields() {
@@ -55,3 +55,102 @@ fn no_warnings_for_valid_target() {
     let warnings = Target::from_json(json).unwrap().1;
     assert_eq!(warnings.warning_messages().len(), 0);
 }
+
+#[test]
+fn suggest_misspelled_fields() {
+    let json = serde_json::from_str(
+        r#"
+    {
+        "arch": "powerpc64",
+        "data-layout": "e-m:e-i64:64-n32:64",
+        "llvm-target": "powerpc64le-elf",
+        "target-pointer-width": "64",
+        "dynamic-linkng": true
+    }
+    "#,
+    )
+    .unwrap();
+    let warnings = Target::from_json(json).unwrap().1;
+    assert_eq!(
+        warnings.warning_messages(),
+        [
+            "target json file contains unused fields: dynamic-linkng (did you mean `dynamic-linking`?)"
+        ]
+    );
+}
+
+#[test]
+fn report_inconsistent_llvm_target() {
+    let json = serde_json::from_str(
+        r#"
+    {
+        "arch": "arm",
+        "data-layout": "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+        "llvm-target": "thumbv7em-none-eabi",
+        "target-pointer-width": "32",
+        "abi": "eabihf"
+    }
+    "#,
+    )
+    .unwrap();
+    let warnings = Target::from_json(json).unwrap().1;
+    assert_eq!(warnings.warning_messages().len(), 1);
+    assert!(warnings.warning_messages()[0].contains("soft-float"));
+
+    let json = serde_json::from_str(
+        r#"
+    {
+        "arch": "aarch64",
+        "data-layout": "e-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128",
+        "llvm-target": "armebv7r-none-eabi",
+        "target-pointer-width": "64"
+    }
+    "#,
+    )
+    .unwrap();
+    let warnings = Target::from_json(json).unwrap().1;
+    let messages = warnings.warning_messages().join("\n");
+    assert!(messages.contains("`arch` is `aarch64`"));
+    assert!(messages.contains("`target-endian` is `little`"));
+}
+
+#[test]
+fn report_inconsistent_linker_flavor() {
+    let json = serde_json::from_str(
+        r#"
+    {
+        "arch": "x86_64",
+        "data-layout": "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128",
+        "llvm-target": "x86_64-unknown-linux-gnu",
+        "target-pointer-width": "64",
+        "linker-flavor": "gcc",
+        "pre-link-args": { "lld-link": ["/debug"] },
+        "is-like-msvc": true
+    }
+    "#,
+    )
+    .unwrap();
+    let warnings = Target::from_json(json).unwrap().1;
+    let messages = warnings.warning_messages().join("\n");
+    assert!(messages.contains("`is-like-msvc` is set without `is-like-windows`"));
+    assert!(messages.contains("`is-like-msvc` is set, but `linker-flavor` is `gcc`"));
+    assert!(messages.contains("`pre-link-args` has arguments for the `lld-link` linker flavor"));
+}
+
+#[test]
+fn json_diff_against_builtin() {
+    let base = Target::expect_builtin(&TargetTriple::from_triple("thumbv7em-none-eabihf"));
+    let mut target = base.clone();
+    target.options.is_builtin = false;
+    target.options.cpu = "cortex-m4".into();
+    target.options.max_atomic_width = None;
+
+    let diff = target.json_diff(&base);
+    assert_eq!(
+        diff.into_iter().collect::<Vec<_>>(),
+        [
+            ("cpu".to_owned(), (None, Some("cortex-m4".into()))),
+            ("max-atomic-width".to_owned(), (Some(32.into()), None)),
+        ]
+    );
+}
@@ -4,6 +4,20 @@
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicU32, Ordering};
 
+#[test]
+fn test_new_in() {
+    use std::alloc::System;
+
+    let mut heap = BinaryHeap::with_capacity_in(4, System);
+    heap.extend([2, 9, 5]);
+    heap.push(7);
+    assert_eq!(heap.peek(), Some(&9));
+
+    let vec: Vec<i32, System> = heap.into();
+    let heap = BinaryHeap::from(vec);
+    assert_eq!(heap.into_sorted_vec(), [2, 5, 7, 9]);
+}
+
 #[test]
 fn test_iterator() {
     let data = vec![5, 9, 3];
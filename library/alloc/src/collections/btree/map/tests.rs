@@ -2336,3 +2336,128 @@ fn from_array() {
     let unordered_duplicates = BTreeMap::from([(3, 4), (1, 2), (1, 2)]);
     assert_eq!(map, unordered_duplicates);
 }
+
+#[test]
+fn test_cursor() {
+    let map = BTreeMap::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+
+    let mut cur = map.lower_bound(Bound::Unbounded);
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&2));
+    assert_eq!(cur.peek_next(), Some((&3, &'c')));
+    cur.move_prev();
+    assert_eq!(cur.key_value(), Some((&1, &'a')));
+    assert_eq!(cur.peek_prev(), None);
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_prev(), Some((&3, &'c')));
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+
+    let mut cur = map.upper_bound(Bound::Excluded(&1));
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_prev(), Some((&3, &'c')));
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut map = BTreeMap::from([(1, 'a'), (3, 'c'), (5, 'e')]);
+    let mut cur = map.lower_bound_mut(Bound::Excluded(&3));
+    assert_eq!(cur.key(), Some(&5));
+    cur.insert_before(4, 'd');
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.peek_prev(), Some((&4, &mut 'd')));
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    cur.insert_before(6, 'f');
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.remove_current(), None);
+    assert_eq!(cur.key(), None);
+    cur.insert_after(0, '?');
+    assert_eq!(cur.key(), None);
+    assert_eq!(map, BTreeMap::from([(0, '?'), (1, 'a'), (3, 'c'), (4, 'd'), (5, 'e'), (6, 'f')]));
+
+    let mut cur = map.upper_bound_mut(Bound::Included(&5));
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.remove_current(), Some((5, 'e')));
+    assert_eq!(cur.key(), Some(&6));
+    assert_eq!(cur.remove_current_and_move_back(), Some((6, 'f')));
+    assert_eq!(cur.key(), Some(&4));
+    *cur.value_mut().unwrap() = 'D';
+    assert_eq!(cur.as_cursor().key_value(), Some((&4, &'D')));
+    assert_eq!(map, BTreeMap::from([(0, '?'), (1, 'a'), (3, 'c'), (4, 'D')]));
+    map.check();
+}
+
+#[test]
+fn test_cursor_mut_empty() {
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Bound::Unbounded);
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_next(), None);
+    cur.insert_after(1, 'a');
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_next(), Some((&1, &mut 'a')));
+    cur.move_next();
+    assert_eq!(cur.remove_current(), Some((1, 'a')));
+    assert_eq!(cur.key(), None);
+    assert!(map.is_empty());
+    map.check();
+}
+
+#[test]
+fn test_cursor_mut_insert_split() {
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Bound::Unbounded);
+    // Inserting at the end repeatedly splits nodes, including the root.
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        cur.insert_before(i * 10, i);
+        assert_eq!(cur.key(), None);
+        assert_eq!(cur.peek_prev().map(|(k, v)| (*k, *v)), Some((i * 10, i)));
+    }
+    map.check();
+    assert_eq!(map.height(), Some(2));
+
+    // Inserting around a cursor in the middle keeps it pointing at the same element.
+    let mut cur = map.lower_bound_mut(Bound::Included(&100));
+    for i in 1..10 {
+        cur.insert_after(110 - i, 0);
+        assert_eq!(cur.key(), Some(&100));
+        cur.insert_before(90 + i, 0);
+        assert_eq!(cur.key(), Some(&100));
+    }
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&101));
+    cur.move_prev();
+    cur.move_prev();
+    assert_eq!(cur.key(), Some(&99));
+    assert_eq!(map.len(), MIN_INSERTS_HEIGHT_2 + 18);
+    map.check();
+}
+
+#[test]
+fn test_cursor_mut_remove_all() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let mut map = BTreeMap::from_iter((0..size).map(|i| (i, i)));
+    let mut cur = map.lower_bound_mut(Bound::Unbounded);
+    for i in 0..size {
+        assert_eq!(cur.remove_current(), Some((i, i)));
+    }
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.remove_current(), None);
+    assert!(map.is_empty());
+    map.check();
+}
+
+#[should_panic(expected = "key must be ordered above the current element")]
+#[test]
+fn test_cursor_mut_insert_after_panic() {
+    let mut map = BTreeMap::from([(1, 'a'), (3, 'c')]);
+    let mut cur = map.lower_bound_mut(Bound::Included(&3));
+    cur.insert_after(2, 'b');
+}
@@ -854,3 +854,31 @@ fn test_range_panic_2() {
 
     let _invalid_range = set.range((Excluded(&5), Excluded(&5)));
 }
+
+#[test]
+fn test_cursor() {
+    let set = BTreeSet::from([1, 2, 3]);
+    let mut cur = set.upper_bound(Bound::Included(&2));
+    assert_eq!(cur.key(), Some(&2));
+    assert_eq!(cur.peek_next(), Some(&3));
+    assert_eq!(cur.peek_prev(), Some(&1));
+    cur.move_next();
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_next(), Some(&1));
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut set = BTreeSet::from([1, 3, 5]);
+    let mut cur = set.lower_bound_mut(Bound::Excluded(&1));
+    assert_eq!(cur.key(), Some(&3));
+    cur.insert_before(2);
+    cur.insert_after(4);
+    assert_eq!(cur.key(), Some(&3));
+    assert_eq!(cur.remove_current(), Some(3));
+    assert_eq!(cur.key(), Some(&4));
+    assert_eq!(cur.remove_current_and_move_back(), Some(4));
+    assert_eq!(cur.as_cursor().key(), Some(&2));
+    assert_eq!(Vec::from_iter(set), [1, 2, 5]);
+}
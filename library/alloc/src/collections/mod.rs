@@ -8,7 +8,6 @@
 mod btree;
 #[cfg(not(no_global_oom_handling))]
 pub mod linked_list;
-#[cfg(not(no_global_oom_handling))]
 pub mod vec_deque;
 
 #[cfg(not(no_global_oom_handling))]
@@ -47,7 +46,6 @@ pub mod btree_set {
 #[doc(no_inline)]
 pub use linked_list::LinkedList;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use vec_deque::VecDeque;
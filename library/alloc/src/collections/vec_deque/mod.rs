@@ -10,6 +10,7 @@
 use core::cmp::{self, Ordering};
 use core::fmt;
 use core::hash::{Hash, Hasher};
+#[cfg(not(no_global_oom_handling))]
 use core::iter::{repeat_with, FromIterator};
 use core::marker::PhantomData;
 use core::mem::{ManuallyDrop, MaybeUninit, SizedTypeProperties};
@@ -60,13 +61,16 @@
 
 mod ring_slices;
 
+#[cfg(not(no_global_oom_handling))]
 use self::spec_extend::SpecExtend;
 
+#[cfg(not(no_global_oom_handling))]
 mod spec_extend;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(not(no_global_oom_handling))]
 const INITIAL_CAPACITY: usize = 7; // 2^3 - 1
 const MINIMUM_CAPACITY: usize = 1; // 2 - 1
 
@@ -115,6 +119,7 @@ pub struct VecDeque<
     buf: RawVec<T, A>,
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Clone, A: Allocator + Clone> Clone for VecDeque<T, A> {
     fn clone(&self) -> Self {
@@ -164,6 +169,7 @@ fn drop(&mut self) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Default for VecDeque<T> {
     /// Creates an empty deque.
@@ -442,6 +448,7 @@ fn diff(a: usize, b: usize) -> usize {
 
     /// Copies all values from `src` to `dst`, wrapping around if needed.
     /// Assumes capacity is sufficient.
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     unsafe fn copy_slice(&mut self, dst: usize, src: &[T]) {
         debug_assert!(src.len() <= self.cap());
@@ -465,6 +472,7 @@ unsafe fn copy_slice(&mut self, dst: usize, src: &[T]) {
     ///
     /// Assumes no wrapping around happens.
     /// Assumes capacity is sufficient.
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     unsafe fn write_iter(
         &mut self,
@@ -533,6 +541,7 @@ impl<T> VecDeque<T> {
     ///
     /// let deque: VecDeque<u32> = VecDeque::new();
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[must_use]
@@ -549,12 +558,40 @@ pub fn new() -> VecDeque<T> {
     ///
     /// let deque: VecDeque<u32> = VecDeque::with_capacity(10);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[must_use]
     pub fn with_capacity(capacity: usize) -> VecDeque<T> {
         Self::with_capacity_in(capacity, Global)
     }
+
+    /// Creates an empty deque with space for at least `capacity` elements,
+    /// returning an error if the allocation fails.
+    ///
+    /// This is the fallible version of [`VecDeque::with_capacity`].
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let deque: VecDeque<u32> = VecDeque::try_with_capacity(10)?;
+    /// assert!(deque.capacity() >= 10);
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_with_capacity(capacity: usize) -> Result<VecDeque<T>, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
 }
 
 impl<T, A: Allocator> VecDeque<T, A> {
@@ -567,6 +604,7 @@ impl<T, A: Allocator> VecDeque<T, A> {
     ///
     /// let deque: VecDeque<u32> = VecDeque::new();
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[unstable(feature = "allocator_api", issue = "32838")]
     pub fn new_in(alloc: A) -> VecDeque<T, A> {
@@ -582,6 +620,7 @@ pub fn new_in(alloc: A) -> VecDeque<T, A> {
     ///
     /// let deque: VecDeque<u32> = VecDeque::with_capacity(10);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "allocator_api", issue = "32838")]
     pub fn with_capacity_in(capacity: usize, alloc: A) -> VecDeque<T, A> {
         assert!(capacity < 1_usize << usize::BITS - 1, "capacity overflow");
@@ -591,6 +630,42 @@ pub fn with_capacity_in(capacity: usize, alloc: A) -> VecDeque<T, A> {
         VecDeque { tail: 0, head: 0, buf: RawVec::with_capacity_in(cap, alloc) }
     }
 
+    /// Creates an empty deque with space for at least `capacity` elements,
+    /// returning an error if the allocation fails.
+    ///
+    /// This is the fallible version of [`VecDeque::with_capacity_in`].
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, fallible_allocation)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::VecDeque;
+    ///
+    /// let deque: VecDeque<u32, _> = VecDeque::try_with_capacity_in(10, System)?;
+    /// assert!(deque.capacity() >= 10);
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_with_capacity_in(
+        capacity: usize,
+        alloc: A,
+    ) -> Result<VecDeque<T, A>, TryReserveError> {
+        // +1 since the ringbuffer always leaves one space empty
+        let cap = capacity
+            .checked_add(1)
+            .and_then(|cap| cmp::max(cap, MINIMUM_CAPACITY + 1).checked_next_power_of_two())
+            .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+        Ok(VecDeque { tail: 0, head: 0, buf: RawVec::try_with_capacity_in(cap, alloc)? })
+    }
+
     /// Provides a reference to the element at the given index.
     ///
     /// Element at index 0 is the front of the queue.
@@ -716,6 +791,7 @@ pub fn capacity(&self) -> usize {
     /// ```
     ///
     /// [`reserve`]: VecDeque::reserve
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve_exact(&mut self, additional: usize) {
         self.reserve(additional);
@@ -737,6 +813,7 @@ pub fn reserve_exact(&mut self, additional: usize) {
     /// buf.reserve(10);
     /// assert!(buf.capacity() >= 11);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve(&mut self, additional: usize) {
         let old_cap = self.cap();
@@ -863,6 +940,7 @@ pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError>
     /// buf.shrink_to_fit();
     /// assert!(buf.capacity() >= 4);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "deque_extras_15", since = "1.5.0")]
     pub fn shrink_to_fit(&mut self) {
         self.shrink_to(0);
@@ -888,6 +966,7 @@ pub fn shrink_to_fit(&mut self) {
     /// buf.shrink_to(0);
     /// assert!(buf.capacity() >= 4);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "shrink_to", since = "1.56.0")]
     pub fn shrink_to(&mut self, min_capacity: usize) {
         let min_capacity = cmp::min(min_capacity, self.capacity());
@@ -1546,6 +1625,7 @@ pub fn pop_back(&mut self) -> Option<T> {
     /// d.push_front(2);
     /// assert_eq!(d.front(), Some(&2));
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn push_front(&mut self, value: T) {
         if self.is_full() {
@@ -1559,6 +1639,44 @@ pub fn push_front(&mut self, value: T) {
         }
     }
 
+    /// Prepends an element to the deque, returning an error if the allocation
+    /// fails.
+    ///
+    /// This is the fallible version of [`VecDeque::push_front`]. If an error
+    /// is returned, `value` is dropped and the deque is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut d = VecDeque::new();
+    /// d.try_push_front(1)?;
+    /// d.try_push_front(2)?;
+    /// assert_eq!(d.front(), Some(&2));
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push_front(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.is_full() {
+            self.try_grow()?;
+        }
+
+        self.tail = self.wrap_sub(self.tail, 1);
+        let tail = self.tail;
+        unsafe {
+            self.buffer_write(tail, value);
+        }
+        Ok(())
+    }
+
     /// Appends an element to the back of the deque.
     ///
     /// # Examples
@@ -1571,6 +1689,7 @@ pub fn push_front(&mut self, value: T) {
     /// buf.push_back(3);
     /// assert_eq!(3, *buf.back().unwrap());
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn push_back(&mut self, value: T) {
         if self.is_full() {
@@ -1582,6 +1701,42 @@ pub fn push_back(&mut self, value: T) {
         unsafe { self.buffer_write(head, value) }
     }
 
+    /// Appends an element to the back of the deque, returning an error if the
+    /// allocation fails.
+    ///
+    /// This is the fallible version of [`VecDeque::push_back`]. If an error
+    /// is returned, `value` is dropped and the deque is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut buf = VecDeque::new();
+    /// buf.try_push_back(1)?;
+    /// buf.try_push_back(3)?;
+    /// assert_eq!(3, *buf.back().unwrap());
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push_back(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.is_full() {
+            self.try_grow()?;
+        }
+
+        let head = self.head;
+        self.head = self.wrap_add(self.head, 1);
+        unsafe { self.buffer_write(head, value) }
+        Ok(())
+    }
+
     #[inline]
     fn is_contiguous(&self) -> bool {
         // FIXME: Should we consider `head == 0` to mean
@@ -1682,6 +1837,7 @@ pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
     /// vec_deque.insert(1, 'd');
     /// assert_eq!(vec_deque, &['a', 'd', 'b', 'c']);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "deque_extras_15", since = "1.5.0")]
     pub fn insert(&mut self, index: usize, value: T) {
         assert!(index <= self.len(), "index out of bounds");
@@ -2084,6 +2240,7 @@ pub fn remove(&mut self, index: usize) -> Option<T> {
     /// assert_eq!(buf, [1]);
     /// assert_eq!(buf2, [2, 3]);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[must_use = "use `.truncate()` if you don't need the other half"]
     #[stable(feature = "split_off", since = "1.4.0")]
@@ -2151,6 +2308,7 @@ pub fn split_off(&mut self, at: usize) -> Self
     /// assert_eq!(buf, [1, 2, 3, 4]);
     /// assert_eq!(buf2, []);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[stable(feature = "append", since = "1.4.0")]
     pub fn append(&mut self, other: &mut Self) {
@@ -2265,6 +2423,7 @@ pub fn retain_mut<F>(&mut self, mut f: F)
     // Double the buffer size. This method is inline(never), so we expect it to only
     // be called in cold paths.
     // This may panic or abort
+    #[cfg(not(no_global_oom_handling))]
     #[inline(never)]
     fn grow(&mut self) {
         // Extend or possibly remove this assertion when valid use-cases for growing the
@@ -2279,6 +2438,20 @@ fn grow(&mut self) {
         debug_assert!(!self.is_full());
     }
 
+    // Like `grow`, but returns on errors instead of panicking or aborting.
+    #[inline(never)]
+    fn try_grow(&mut self) -> Result<(), TryReserveError> {
+        debug_assert!(self.is_full());
+        let old_cap = self.cap();
+        self.buf.try_reserve_exact(old_cap, old_cap)?;
+        assert!(self.cap() == old_cap * 2);
+        unsafe {
+            self.handle_capacity_increase(old_cap);
+        }
+        debug_assert!(!self.is_full());
+        Ok(())
+    }
+
     /// Modifies the deque in-place so that `len()` is equal to `new_len`,
     /// either by removing excess elements from the back or by appending
     /// elements generated by calling `generator` to the back.
@@ -2304,6 +2477,7 @@ fn grow(&mut self) {
     /// buf.resize_with(5, || { state += 1; state });
     /// assert_eq!(buf, [5, 10, 101, 102, 103]);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "vec_resize_with", since = "1.33.0")]
     pub fn resize_with(&mut self, new_len: usize, generator: impl FnMut() -> T) {
         let len = self.len();
@@ -2809,6 +2983,7 @@ pub fn partition_point<P>(&self, mut pred: P) -> usize
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<T: Clone, A: Allocator> VecDeque<T, A> {
     /// Modifies the deque in-place so that `len()` is equal to new_len,
     /// either by removing excess elements from the back or by appending clones of `value`
@@ -2948,6 +3123,7 @@ fn index_mut(&mut self, index: usize) -> &mut T {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> FromIterator<T> for VecDeque<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> VecDeque<T> {
@@ -2991,6 +3167,7 @@ fn into_iter(self) -> IterMut<'a, T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T, A: Allocator> Extend<T> for VecDeque<T, A> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
@@ -3008,6 +3185,7 @@ fn extend_reserve(&mut self, additional: usize) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
 impl<'a, T: 'a + Copy, A: Allocator> Extend<&'a T> for VecDeque<T, A> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
@@ -3032,6 +3210,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "vecdeque_vec_conversions", since = "1.10.0")]
 impl<T, A: Allocator> From<Vec<T, A>> for VecDeque<T, A> {
     /// Turn a [`Vec<T>`] into a [`VecDeque<T>`].
@@ -3116,6 +3295,7 @@ fn from(mut other: VecDeque<T, A>) -> Self {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 impl<T, const N: usize> From<[T; N]> for VecDeque<T> {
     /// Converts a `[T; N]` into a `VecDeque<T>`.
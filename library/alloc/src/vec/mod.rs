@@ -483,6 +483,33 @@ pub fn with_capacity(capacity: usize) -> Self {
         Self::with_capacity_in(capacity, Global)
     }
 
+    /// Constructs a new, empty `Vec<T>` with at least the specified capacity,
+    /// returning an error if the allocation fails.
+    ///
+    /// This is the fallible version of [`Vec::with_capacity`].
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let vec: Vec<u32> = Vec::try_with_capacity(10)?;
+    /// assert!(vec.capacity() >= 10);
+    ///
+    /// assert!(Vec::<u32>::try_with_capacity(usize::MAX).is_err());
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+
     /// Creates a `Vec<T>` directly from a pointer, a capacity, and a length.
     ///
     /// # Safety
@@ -673,6 +700,33 @@ pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
         Vec { buf: RawVec::with_capacity_in(capacity, alloc), len: 0 }
     }
 
+    /// Constructs a new, empty `Vec<T, A>` with at least the specified capacity
+    /// with the provided allocator, returning an error if the allocation fails.
+    ///
+    /// This is the fallible version of [`Vec::with_capacity_in`].
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, fallible_allocation)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let vec: Vec<u32, _> = Vec::try_with_capacity_in(10, System)?;
+    /// assert_eq!(vec.capacity(), 10);
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        Ok(Vec { buf: RawVec::try_with_capacity_in(capacity, alloc)?, len: 0 })
+    }
+
     /// Creates a `Vec<T, A>` directly from a pointer, a capacity, a length,
     /// and an allocator.
     ///
@@ -1439,19 +1493,60 @@ fn assert_failed(index: usize, len: usize) -> ! {
     #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(&mut self, index: usize, element: T) {
-        #[cold]
-        #[inline(never)]
-        fn assert_failed(index: usize, len: usize) -> ! {
-            panic!("insertion index (is {index}) should be <= len (is {len})");
+        // space for the new element
+        if self.len == self.buf.capacity() {
+            self.reserve(1);
         }
 
-        let len = self.len();
+        self.insert_within_capacity(index, element);
+    }
 
-        // space for the new element
-        if len == self.buf.capacity() {
-            self.reserve(1);
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right, returning an error if the allocation
+    /// fails.
+    ///
+    /// This is the fallible version of [`Vec::insert`]. If an error is
+    /// returned, `element` is dropped and the vector is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut vec = vec![1, 2, 3];
+    /// vec.try_insert(1, 4)?;
+    /// assert_eq!(vec, [1, 4, 2, 3]);
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), TryReserveError> {
+        if index > self.len {
+            insert_assert_failed(index, self.len);
+        }
+
+        if self.len == self.buf.capacity() {
+            self.try_reserve(1)?;
         }
 
+        self.insert_within_capacity(index, element);
+        Ok(())
+    }
+
+    /// Shared implementation of `insert` and `try_insert`, once there is room
+    /// for at least one more element.
+    fn insert_within_capacity(&mut self, index: usize, element: T) {
+        let len = self.len();
+        debug_assert!(len < self.buf.capacity());
+
         unsafe {
             // infallible
             // The spot to put the new value
@@ -1464,7 +1559,7 @@ fn assert_failed(index: usize, len: usize) -> ! {
                 } else if index == len {
                     // No elements need shifting.
                 } else {
-                    assert_failed(index, len);
+                    insert_assert_failed(index, len);
                 }
                 // Write it in, overwriting the first copy of the `index`th
                 // element.
@@ -1840,6 +1935,42 @@ pub fn push(&mut self, value: T) {
         }
     }
 
+    /// Appends an element to the back of a collection, returning an error if
+    /// the allocation fails.
+    ///
+    /// This is the fallible version of [`Vec::push`]. If an error is
+    /// returned, `value` is dropped and the vector is left unchanged. Use
+    /// [`Vec::push_within_capacity`] to get the element back instead.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut vec = vec![1, 2];
+    /// vec.try_push(3)?;
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.len == self.buf.capacity() {
+            self.buf.try_reserve(self.len, 1)?;
+        }
+        unsafe {
+            let end = self.as_mut_ptr().add(self.len);
+            ptr::write(end, value);
+            self.len += 1;
+        }
+        Ok(())
+    }
+
     /// Appends an element if there is sufficient spare capacity, otherwise an error is returned
     /// with the element.
     ///
@@ -1944,6 +2075,13 @@ pub fn append(&mut self, other: &mut Self) {
     unsafe fn append_elements(&mut self, other: *const [T]) {
         let count = unsafe { (*other).len() };
         self.reserve(count);
+        unsafe { self.append_elements_unchecked(other) };
+    }
+
+    /// Appends elements to `self` from other buffer, which must fit in the spare capacity.
+    #[inline]
+    unsafe fn append_elements_unchecked(&mut self, other: *const [T]) {
+        let count = unsafe { (*other).len() };
         let len = self.len();
         unsafe { ptr::copy_nonoverlapping(other as *const T, self.as_mut_ptr().add(len), count) };
         self.len += count;
@@ -2392,6 +2530,35 @@ pub fn extend_from_slice(&mut self, other: &[T]) {
         self.spec_extend(other.iter())
     }
 
+    /// Clones and appends all elements in a slice to the `Vec`, returning an
+    /// error if the allocation fails.
+    ///
+    /// This is the fallible version of [`Vec::extend_from_slice`]. If an error
+    /// is returned, the vector is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut vec = vec![1];
+    /// vec.try_extend_from_slice(&[2, 3, 4])?;
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// # Ok::<(), std::collections::TryReserveError>(())
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), TryReserveError> {
+        self.try_reserve(other.len())?;
+        // SAFETY: we reserved enough room for all of `other` above.
+        unsafe { self.spec_extend_from_slice(other) };
+        Ok(())
+    }
+
     /// Copies elements from `src` range to the end of the vector.
     ///
     /// # Panics
@@ -2573,6 +2740,12 @@ pub fn from_elem_in<T: Clone, A: Allocator>(elem: T, n: usize, alloc: A) -> Vec<
     <T as SpecFromElem>::from_elem(elem, n, alloc)
 }
 
+#[cold]
+#[inline(never)]
+fn insert_assert_failed(index: usize, len: usize) -> ! {
+    panic!("insertion index (is {index}) should be <= len (is {len})");
+}
+
 trait ExtendFromWithinSpec {
     /// # Safety
     ///
@@ -2628,6 +2801,37 @@ unsafe fn spec_extend_from_within(&mut self, src: Range<usize>) {
     }
 }
 
+// The counterpart of the `SpecExtend` implementations for slice iterators, for callers which
+// already reserved the capacity and must not go through the infallible `reserve`.
+trait ExtendFromSliceSpec<T> {
+    /// # Safety
+    ///
+    /// - `self.capacity() - self.len()` must be `>= other.len()`
+    unsafe fn spec_extend_from_slice(&mut self, other: &[T]);
+}
+
+impl<T: Clone, A: Allocator> ExtendFromSliceSpec<T> for Vec<T, A> {
+    default unsafe fn spec_extend_from_slice(&mut self, other: &[T]) {
+        // SAFETY:
+        // - len is increased only after initializing elements
+        let (_, spare, len) = unsafe { self.split_at_spare_mut_with_len() };
+
+        iter::zip(other, spare)
+            .map(|(src, dst)| dst.write(src.clone()))
+            // Note:
+            // - Element was just initialized with `MaybeUninit::write`, so it's ok to increase len
+            // - len is increased after each element to prevent leaks (see issue #82533)
+            .for_each(|_| *len += 1);
+    }
+}
+
+impl<T: Copy, A: Allocator> ExtendFromSliceSpec<T> for Vec<T, A> {
+    unsafe fn spec_extend_from_slice(&mut self, other: &[T]) {
+        // SAFETY: the caller guarantees that the spare capacity can hold `other`.
+        unsafe { self.append_elements_unchecked(other) };
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Common trait implementations for Vec
 ////////////////////////////////////////////////////////////////////////////////
@@ -225,3 +225,41 @@ fn arc_from_vec_opt() {
         );
     }
 }
+
+#[test]
+fn arc_in_allocator() {
+    use std::alloc::System;
+
+    let five = Arc::new_in(5, System);
+    let weak = Arc::downgrade(&five);
+    assert_eq!(weak.upgrade().as_deref(), Some(&5));
+    assert_eq!(Arc::try_unwrap(five), Ok(5));
+    assert!(weak.upgrade().is_none());
+
+    let boxed: Box<dyn Any + Send + Sync, System> = Box::new_in(String::from("any"), System);
+    let arc: Arc<dyn Any + Send + Sync, System> = Arc::from(boxed);
+    let arc: Arc<String, System> = arc.downcast().unwrap();
+    assert_eq!(*arc, "any");
+
+    let (ptr, alloc) = Arc::into_raw_with_allocator(arc);
+    let arc = unsafe { Arc::from_raw_in(ptr, alloc) };
+    assert_eq!(Arc::strong_count(&arc), 1);
+}
+
+#[test]
+fn arc_from_vec_and_string_in_allocator() {
+    use std::alloc::System;
+
+    // The first allocation is too small to be reused, the second one is reused in place.
+    for capacity in [2, 16] {
+        let mut v = Vec::with_capacity_in(capacity, System);
+        v.push(String::from("a"));
+        v.push(String::from("b"));
+        let arc: Arc<[String], System> = Arc::from(v);
+        assert_eq!(&*arc, ["a", "b"]);
+    }
+
+    let s = String::from_utf8(b"statue".to_vec_in(System)).unwrap();
+    let arc: Arc<str, System> = Arc::from(s);
+    assert_eq!(&*arc, "statue");
+}
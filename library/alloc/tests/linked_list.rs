@@ -19,3 +19,19 @@ fn test_hash() {
 
     assert!(hash(&x) == hash(&y));
 }
+
+#[test]
+fn test_new_in() {
+    use std::alloc::System;
+
+    let mut list = LinkedList::new_in(System);
+    list.push_back(2);
+    list.push_front(1);
+    list.push_back(3);
+
+    let tail = list.split_off(1);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1]);
+    assert_eq!(tail.into_iter().collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(list.pop_front(), Some(1));
+    assert!(list.is_empty());
+}
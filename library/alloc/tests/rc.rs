@@ -221,3 +221,41 @@ fn rc_from_vec_opt() {
         );
     }
 }
+
+#[test]
+fn rc_in_allocator() {
+    use std::alloc::System;
+
+    let five = Rc::new_in(5, System);
+    let weak = Rc::downgrade(&five);
+    assert_eq!(weak.upgrade().as_deref(), Some(&5));
+    assert_eq!(Rc::try_unwrap(five), Ok(5));
+    assert!(weak.upgrade().is_none());
+
+    let boxed: Box<dyn Any, System> = Box::new_in(String::from("any"), System);
+    let rc: Rc<dyn Any, System> = Rc::from(boxed);
+    let rc: Rc<String, System> = rc.downcast().unwrap();
+    assert_eq!(*rc, "any");
+
+    let (ptr, alloc) = Rc::into_raw_with_allocator(rc);
+    let rc = unsafe { Rc::from_raw_in(ptr, alloc) };
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn rc_from_vec_and_string_in_allocator() {
+    use std::alloc::System;
+
+    // The first allocation is too small to be reused, the second one is reused in place.
+    for capacity in [2, 16] {
+        let mut v = Vec::with_capacity_in(capacity, System);
+        v.push(String::from("a"));
+        v.push(String::from("b"));
+        let rc: Rc<[String], System> = Rc::from(v);
+        assert_eq!(&*rc, ["a", "b"]);
+    }
+
+    let s = String::from_utf8(b"statue".to_vec_in(System)).unwrap();
+    let rc: Rc<str, System> = Rc::from(s);
+    assert_eq!(&*rc, "statue");
+}
@@ -810,6 +810,22 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push() {
+    let mut s = String::try_with_capacity(2).unwrap();
+    assert!(s.capacity() >= 2);
+    s.try_push('a').unwrap();
+    s.try_push('ö').unwrap();
+    s.try_push_str("bc").unwrap();
+    s.try_push('\u{1F600}').unwrap();
+    assert_eq!(s, "aöbc\u{1F600}");
+
+    assert_matches!(
+        String::try_with_capacity(usize::MAX).map_err(|e| e.kind()),
+        Err(CapacityOverflow)
+    );
+}
+
 #[test]
 fn test_from_char() {
     assert_eq!(String::from('a'), 'a'.to_string());
@@ -824,3 +840,39 @@ fn test_str_concat() {
     let s: String = format!("{a}{b}");
     assert_eq!(s.as_bytes()[9], 'd' as u8);
 }
+
+#[test]
+fn test_string_in_allocator() {
+    use std::alloc::System;
+
+    let mut s = String::new_in(System);
+    s.push_str("hello");
+    s.push(' ');
+    s += "world";
+    assert_eq!(s, "hello world");
+
+    let tail = s.split_off(5);
+    assert_eq!(tail, " world");
+    assert_eq!(s.drain(..).collect::<String>(), "hello");
+
+    let bytes: Vec<u8, System> = String::from_utf8(b"abc".to_vec_in(System)).unwrap().into_bytes();
+    assert_eq!(bytes, b"abc");
+
+    let err = String::from_utf8([0xff].to_vec_in(System)).unwrap_err();
+    let bytes: Vec<u8, System> = err.into_bytes();
+    assert_eq!(bytes, [0xff]);
+}
+
+#[test]
+fn test_into_boxed_str_in_allocator() {
+    use std::alloc::System;
+
+    let mut s = String::with_capacity_in(16, System);
+    s.push_str("hello");
+    let boxed: Box<str, System> = s.into_boxed_str();
+    assert_eq!(&*boxed, "hello");
+
+    let s = String::from_utf8(b"world".to_vec_in(System)).unwrap();
+    let boxed: Box<str, System> = Box::from(s);
+    assert_eq!(&*boxed, "world");
+}
@@ -1813,6 +1813,73 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push() {
+    let mut vec = Vec::try_with_capacity(1).unwrap();
+    vec.try_push(1).unwrap();
+    vec.try_push(3).unwrap();
+    vec.try_insert(1, 2).unwrap();
+    vec.try_insert(3, 4).unwrap();
+    vec.try_extend_from_slice(&[5, 6, 7]).unwrap();
+    assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+
+    assert_matches!(
+        Vec::<u8>::try_with_capacity(isize::MAX as usize + 1).map_err(|e| e.kind()),
+        Err(CapacityOverflow)
+    );
+    assert_matches!(
+        Vec::<u32>::try_with_capacity(usize::MAX).map_err(|e| e.kind()),
+        Err(CapacityOverflow)
+    );
+}
+
+#[test]
+fn test_try_extend_from_slice_clone() {
+    let mut vec = vec![String::from("a")];
+    vec.try_extend_from_slice(&[String::from("b"), String::from("c")]).unwrap();
+    assert_eq!(vec, ["a", "b", "c"]);
+}
+
+#[test]
+#[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+fn test_try_insert_out_of_bounds() {
+    let mut vec = vec![1];
+    let _ = vec.try_insert(2, 2);
+}
+
+#[test]
+fn test_try_push_alloc_error() {
+    struct NoAlloc;
+
+    unsafe impl Allocator for NoAlloc {
+        fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+            Err(core::alloc::AllocError)
+        }
+        unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {
+            unreachable!()
+        }
+    }
+
+    assert_matches!(
+        Vec::<u32, _>::try_with_capacity_in(1, NoAlloc).map_err(|e| e.kind()),
+        Err(AllocError { .. })
+    );
+
+    let mut vec = Vec::new_in(NoAlloc);
+    assert_matches!(vec.try_push(1u32).map_err(|e| e.kind()), Err(AllocError { .. }));
+    assert_matches!(vec.try_insert(0, 1).map_err(|e| e.kind()), Err(AllocError { .. }));
+    assert_matches!(
+        vec.try_extend_from_slice(&[1, 2]).map_err(|e| e.kind()),
+        Err(AllocError { .. })
+    );
+    assert!(vec.is_empty());
+
+    // Nothing needs to be allocated for zero-sized types.
+    let mut units = Vec::new_in(NoAlloc);
+    units.try_push(()).unwrap();
+    assert_eq!(units.len(), 1);
+}
+
 #[test]
 fn test_stable_pointers() {
     /// Pull an element from the iterator, then drop it.
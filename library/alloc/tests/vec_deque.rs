@@ -1331,6 +1331,23 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push() {
+    let mut deque = VecDeque::try_with_capacity(1).unwrap();
+    for i in 0..10 {
+        deque.try_push_back(i).unwrap();
+        deque.try_push_front(-i).unwrap();
+    }
+    assert_eq!(deque.len(), 20);
+    assert_eq!(deque.front(), Some(&-9));
+    assert_eq!(deque.back(), Some(&9));
+
+    assert_matches!(
+        VecDeque::<u8>::try_with_capacity(usize::MAX).map_err(|e| e.kind()),
+        Err(CapacityOverflow)
+    );
+}
+
 #[test]
 fn test_rotate_nop() {
     let mut v: VecDeque<_> = (0..10).collect();
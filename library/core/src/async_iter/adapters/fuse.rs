@@ -0,0 +1,52 @@
+use crate::async_iter::AsyncIterator;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// An async iterator that yields `None` forever after the underlying async
+/// iterator yields `None` once.
+///
+/// This `struct` is created by the [`fuse`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`fuse`]: AsyncIterator::fuse
+#[must_use = "async iterators are lazy and do nothing unless polled"]
+#[unstable(feature = "async_iterator", issue = "79024")]
+#[derive(Clone, Debug)]
+pub struct Fuse<I> {
+    // NOTE: `None` once the underlying async iterator has been exhausted.
+    iter: Option<I>,
+}
+
+impl<I> Fuse<I> {
+    pub(in crate::async_iter) fn new(iter: I) -> Fuse<I> {
+        Fuse { iter: Some(iter) }
+    }
+}
+
+#[unstable(feature = "async_iterator", issue = "79024")]
+impl<I: AsyncIterator> AsyncIterator for Fuse<I> {
+    type Item = I::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        // SAFETY: `iter` is structurally pinned. It is never moved out of
+        // `self`, only dropped in place once exhausted.
+        let this = unsafe { self.get_unchecked_mut() };
+        let iter = match &mut this.iter {
+            Some(iter) => iter,
+            None => return Poll::Ready(None),
+        };
+        let next = unsafe { Pin::new_unchecked(iter) }.poll_next(cx);
+        if let Poll::Ready(None) = next {
+            this.iter = None;
+        }
+        next
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.iter {
+            Some(iter) => iter.size_hint(),
+            None => (0, Some(0)),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Adapters for composing async iterators.
+
+mod filter;
+mod fuse;
+mod map;
+mod take;
+mod then;
+
+pub use self::{filter::Filter, fuse::Fuse, map::Map, take::Take, then::Then};
@@ -0,0 +1,69 @@
+use crate::async_iter::AsyncIterator;
+use crate::fmt;
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::task::{ready, Context, Poll};
+
+/// An async iterator that maps the values of `iter` to futures with `f`, and
+/// yields the output of each future in turn.
+///
+/// This `struct` is created by the [`then`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`then`]: AsyncIterator::then
+#[must_use = "async iterators are lazy and do nothing unless polled"]
+#[unstable(feature = "async_iterator", issue = "79024")]
+pub struct Then<I, F, Fut> {
+    iter: I,
+    f: F,
+    future: Option<Fut>,
+}
+
+impl<I, F, Fut> Then<I, F, Fut> {
+    pub(in crate::async_iter) fn new(iter: I, f: F) -> Then<I, F, Fut> {
+        Then { iter, f, future: None }
+    }
+}
+
+#[unstable(feature = "async_iterator", issue = "79024")]
+impl<I: fmt::Debug, F, Fut: fmt::Debug> fmt::Debug for Then<I, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Then").field("iter", &self.iter).field("future", &self.future).finish()
+    }
+}
+
+#[unstable(feature = "async_iterator", issue = "79024")]
+impl<I: AsyncIterator, F, Fut> AsyncIterator for Then<I, F, Fut>
+where
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    type Item = Fut::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Fut::Output>> {
+        // SAFETY: `iter` and `future` are structurally pinned: neither is ever
+        // moved out of `self`, and `future` is only ever replaced in place,
+        // which drops the old value without moving it. `f` is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        loop {
+            if let Some(future) = &mut this.future {
+                let output = ready!(unsafe { Pin::new_unchecked(future) }.poll(cx));
+                this.future = None;
+                return Poll::Ready(Some(output));
+            }
+
+            let iter = unsafe { Pin::new_unchecked(&mut this.iter) };
+            match ready!(iter.poll_next(cx)) {
+                Some(item) => this.future = Some((this.f)(item)),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.future.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
@@ -1,3 +1,5 @@
+use crate::async_iter::{Collect, Filter, Fuse, Map, Next, Take, Then};
+use crate::future::Future;
 use crate::ops::DerefMut;
 use crate::pin::Pin;
 use crate::task::{Context, Poll};
@@ -46,6 +48,7 @@ pub trait AsyncIterator {
     /// Rust's usual rules apply: calls must never cause undefined behavior
     /// (memory corruption, incorrect use of `unsafe` functions, or the like),
     /// regardless of the async iterator's state.
+    #[cfg_attr(not(bootstrap), lang = "async_iterator_poll_next")]
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
 
     /// Returns the bounds on the remaining length of the async iterator.
@@ -78,6 +81,117 @@ pub trait AsyncIterator {
     fn size_hint(&self) -> (usize, Option<usize>) {
         (0, None)
     }
+
+    /// Returns a future that resolves to the next value of the async iterator.
+    ///
+    /// The future resolves to `None` when the async iterator is exhausted. This
+    /// is the `async` counterpart of [`Iterator::next`].
+    ///
+    /// Polling the returned future polls the async iterator in place, so the
+    /// async iterator has to be [`Unpin`]. Async iterators that are not `Unpin`
+    /// can be pinned first, for example with [`pin!`](crate::pin::pin).
+    #[inline]
+    #[unstable(feature = "async_iterator", issue = "79024")]
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next::new(self)
+    }
+
+    /// Takes a closure and creates an async iterator which calls that closure on
+    /// each element.
+    ///
+    /// This is the `async` counterpart of [`Iterator::map`]. The closure is
+    /// synchronous; use [`then`] to map each element with a future instead.
+    ///
+    /// [`then`]: AsyncIterator::then
+    #[inline]
+    #[unstable(feature = "async_iterator", issue = "79024")]
+    fn map<B, F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> B,
+    {
+        Map::new(self, f)
+    }
+
+    /// Creates an async iterator which uses a closure to determine if an element
+    /// should be yielded.
+    ///
+    /// Only the elements for which the closure returns `true` are yielded. This
+    /// is the `async` counterpart of [`Iterator::filter`].
+    #[inline]
+    #[unstable(feature = "async_iterator", issue = "79024")]
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// Creates an async iterator that yields the first `n` elements, or fewer
+    /// if the underlying async iterator ends sooner.
+    ///
+    /// Once `n` elements have been yielded, the underlying async iterator is not
+    /// polled again.
+    #[inline]
+    #[unstable(feature = "async_iterator", issue = "79024")]
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, n)
+    }
+
+    /// Takes a closure returning a future and creates an async iterator which
+    /// yields the output of that future for each element.
+    ///
+    /// Each future is polled to completion before the next element is pulled
+    /// from the underlying async iterator, so elements are yielded in order.
+    #[inline]
+    #[unstable(feature = "async_iterator", issue = "79024")]
+    fn then<F, Fut>(self, f: F) -> Then<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future,
+    {
+        Then::new(self, f)
+    }
+
+    /// Creates an async iterator which ends after the first `None`.
+    ///
+    /// After an async iterator returns `Poll::Ready(None)`, future calls to
+    /// `poll_next` may or may not yield `Some(T)` again. `fuse()` adapts an
+    /// async iterator, ensuring that after a `None` is given, it will always
+    /// return `Poll::Ready(None)` forever.
+    #[inline]
+    #[unstable(feature = "async_iterator", issue = "79024")]
+    fn fuse(self) -> Fuse<Self>
+    where
+        Self: Sized,
+    {
+        Fuse::new(self)
+    }
+
+    /// Returns a future that drains the async iterator into a collection.
+    ///
+    /// This is the `async` counterpart of [`Iterator::collect`]. Since the
+    /// async iterator may suspend between elements, the collection is built
+    /// up incrementally: it starts out as [`Default::default()`] and each
+    /// element is added with [`Extend`]. All of the standard collections that
+    /// implement [`FromIterator`] also implement both of these traits.
+    #[inline]
+    #[unstable(feature = "async_iterator", issue = "79024")]
+    fn collect<B>(self) -> Collect<Self, B>
+    where
+        Self: Sized,
+        B: Default + Extend<Self::Item>,
+    {
+        Collect::new(self)
+    }
 }
 
 #[unstable(feature = "async_iterator", issue = "79024")]
@@ -0,0 +1,57 @@
+use crate::async_iter::AsyncIterator;
+use crate::fmt;
+use crate::future::Future;
+use crate::mem;
+use crate::pin::Pin;
+use crate::task::{ready, Context, Poll};
+
+/// A future that drains an async iterator into a collection.
+///
+/// This `struct` is created by the [`collect`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`collect`]: AsyncIterator::collect
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[unstable(feature = "async_iterator", issue = "79024")]
+pub struct Collect<I, B> {
+    iter: I,
+    collection: B,
+}
+
+impl<I, B: Default> Collect<I, B> {
+    pub(super) fn new(iter: I) -> Collect<I, B> {
+        Collect { iter, collection: B::default() }
+    }
+}
+
+#[unstable(feature = "async_iterator", issue = "79024")]
+impl<I: fmt::Debug, B: fmt::Debug> fmt::Debug for Collect<I, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collect")
+            .field("iter", &self.iter)
+            .field("collection", &self.collection)
+            .finish()
+    }
+}
+
+#[unstable(feature = "async_iterator", issue = "79024")]
+impl<I, B> Future for Collect<I, B>
+where
+    I: AsyncIterator,
+    B: Default + Extend<I::Item>,
+{
+    type Output = B;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<B> {
+        // SAFETY: `iter` is structurally pinned and never moved out of `self`;
+        // `collection` is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut iter = unsafe { Pin::new_unchecked(&mut this.iter) };
+        loop {
+            match ready!(iter.as_mut().poll_next(cx)) {
+                Some(item) => this.collection.extend(Some(item)),
+                None => return Poll::Ready(mem::take(&mut this.collection)),
+            }
+        }
+    }
+}
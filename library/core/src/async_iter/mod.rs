@@ -121,8 +121,14 @@
 //! warning: unused result that must be used: async iterators do nothing unless polled
 //! ```
 
+mod adapters;
 mod async_iter;
+mod collect;
 mod from_iter;
+mod next;
 
+pub use adapters::{Filter, Fuse, Map, Take, Then};
 pub use async_iter::AsyncIterator;
+pub use collect::Collect;
 pub use from_iter::{from_iter, FromIter};
+pub use next::Next;
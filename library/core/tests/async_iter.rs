@@ -0,0 +1,91 @@
+use core::async_iter::{self, AsyncIterator};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::sync::Arc;
+use std::task::Wake;
+use std::thread;
+
+/// Yields `Poll::Pending` once before each value of `iter`.
+struct Yielding<I> {
+    iter: I,
+    yielded: bool,
+}
+
+impl<I: Iterator + Unpin> AsyncIterator for Yielding<I> {
+    type Item = I::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        let this = &mut *self;
+        if this.yielded {
+            this.yielded = false;
+            Poll::Ready(this.iter.next())
+        } else {
+            this.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn yielding<I: IntoIterator>(iter: I) -> Yielding<I::IntoIter> {
+    Yielding { iter: iter.into_iter(), yielded: false }
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    struct Waker;
+    impl Wake for Waker {
+        fn wake(self: Arc<Self>) {
+            thread::current().unpark()
+        }
+    }
+
+    let waker = Arc::new(Waker).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = Box::pin(fut);
+
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => break output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test_next() {
+    let mut iter = yielding([1, 2]);
+    assert_eq!(block_on(iter.next()), Some(1));
+    assert_eq!(block_on(iter.next()), Some(2));
+    assert_eq!(block_on(iter.next()), None);
+}
+
+#[test]
+fn test_map_filter_take() {
+    let iter = yielding(1..).map(|x| x * 2).filter(|x| x % 3 != 0).take(4);
+    assert_eq!(iter.size_hint(), (0, Some(4)));
+    let v: Vec<_> = block_on(iter.collect());
+    assert_eq!(v, [2, 4, 8, 10]);
+}
+
+#[test]
+fn test_then() {
+    let iter = async_iter::from_iter([1, 2, 3]).then(|x| async move { x + 1 });
+    let v: Vec<_> = block_on(iter.collect());
+    assert_eq!(v, [2, 3, 4]);
+}
+
+#[test]
+fn test_fuse() {
+    let mut iter = yielding([1]).fuse();
+    assert_eq!(block_on(iter.next()), Some(1));
+    assert_eq!(block_on(iter.next()), None);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(block_on(iter.next()), None);
+}
+
+#[test]
+fn test_collect_string() {
+    let s: String = block_on(async_iter::from_iter(['a', 'b', 'c']).collect());
+    assert_eq!(s, "abc");
+}
@@ -855,6 +855,53 @@ pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         map_entry(self.base.rustc_entry(key))
     }
 
+    /// Gets the entry for a borrowed form of a key in the map for in-place
+    /// manipulation.
+    ///
+    /// Unlike [`entry`], this doesn't need an owned key up front: the key is
+    /// only converted into `K`, through its [`From`] implementation, when a
+    /// value is actually inserted into a vacant entry. This avoids allocating
+    /// a key, e.g. a [`String`] looked up by `&str`, if it's already present.
+    ///
+    /// The key may be any borrowed form of the map's key type, but
+    /// [`Hash`] and [`Eq`] on the borrowed form *must* match those for
+    /// the key type.
+    ///
+    /// [`entry`]: HashMap::entry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut ids: HashMap<String, usize> = HashMap::new();
+    ///
+    /// for word in "the cat sat on the mat".split(' ') {
+    ///     let next_id = ids.len();
+    ///     ids.entry_ref(word).or_insert(next_id);
+    /// }
+    ///
+    /// assert_eq!(ids.len(), 5);
+    /// assert_eq!(ids["the"], 0);
+    /// assert_eq!(ids["mat"], 4);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn entry_ref<'a, 'b, Q: ?Sized>(&'a mut self, key: &'b Q) -> EntryRef<'a, 'b, K, Q, V, S>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let mut state = self.base.hasher().build_hasher();
+        key.hash(&mut state);
+        let hash = state.finish();
+        match self.base.raw_entry_mut().from_key_hashed_nocheck(hash, key) {
+            base::RawEntryMut::Occupied(base) => EntryRef::Occupied(OccupiedEntryRef { base }),
+            base::RawEntryMut::Vacant(base) => EntryRef::Vacant(VacantEntryRef { hash, key, base }),
+        }
+    }
+
     /// Returns a reference to the value corresponding to the key.
     ///
     /// The key may be any borrowed form of the map's key type, but
@@ -2125,6 +2172,68 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// A view into a single entry in a map, which may either be vacant or occupied,
+/// that holds a borrowed form of the key until a value is inserted.
+///
+/// This `enum` is constructed from the [`entry_ref`] method on [`HashMap`].
+///
+/// [`entry_ref`]: HashMap::entry_ref
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+pub enum EntryRef<'a, 'b, K: 'a, Q: ?Sized + 'b, V: 'a, S: 'a> {
+    /// An occupied entry.
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    Occupied(#[unstable(feature = "hash_entry_ref", issue = "none")] OccupiedEntryRef<'a, K, V, S>),
+
+    /// A vacant entry.
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    Vacant(
+        #[unstable(feature = "hash_entry_ref", issue = "none")] VacantEntryRef<'a, 'b, K, Q, V, S>,
+    ),
+}
+
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+impl<K: Debug, Q: ?Sized + Debug, V: Debug, S> Debug for EntryRef<'_, '_, K, Q, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            EntryRef::Vacant(ref v) => f.debug_tuple("EntryRef").field(v).finish(),
+            EntryRef::Occupied(ref o) => f.debug_tuple("EntryRef").field(o).finish(),
+        }
+    }
+}
+
+/// A view into an occupied entry in a `HashMap`.
+/// It is part of the [`EntryRef`] enum.
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+pub struct OccupiedEntryRef<'a, K: 'a, V: 'a, S: 'a> {
+    base: base::RawOccupiedEntryMut<'a, K, V, S>,
+}
+
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+impl<K: Debug, V: Debug, S> Debug for OccupiedEntryRef<'_, K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedEntryRef")
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A view into a vacant entry in a `HashMap`.
+/// It is part of the [`EntryRef`] enum.
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+pub struct VacantEntryRef<'a, 'b, K: 'a, Q: ?Sized + 'b, V: 'a, S: 'a> {
+    hash: u64,
+    key: &'b Q,
+    base: base::RawVacantEntryMut<'a, K, V, S>,
+}
+
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+impl<K, Q: ?Sized + Debug, V, S> Debug for VacantEntryRef<'_, '_, K, Q, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VacantEntryRef").field(&self.key).finish()
+    }
+}
+
 /// The error returned by [`try_insert`](HashMap::try_insert) when the key already exists.
 ///
 /// Contains the occupied entry, and the value that was not inserted.
@@ -3011,6 +3120,346 @@ pub fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V> {
     }
 }
 
+impl<'a, 'b, K, Q: ?Sized, V, S> EntryRef<'a, 'b, K, Q, V, S> {
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// map.entry_ref("poneyland").or_insert(3);
+    /// assert_eq!(map["poneyland"], 3);
+    ///
+    /// *map.entry_ref("poneyland").or_insert(10) *= 2;
+    /// assert_eq!(map["poneyland"], 6);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_insert(self, default: V) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, String> = HashMap::new();
+    /// let value = "hoho";
+    ///
+    /// map.entry_ref("poneyland").or_insert_with(|| value.to_string());
+    ///
+    /// assert_eq!(map["poneyland"], "hoho");
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default function.
+    /// This method allows for generating key-derived values for insertion by providing the default
+    /// function a reference to the borrowed key that was passed to the `.entry_ref(key)` method
+    /// call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, usize> = HashMap::new();
+    ///
+    /// map.entry_ref("poneyland").or_insert_with_key(|key| key.chars().count());
+    ///
+    /// assert_eq!(map["poneyland"], 9);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_insert_with_key<F: FnOnce(&Q) -> V>(self, default: F) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => {
+                let value = default(entry.key);
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Returns a reference to this entry's key, in its borrowed form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// assert_eq!(map.entry_ref("poneyland").key(), "poneyland");
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn key(&self) -> &Q
+    where
+        K: Borrow<Q>,
+    {
+        match *self {
+            EntryRef::Occupied(ref entry) => entry.key().borrow(),
+            EntryRef::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// map.entry_ref("poneyland")
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map["poneyland"], 42);
+    ///
+    /// map.entry_ref("poneyland")
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map["poneyland"], 43);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            EntryRef::Occupied(mut entry) => {
+                f(entry.get_mut());
+                EntryRef::Occupied(entry)
+            }
+            EntryRef::Vacant(entry) => EntryRef::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, 'b, K, Q: ?Sized, V: Default, S> EntryRef<'a, 'b, K, Q, V, S> {
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, Option<u32>> = HashMap::new();
+    /// map.entry_ref("poneyland").or_default();
+    ///
+    /// assert_eq!(map["poneyland"], None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_default(self) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(Default::default()),
+        }
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntryRef<'a, K, V, S> {
+    /// Gets a reference to the key in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.insert("poneyland".to_string(), 12);
+    ///
+    /// if let EntryRef::Occupied(o) = map.entry_ref("poneyland") {
+    ///     assert_eq!(o.key(), "poneyland");
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn key(&self) -> &K {
+        self.base.key()
+    }
+
+    /// Take the ownership of the key and value from the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.insert("poneyland".to_string(), 12);
+    ///
+    /// if let EntryRef::Occupied(o) = map.entry_ref("poneyland") {
+    ///     assert_eq!(o.remove_entry(), ("poneyland".to_string(), 12));
+    /// }
+    ///
+    /// assert_eq!(map.contains_key("poneyland"), false);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn remove_entry(self) -> (K, V) {
+        self.base.remove_entry()
+    }
+
+    /// Gets a reference to the value in the entry.
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn get(&self) -> &V {
+        self.base.get()
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference to the `OccupiedEntryRef` which may outlive the
+    /// destruction of the `EntryRef` value, see [`into_mut`].
+    ///
+    /// [`into_mut`]: Self::into_mut
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn get_mut(&mut self) -> &mut V {
+        self.base.get_mut()
+    }
+
+    /// Converts the `OccupiedEntryRef` into a mutable reference to the value in the entry
+    /// with a lifetime bound to the map itself.
+    ///
+    /// If you need multiple references to the `OccupiedEntryRef`, see [`get_mut`].
+    ///
+    /// [`get_mut`]: Self::get_mut
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn into_mut(self) -> &'a mut V {
+        self.base.into_mut()
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.insert("poneyland".to_string(), 12);
+    ///
+    /// if let EntryRef::Occupied(mut o) = map.entry_ref("poneyland") {
+    ///     assert_eq!(o.insert(15), 12);
+    /// }
+    ///
+    /// assert_eq!(map["poneyland"], 15);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn insert(&mut self, value: V) -> V {
+        self.base.insert(value)
+    }
+
+    /// Takes the value out of the entry, and returns it.
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn remove(self) -> V {
+        self.base.remove()
+    }
+}
+
+impl<'a, 'b, K, Q: ?Sized, V, S> VacantEntryRef<'a, 'b, K, Q, V, S> {
+    /// Gets a reference to the borrowed key that would be used when inserting
+    /// a value through the `VacantEntryRef`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// assert_eq!(map.entry_ref("poneyland").key(), "poneyland");
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn key(&self) -> &'b Q {
+        self.key
+    }
+
+    /// Sets the value of the entry, converting the borrowed key into an owned
+    /// key, and returns a mutable reference to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// if let EntryRef::Vacant(o) = map.entry_ref("poneyland") {
+    ///     o.insert(37);
+    /// }
+    /// assert_eq!(map["poneyland"], 37);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn insert(self, value: V) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        // The owned key hashes the same as the borrowed one, so there's no need to hash it again.
+        self.base.insert_hashed_nocheck(self.hash, K::from(self.key), value).1
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K, V, S> FromIterator<(K, V)> for HashMap<K, V, S>
 where
@@ -699,6 +699,54 @@ fn test_entry() {
     assert_eq!(map.len(), 6);
 }
 
+#[test]
+fn test_entry_ref() {
+    use super::EntryRef;
+
+    let mut map: HashMap<String, i32> =
+        [("a", 10), ("b", 20), ("c", 30)].iter().map(|&(k, v)| (k.to_string(), v)).collect();
+
+    // Existing key (insert)
+    match map.entry_ref("a") {
+        EntryRef::Vacant(_) => unreachable!(),
+        EntryRef::Occupied(mut view) => {
+            assert_eq!(view.key(), "a");
+            assert_eq!(view.insert(100), 10);
+        }
+    }
+    assert_eq!(map["a"], 100);
+
+    // Existing key (take)
+    match map.entry_ref("b") {
+        EntryRef::Vacant(_) => unreachable!(),
+        EntryRef::Occupied(view) => assert_eq!(view.remove_entry(), ("b".to_string(), 20)),
+    }
+    assert_eq!(map.get("b"), None);
+    assert_eq!(map.len(), 2);
+
+    // Inexistent key (insert)
+    match map.entry_ref("d") {
+        EntryRef::Occupied(_) => unreachable!(),
+        EntryRef::Vacant(view) => {
+            assert_eq!(view.key(), "d");
+            assert_eq!(*view.insert(1000), 1000);
+        }
+    }
+    assert_eq!(map["d"], 1000);
+    assert_eq!(map.len(), 3);
+
+    *map.entry_ref("c").and_modify(|v| *v += 1).or_insert(0) += 1;
+    assert_eq!(map["c"], 32);
+    assert_eq!(*map.entry_ref("eee").or_insert_with_key(|k| k.len() as i32), 3);
+    assert_eq!(*map.entry_ref("f").or_default(), 0);
+    assert_eq!(map.len(), 5);
+
+    // The entries are found by looking up the owned keys.
+    for key in ["a", "c", "d", "eee", "f"] {
+        assert!(map.contains_key(&key.to_string()));
+    }
+}
+
 #[test]
 fn test_entry_take_doesnt_corrupt() {
     #![allow(deprecated)] //rand
@@ -219,6 +219,53 @@ pub struct DirBuilder {
     recursive: bool,
 }
 
+/// A handle to an open directory, which file system operations can be
+/// performed relative to.
+///
+/// Paths passed to the methods of `Dir` are resolved relative to the
+/// directory itself rather than to the current working directory, and keep
+/// referring to the same directory even if it is renamed or replaced after
+/// being opened. This allows walking a directory tree without the
+/// time-of-check to time-of-use races inherent to building full paths.
+///
+/// Note that the paths are otherwise resolved as usual: absolute paths
+/// ignore the directory, and `..` components as well as symbolic links can
+/// lead outside of it. To stay inside a tree, open one component at a time
+/// with [`Dir::open_dir_nofollow`], which refuses to follow a symbolic link
+/// as part of the same operation that opens the directory. Checking the
+/// component with [`Dir::symlink_metadata`] before opening it is not enough,
+/// as it could be replaced with a symbolic link in between.
+///
+/// # Platform-specific behavior
+///
+/// On Unix, this is backed by a directory file descriptor and the `openat`
+/// family of functions. Other platforms don't support directory handles yet,
+/// and [`Dir::open`] returns an [`Unsupported`] error there.
+///
+/// [`Unsupported`]: io::ErrorKind::Unsupported
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(dirfd)]
+/// use std::fs::{Dir, OpenOptions};
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     let dir = Dir::open("/srv/uploads")?;
+///     let mut options = OpenOptions::new();
+///     options.write(true).create_new(true);
+///     let mut file = dir.open_file("incoming.tmp", &options)?;
+///     file.write_all(b"hello")?;
+///     dir.rename("incoming.tmp", &dir, "hello.txt")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "dirfd", issue = "none")]
+pub struct Dir {
+    inner: fs_imp::Dir,
+}
+
 /// Read the entire contents of a file into a bytes vector.
 ///
 /// This is a convenience function for using [`File::open`] and [`read_to_end`]
@@ -2485,6 +2532,154 @@ fn as_inner_mut(&mut self) -> &mut fs_imp::DirBuilder {
     }
 }
 
+impl Dir {
+    /// Opens the directory at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` doesn't exist or isn't a
+    /// directory, or if the platform doesn't support directory handles.
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dir> {
+        fs_imp::Dir::open(path.as_ref()).map(|inner| Dir { inner })
+    }
+
+    /// Opens the directory at `path`, relative to this directory.
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn open_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<Dir> {
+        self.inner.open_dir(path.as_ref()).map(|inner| Dir { inner })
+    }
+
+    /// Opens the directory at `path`, relative to this directory, without
+    /// following a symbolic link in its last component.
+    ///
+    /// Symbolic links in the other components of `path` are still followed,
+    /// so pass a single file name to walk a tree without leaving it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the last component of `path` is
+    /// a symbolic link, or if `path` doesn't exist or isn't a directory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(dirfd)]
+    /// use std::fs::Dir;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let root = Dir::open("/srv/uploads")?;
+    ///     // Fails instead of escaping `/srv/uploads` if `user` is a symbolic link.
+    ///     let user = root.open_dir_nofollow("user")?;
+    ///     user.remove_file("incoming.tmp")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn open_dir_nofollow<P: AsRef<Path>>(&self, path: P) -> io::Result<Dir> {
+        self.inner.open_dir_nofollow(path.as_ref()).map(|inner| Dir { inner })
+    }
+
+    /// Opens the file at `path`, relative to this directory, with the options
+    /// specified by `opts`.
+    ///
+    /// This is the directory-relative counterpart of [`OpenOptions::open`].
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn open_file<P: AsRef<Path>>(&self, path: P, opts: &OpenOptions) -> io::Result<File> {
+        self.inner.open_file(path.as_ref(), &opts.0).map(|inner| File { inner })
+    }
+
+    /// Creates a new, empty directory at `path`, relative to this directory.
+    ///
+    /// This is the directory-relative counterpart of [`create_dir`].
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn create_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.create_dir(path.as_ref())
+    }
+
+    /// Removes the file at `path`, relative to this directory.
+    ///
+    /// This is the directory-relative counterpart of [`remove_file`].
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn remove_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.remove_file(path.as_ref())
+    }
+
+    /// Removes the empty directory at `path`, relative to this directory.
+    ///
+    /// This is the directory-relative counterpart of [`remove_dir`].
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn remove_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.remove_dir(path.as_ref())
+    }
+
+    /// Renames `from`, relative to this directory, to `to`, relative to
+    /// `to_dir`, replacing the original file if `to` already exists.
+    ///
+    /// This is the directory-relative counterpart of [`rename`]. Both
+    /// directories must be on the same file system.
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        from: P,
+        to_dir: &Dir,
+        to: Q,
+    ) -> io::Result<()> {
+        self.inner.rename(from.as_ref(), &to_dir.inner, to.as_ref())
+    }
+
+    /// Queries metadata about the file at `path`, relative to this directory,
+    /// following symbolic links.
+    ///
+    /// This is the directory-relative counterpart of [`metadata`].
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn metadata<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.inner.metadata(path.as_ref()).map(Metadata)
+    }
+
+    /// Queries metadata about the file at `path`, relative to this directory,
+    /// without following symbolic links.
+    ///
+    /// This is the directory-relative counterpart of [`symlink_metadata`].
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn symlink_metadata<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.inner.symlink_metadata(path.as_ref()).map(Metadata)
+    }
+
+    /// Returns an iterator over the entries of this directory.
+    ///
+    /// Unlike with [`read_dir`], the [`DirEntry::path`] of the returned
+    /// entries is just their file name, to be used with the methods of this
+    /// `Dir`.
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.inner.read_dir().map(ReadDir)
+    }
+
+    /// Creates a new symbolic link at `link`, relative to this directory,
+    /// pointing to `original`.
+    ///
+    /// `original` is stored as is, so a relative `original` is resolved
+    /// relative to the directory containing the link when it's followed.
+    #[unstable(feature = "dirfd", issue = "none")]
+    pub fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(&self, original: P, link: Q) -> io::Result<()> {
+        self.inner.symlink(original.as_ref(), link.as_ref())
+    }
+}
+
+#[unstable(feature = "dirfd", issue = "none")]
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl AsInner<fs_imp::Dir> for Dir {
+    fn as_inner(&self) -> &fs_imp::Dir {
+        &self.inner
+    }
+}
+
 /// Returns `Ok(true)` if the path points at an existing entity.
 ///
 /// This function will traverse symbolic links to query information about the
@@ -1551,3 +1551,57 @@ fn hiberfil_sys() {
     fs::metadata(hiberfil).unwrap();
     assert_eq!(true, hiberfil.exists());
 }
+
+#[test]
+#[cfg(unix)]
+fn dir_relative_operations() {
+    use crate::fs::Dir;
+
+    let tmpdir = tmpdir();
+    let dir = check!(Dir::open(tmpdir.path()));
+
+    check!(dir.create_dir("sub"));
+    let sub = check!(dir.open_dir("sub"));
+    let mut opts = OpenOptions::new();
+    opts.write(true).create_new(true);
+    check!(check!(sub.open_file("a.txt", &opts)).write_all(b"hello"));
+    assert_eq!(check!(fs::read_to_string(tmpdir.join("sub/a.txt"))), "hello");
+
+    // The handle keeps referring to the same directory after it's renamed.
+    check!(fs::rename(tmpdir.join("sub"), tmpdir.join("moved")));
+    assert_eq!(check!(sub.metadata("a.txt")).len(), 5);
+
+    check!(sub.rename("a.txt", &dir, "b.txt"));
+    assert!(check!(dir.metadata("b.txt")).is_file());
+    assert_eq!(sub.metadata("a.txt").unwrap_err().kind(), ErrorKind::NotFound);
+
+    check!(dir.symlink("b.txt", "link"));
+    assert!(check!(dir.symlink_metadata("link")).file_type().is_symlink());
+    assert!(check!(dir.metadata("link")).is_file());
+
+    check!(dir.symlink("moved", "dir_link"));
+    check!(dir.open_dir("dir_link"));
+    dir.open_dir_nofollow("dir_link").unwrap_err();
+    check!(check!(dir.open_dir_nofollow("moved")).metadata("."));
+    check!(dir.remove_file("dir_link"));
+
+    let mut names: Vec<_> = check!(dir.read_dir())
+        .map(|entry| {
+            let entry = check!(entry);
+            assert_eq!(entry.path(), Path::new(&entry.file_name()));
+            // Entry metadata must be resolved relative to the directory, not the working directory.
+            let is_symlink = check!(entry.metadata()).file_type().is_symlink();
+            (entry.file_name().into_string().unwrap(), is_symlink)
+        })
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [("b.txt".to_owned(), false), ("link".to_owned(), true), ("moved".to_owned(), false)]
+    );
+
+    check!(dir.remove_file("link"));
+    check!(dir.remove_file("b.txt"));
+    check!(dir.remove_dir("moved"));
+    assert_eq!(check!(dir.read_dir()).count(), 0);
+}
@@ -279,6 +279,14 @@ fn as_fd(&self) -> BorrowedFd<'_> {
     }
 }
 
+#[unstable(feature = "dirfd", issue = "none")]
+impl AsFd for fs::Dir {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.as_inner().as_fd()
+    }
+}
+
 #[stable(feature = "io_safety", since = "1.63.0")]
 impl From<fs::File> for OwnedFd {
     #[inline]
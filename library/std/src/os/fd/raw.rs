@@ -173,6 +173,14 @@ fn into_raw_fd(self) -> RawFd {
     }
 }
 
+#[unstable(feature = "dirfd", issue = "none")]
+impl AsRawFd for fs::Dir {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.as_inner().as_fd().as_raw_fd()
+    }
+}
+
 #[stable(feature = "asraw_stdio", since = "1.21.0")]
 impl AsRawFd for io::Stdin {
     #[inline]
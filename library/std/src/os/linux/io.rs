@@ -0,0 +1,109 @@
+//! Linux-specific extensions to the [`std::io`] module.
+//!
+//! [`std::io`]: crate::io
+
+#![unstable(feature = "linux_copy_range", issue = "none")]
+
+use crate::io;
+use crate::os::fd::{AsFd, AsRawFd};
+
+/// The mechanism that [`copy_range`] used to transfer the data.
+#[unstable(feature = "linux_copy_range", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CopyMethod {
+    /// [`copy_file_range(2)`], between two regular files. On some file
+    /// systems this shares the underlying storage instead of copying it.
+    ///
+    /// [`copy_file_range(2)`]: https://man7.org/linux/man-pages/man2/copy_file_range.2.html
+    CopyFileRange,
+    /// [`sendfile(2)`], from a regular file or block device, most commonly
+    /// to a socket.
+    ///
+    /// [`sendfile(2)`]: https://man7.org/linux/man-pages/man2/sendfile.2.html
+    Sendfile,
+    /// [`splice(2)`], to or from a pipe.
+    ///
+    /// [`splice(2)`]: https://man7.org/linux/man-pages/man2/splice.2.html
+    Splice,
+    /// A buffered copy through `read(2)` or `pread(2)` and `write(2)`.
+    ReadWrite,
+}
+
+/// Copies up to `len` bytes from `reader` to `writer`, letting the kernel move
+/// the data directly between the file descriptors where possible.
+///
+/// If `offset` is `Some`, the data is read starting at that offset, and the
+/// file position of `reader` is left unchanged, like with [`read_at`].
+/// Otherwise the data is read from the current file position of `reader`,
+/// which is advanced past it. The data is always written at the current file
+/// position of `writer`.
+///
+/// `reader` and `writer` can be any kind of file descriptor, e.g. a [`File`],
+/// a [`TcpStream`] or either end of a pipe. This function tries
+/// [`copy_file_range(2)`], [`sendfile(2)`] and [`splice(2)`] in that order,
+/// depending on the file types, and falls back to a buffered copy if none of
+/// them can handle the pair of file descriptors. On success it returns the
+/// number of bytes copied, which is only less than `len` if the end of
+/// `reader` was reached, together with the [`CopyMethod`] that completed the
+/// copy.
+///
+/// Unlike [`io::copy`], this function doesn't know about any data buffered
+/// in userspace, e.g. by a [`BufReader`] or [`BufWriter`], which has to be
+/// flushed beforehand.
+///
+/// # Errors
+///
+/// This function will return an error if reading from `reader` or writing to
+/// `writer` fails, for example when `offset` is given but `reader` isn't
+/// seekable. If some bytes were already copied when the error occurred, they
+/// are not reported, and the file position of `reader` is unspecified when
+/// no `offset` was given.
+///
+/// An error of the [`InvalidInput`] kind is returned if `offset` is larger
+/// than [`i64::MAX`].
+///
+/// # Examples
+///
+/// Sending part of a file over a socket, for example to answer an HTTP range
+/// request:
+///
+/// ```no_run
+/// #![feature(linux_copy_range)]
+/// use std::fs::File;
+/// use std::net::TcpStream;
+/// use std::os::linux::io::copy_range;
+///
+/// fn main() -> std::io::Result<()> {
+///     let file = File::open("static/video.mp4")?;
+///     let stream = TcpStream::connect("127.0.0.1:8080")?;
+///
+///     let (sent, method) = copy_range(&file, Some(1024), &stream, 4096)?;
+///     println!("sent {sent} bytes using {method:?}");
+///     Ok(())
+/// }
+/// ```
+///
+/// [`read_at`]: crate::os::unix::fs::FileExt::read_at
+/// [`File`]: crate::fs::File
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`BufReader`]: io::BufReader
+/// [`BufWriter`]: io::BufWriter
+/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+/// [`copy_file_range(2)`]: https://man7.org/linux/man-pages/man2/copy_file_range.2.html
+/// [`sendfile(2)`]: https://man7.org/linux/man-pages/man2/sendfile.2.html
+/// [`splice(2)`]: https://man7.org/linux/man-pages/man2/splice.2.html
+#[unstable(feature = "linux_copy_range", issue = "none")]
+pub fn copy_range<R: AsFd + ?Sized, W: AsFd + ?Sized>(
+    reader: &R,
+    offset: Option<u64>,
+    writer: &W,
+    len: u64,
+) -> io::Result<(u64, CopyMethod)> {
+    crate::sys::kernel_copy::copy_range(
+        reader.as_fd().as_raw_fd(),
+        offset,
+        writer.as_fd().as_raw_fd(),
+        len,
+    )
+}
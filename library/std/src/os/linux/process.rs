@@ -6,14 +6,15 @@
 
 use crate::io::Result;
 use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
-use crate::process;
+use crate::process::{self, ExitStatus};
 use crate::sealed::Sealed;
 #[cfg(not(doc))]
-use crate::sys::fd::FileDesc;
+use crate::sys::pidfd::PidFd as InnerPidFd;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::Duration;
 
 #[cfg(doc)]
-struct FileDesc;
+struct InnerPidFd;
 
 /// This type represents a file descriptor that refers to a process.
 ///
@@ -38,6 +39,16 @@
 ///
 /// // The file descriptor will be closed when `pidfd` is dropped.
 /// ```
+///
+/// Since a pidfd keeps referring to the same process, signals sent and waits
+/// performed through it can't affect an unrelated process that reused the
+/// PID, even after the child has been reaped.
+///
+/// A pidfd becomes readable once the process exits. It can therefore be
+/// registered with `poll(2)`, `epoll(7)` or an async runtime through
+/// [`AsRawFd`] or [`AsFd`], and then reaped without blocking with
+/// [`try_wait`].
+///
 /// Refer to the man page of [`pidfd_open(2)`] for further details.
 ///
 /// [`Command`]: process::Command
@@ -45,26 +56,104 @@
 /// [`Child`]: process::Child
 /// [`pidfd`]: fn@ChildExt::pidfd
 /// [`take_pidfd`]: ChildExt::take_pidfd
+/// [`try_wait`]: PidFd::try_wait
 /// [`pidfd_open(2)`]: https://man7.org/linux/man-pages/man2/pidfd_open.2.html
 #[derive(Debug)]
 pub struct PidFd {
-    inner: FileDesc,
+    inner: InnerPidFd,
+}
+
+impl PidFd {
+    /// Forces the process to exit by sending it `SIGKILL`.
+    ///
+    /// This is equivalent to sending a `SIGKILL` with [`send_signal`], and
+    /// is never delivered to another process, even if the PID was reused.
+    ///
+    /// [`send_signal`]: PidFd::send_signal
+    pub fn kill(&self) -> Result<()> {
+        self.inner.kill()
+    }
+
+    /// Sends the signal `signal` to the process, using `pidfd_send_signal(2)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process has already been reaped, or if the
+    /// signal number is invalid.
+    pub fn send_signal(&self, signal: i32) -> Result<()> {
+        self.inner.send_signal(signal)
+    }
+
+    /// Waits for the process to exit, and returns its exit status.
+    ///
+    /// This reaps the process, so waiting on it again through this pidfd or
+    /// through the [`Child`] it belongs to will return an error afterwards.
+    ///
+    /// This requires Linux 5.4 or later.
+    ///
+    /// [`Child`]: process::Child
+    pub fn wait(&self) -> Result<ExitStatus> {
+        self.inner.wait().map(FromInner::from_inner)
+    }
+
+    /// Reaps the process if it has exited, without blocking.
+    ///
+    /// Returns `Ok(None)` if the process is still running. Like [`wait`],
+    /// this reaps the process once it has exited.
+    ///
+    /// This requires Linux 5.4 or later.
+    ///
+    /// [`wait`]: PidFd::wait
+    pub fn try_wait(&self) -> Result<Option<ExitStatus>> {
+        Ok(self.inner.try_wait()?.map(FromInner::from_inner))
+    }
+
+    /// Waits for the process to exit, but only for a limited time.
+    ///
+    /// Returns `Ok(None)` if the process is still running once `timeout` has
+    /// elapsed. Like [`wait`], this reaps the process once it has exited.
+    ///
+    /// This requires Linux 5.4 or later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_pidfd)]
+    /// use std::os::linux::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("10").create_pidfd(true).spawn()?;
+    /// let pidfd = child.take_pidfd()?;
+    ///
+    /// if pidfd.wait_timeout(Duration::from_secs(1))?.is_none() {
+    ///     pidfd.kill()?;
+    ///     let status = pidfd.wait()?;
+    ///     assert!(!status.success());
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    ///
+    /// [`wait`]: PidFd::wait
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<Option<ExitStatus>> {
+        Ok(self.inner.wait_timeout(timeout)?.map(FromInner::from_inner))
+    }
 }
 
-impl AsInner<FileDesc> for PidFd {
-    fn as_inner(&self) -> &FileDesc {
+impl AsInner<InnerPidFd> for PidFd {
+    fn as_inner(&self) -> &InnerPidFd {
         &self.inner
     }
 }
 
-impl FromInner<FileDesc> for PidFd {
-    fn from_inner(inner: FileDesc) -> PidFd {
+impl FromInner<InnerPidFd> for PidFd {
+    fn from_inner(inner: InnerPidFd) -> PidFd {
         PidFd { inner }
     }
 }
 
-impl IntoInner<FileDesc> for PidFd {
-    fn into_inner(self) -> FileDesc {
+impl IntoInner<InnerPidFd> for PidFd {
+    fn into_inner(self) -> InnerPidFd {
         self.inner
     }
 }
@@ -77,7 +166,7 @@ fn as_raw_fd(&self) -> RawFd {
 
 impl FromRawFd for PidFd {
     unsafe fn from_raw_fd(fd: RawFd) -> Self {
-        Self::from_inner(FileDesc::from_raw_fd(fd))
+        Self::from_inner(InnerPidFd::from_raw_fd(fd))
     }
 }
 
@@ -95,7 +184,7 @@ fn as_fd(&self) -> BorrowedFd<'_> {
 
 impl From<OwnedFd> for PidFd {
     fn from(fd: OwnedFd) -> Self {
-        Self::from_inner(FileDesc::from_inner(fd))
+        Self::from_inner(InnerPidFd::from_inner(fd))
     }
 }
 
@@ -130,6 +219,11 @@ pub trait ChildExt: Sealed {
     /// Even if requested, a pidfd may not be available due to an older
     /// version of Linux being in use, or if some other error occurred.
     ///
+    /// The [`Child`] keeps a duplicate of the pidfd for itself. Waiting on the
+    /// [`Child`] keeps going through that duplicate, so it can't reap an
+    /// unrelated process that reused the PID after the process was reaped
+    /// through the returned [`PidFd`].
+    ///
     /// [`Command`]: process::Command
     /// [`create_pidfd`]: CommandExt::create_pidfd
     /// [`Child`]: process::Child
@@ -150,6 +244,10 @@ pub trait CommandExt: Sealed {
     /// in a guaranteed race-free manner (e.g. if the `clone3` system call
     /// is supported). Otherwise, [`pidfd`] will return an error.
     ///
+    /// If a pidfd was created, [`Child::kill`] sends its signal through the
+    /// pidfd rather than the PID.
+    ///
+    /// [`Child::kill`]: process::Child::kill
     /// [`Command`]: process::Command
     /// [`Child`]: process::Child
     /// [`pidfd`]: fn@ChildExt::pidfd
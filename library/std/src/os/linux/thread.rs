@@ -0,0 +1,261 @@
+//! Linux-specific extensions to primitives in the [`std::thread`] module.
+//!
+//! [`std::thread`]: crate::thread
+
+#![unstable(feature = "linux_thread_builder", issue = "none")]
+
+use crate::fmt;
+use crate::io;
+use crate::sealed::Sealed;
+#[cfg(not(doc))]
+use crate::sys::thread::{CpuSet as InnerCpuSet, SchedPolicy as InnerSchedPolicy};
+use crate::sys_common::AsInnerMut;
+use crate::thread;
+
+#[cfg(doc)]
+#[derive(Clone, PartialEq, Eq)]
+struct InnerCpuSet;
+
+#[cfg(doc)]
+impl InnerCpuSet {
+    const CAPACITY: usize = 1024;
+}
+
+/// A set of CPUs that a thread is allowed to run on.
+///
+/// CPUs are identified by their index, starting at 0. A set can hold CPUs with
+/// an index below [`CpuSet::CAPACITY`].
+///
+/// # Examples
+///
+/// ```
+/// #![feature(linux_thread_builder)]
+/// use std::os::linux::thread::CpuSet;
+///
+/// let cpus: CpuSet = [0, 2].into_iter().collect();
+/// assert!(cpus.contains(2));
+/// assert!(!cpus.contains(1));
+/// assert_eq!(cpus.len(), 2);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct CpuSet {
+    inner: InnerCpuSet,
+}
+
+impl CpuSet {
+    /// The number of CPUs a set can hold, which is `CPU_SETSIZE`.
+    pub const CAPACITY: usize = InnerCpuSet::CAPACITY;
+
+    /// Creates an empty set.
+    #[must_use]
+    pub fn new() -> CpuSet {
+        CpuSet { inner: InnerCpuSet::new() }
+    }
+
+    /// Adds the CPU with index `cpu` to the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cpu` is not below [`CpuSet::CAPACITY`].
+    pub fn insert(&mut self, cpu: usize) {
+        self.inner.insert(cpu)
+    }
+
+    /// Removes the CPU with index `cpu` from the set.
+    pub fn remove(&mut self, cpu: usize) {
+        self.inner.remove(cpu)
+    }
+
+    /// Returns `true` if the set contains the CPU with index `cpu`.
+    #[must_use]
+    pub fn contains(&self, cpu: usize) -> bool {
+        self.inner.contains(cpu)
+    }
+
+    /// Returns the number of CPUs in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the set contains no CPUs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the indices of the CPUs in the set, in
+    /// ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inner.iter()
+    }
+}
+
+impl Default for CpuSet {
+    /// Creates an empty set.
+    fn default() -> CpuSet {
+        CpuSet::new()
+    }
+}
+
+impl fmt::Debug for CpuSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for CpuSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> CpuSet {
+        let mut set = CpuSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for CpuSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for cpu in iter {
+            self.insert(cpu);
+        }
+    }
+}
+
+/// Returns the set of CPUs the current thread is allowed to run on.
+///
+/// This is the thread's CPU affinity mask, as returned by
+/// [`sched_getaffinity(2)`].
+///
+/// # Examples
+///
+/// ```
+/// #![feature(linux_thread_builder)]
+/// use std::os::linux::thread::current_affinity;
+///
+/// let cpus = current_affinity().expect("failed to query the affinity");
+/// assert!(!cpus.is_empty());
+/// ```
+///
+/// [`sched_getaffinity(2)`]: https://man7.org/linux/man-pages/man2/sched_getaffinity.2.html
+pub fn current_affinity() -> io::Result<CpuSet> {
+    InnerCpuSet::current().map(|inner| CpuSet { inner })
+}
+
+/// A scheduling policy for a thread.
+///
+/// See [`sched(7)`] for a description of each policy.
+///
+/// [`sched(7)`]: https://man7.org/linux/man-pages/man7/sched.7.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchedPolicy {
+    /// `SCHED_OTHER`, the default round-robin time-sharing policy.
+    Other,
+    /// `SCHED_BATCH`, for CPU-intensive threads that are not interactive.
+    Batch,
+    /// `SCHED_IDLE`, for threads that should only run when the system is
+    /// otherwise idle.
+    Idle,
+    /// `SCHED_FIFO` with the given static priority, a first-in first-out
+    /// real-time policy.
+    Fifo(i32),
+    /// `SCHED_RR` with the given static priority, a round-robin real-time
+    /// policy.
+    RoundRobin(i32),
+}
+
+/// Linux-specific extensions to [`thread::Builder`].
+///
+/// The options set through these methods are applied to the new thread before
+/// it starts running the closure passed to [`spawn`], so the closure never runs
+/// with the default settings. If an option can't be applied, the thread does
+/// not run the closure and [`spawn`] returns the error.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_thread_builder)]
+/// use std::os::linux::thread::{BuilderExt, CpuSet, SchedPolicy};
+/// use std::thread;
+///
+/// let handle = thread::Builder::new()
+///     .affinity([0].into_iter().collect::<CpuSet>())
+///     .sched_policy(SchedPolicy::Fifo(10))
+///     .spawn(|| {
+///         // latency-sensitive work, pinned to CPU 0
+///     })
+///     .expect("failed to spawn thread");
+/// handle.join().unwrap();
+/// ```
+///
+/// [`spawn`]: thread::Builder::spawn
+pub trait BuilderExt: Sealed {
+    /// Sets the CPUs the new thread is allowed to run on, using
+    /// [`sched_setaffinity(2)`].
+    ///
+    /// By default, the new thread inherits the affinity of the spawning
+    /// thread.
+    ///
+    /// [`sched_setaffinity(2)`]: https://man7.org/linux/man-pages/man2/sched_setaffinity.2.html
+    fn affinity(self, cpus: CpuSet) -> Self;
+
+    /// Sets the nice value of the new thread, using [`setpriority(2)`].
+    ///
+    /// Nice values range from -20 (highest priority) to 19 (lowest priority).
+    /// Lowering the nice value below that of the spawning thread usually
+    /// requires the `CAP_SYS_NICE` capability. By default, the new thread
+    /// inherits the nice value of the spawning thread.
+    ///
+    /// [`setpriority(2)`]: https://man7.org/linux/man-pages/man2/setpriority.2.html
+    fn nice(self, nice: i32) -> Self;
+
+    /// Sets the scheduling policy of the new thread, using
+    /// [`sched_setscheduler(2)`].
+    ///
+    /// The real-time policies usually require the `CAP_SYS_NICE` capability.
+    /// By default, the new thread inherits the scheduling policy of the
+    /// spawning thread.
+    ///
+    /// [`sched_setscheduler(2)`]: https://man7.org/linux/man-pages/man2/sched_setscheduler.2.html
+    fn sched_policy(self, policy: SchedPolicy) -> Self;
+
+    /// Sets the size in bytes of the guard area below the new thread's stack,
+    /// using [`pthread_attr_setguardsize(3)`].
+    ///
+    /// The size is rounded up to a multiple of the page size. The guard area is
+    /// used to detect stack overflows, so it can't be disabled: [`spawn`]
+    /// returns an error of kind [`InvalidInput`] if `size` is 0. By default,
+    /// the guard area is a single page.
+    ///
+    /// [`spawn`]: thread::Builder::spawn
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    /// [`pthread_attr_setguardsize(3)`]: https://man7.org/linux/man-pages/man3/pthread_attr_setguardsize.3.html
+    fn guard_size(self, size: usize) -> Self;
+}
+
+impl BuilderExt for thread::Builder {
+    fn affinity(mut self, cpus: CpuSet) -> Self {
+        self.as_inner_mut().affinity = Some(cpus.inner);
+        self
+    }
+
+    fn nice(mut self, nice: i32) -> Self {
+        self.as_inner_mut().nice = Some(nice);
+        self
+    }
+
+    fn sched_policy(mut self, policy: SchedPolicy) -> Self {
+        self.as_inner_mut().sched_policy = Some(match policy {
+            SchedPolicy::Other => InnerSchedPolicy::Other,
+            SchedPolicy::Batch => InnerSchedPolicy::Batch,
+            SchedPolicy::Idle => InnerSchedPolicy::Idle,
+            SchedPolicy::Fifo(priority) => InnerSchedPolicy::Fifo(priority),
+            SchedPolicy::RoundRobin(priority) => InnerSchedPolicy::RoundRobin(priority),
+        });
+        self
+    }
+
+    fn guard_size(mut self, size: usize) -> Self {
+        self.as_inner_mut().guard_size = Some(size);
+        self
+    }
+}
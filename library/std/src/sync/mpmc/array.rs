@@ -462,6 +462,36 @@ pub(crate) fn disconnect(&self) -> bool {
         }
     }
 
+    /// Returns `true` if a send operation can proceed without blocking.
+    pub(crate) fn can_send(&self) -> bool {
+        !self.is_full() || self.is_disconnected()
+    }
+
+    /// Returns `true` if a receive operation can proceed without blocking.
+    pub(crate) fn can_recv(&self) -> bool {
+        !self.is_empty() || self.is_disconnected()
+    }
+
+    /// Registers an operation waiting for the channel to have free space.
+    pub(crate) fn watch_send(&self, oper: Operation, cx: &Context) {
+        self.senders.watch(oper, cx);
+    }
+
+    /// Unregisters an operation waiting for the channel to have free space.
+    pub(crate) fn unwatch_send(&self, oper: Operation) {
+        self.senders.unwatch(oper);
+    }
+
+    /// Registers an operation waiting for the channel to have a message.
+    pub(crate) fn watch_recv(&self, oper: Operation, cx: &Context) {
+        self.receivers.watch(oper, cx);
+    }
+
+    /// Unregisters an operation waiting for the channel to have a message.
+    pub(crate) fn unwatch_recv(&self, oper: Operation) {
+        self.receivers.unwatch(oper);
+    }
+
     /// Returns `true` if the channel is disconnected.
     pub(crate) fn is_disconnected(&self) -> bool {
         self.tail.load(Ordering::SeqCst) & self.mark_bit != 0
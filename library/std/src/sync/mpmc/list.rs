@@ -581,6 +581,36 @@ fn discard_all_messages(&self) {
         self.head.index.store(head, Ordering::Release);
     }
 
+    /// Returns `true` if a send operation can proceed without blocking.
+    ///
+    /// Sending into an unbounded channel never blocks.
+    pub(crate) fn can_send(&self) -> bool {
+        true
+    }
+
+    /// Returns `true` if a receive operation can proceed without blocking.
+    pub(crate) fn can_recv(&self) -> bool {
+        !self.is_empty() || self.is_disconnected()
+    }
+
+    /// Registers an operation waiting for the channel to have free space.
+    ///
+    /// This is a no-op since the channel always has free space.
+    pub(crate) fn watch_send(&self, _oper: Operation, _cx: &Context) {}
+
+    /// Unregisters an operation waiting for the channel to have free space.
+    pub(crate) fn unwatch_send(&self, _oper: Operation) {}
+
+    /// Registers an operation waiting for the channel to have a message.
+    pub(crate) fn watch_recv(&self, oper: Operation, cx: &Context) {
+        self.receivers.watch(oper, cx);
+    }
+
+    /// Unregisters an operation waiting for the channel to have a message.
+    pub(crate) fn unwatch_recv(&self, oper: Operation) {
+        self.receivers.unwatch(oper);
+    }
+
     /// Returns `true` if the channel is disconnected.
     pub(crate) fn is_disconnected(&self) -> bool {
         self.tail.index.load(Ordering::SeqCst) & MARK_BIT != 0
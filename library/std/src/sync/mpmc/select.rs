@@ -1,3 +1,11 @@
+use super::context::Context;
+use super::error::{ReadyTimeoutError, TryReadyError};
+use super::utils;
+use super::{Receiver, Sender};
+
+use crate::fmt;
+use crate::time::{Duration, Instant};
+
 /// Temporary data that gets initialized during a blocking operation, and is consumed by
 /// `read` or `write`.
 ///
@@ -69,3 +77,229 @@ fn into(self) -> usize {
         }
     }
 }
+
+/// A channel operation that can take part in a [`Select`].
+pub(crate) trait SelectHandle {
+    /// Returns `true` if the operation can proceed without blocking.
+    fn is_ready(&self) -> bool;
+
+    /// Registers an operation to be notified when it may have become ready.
+    fn watch(&self, oper: Operation, cx: &Context);
+
+    /// Unregisters an operation previously registered with `watch`.
+    fn unwatch(&self, oper: Operation);
+}
+
+/// Waits until one of several channel operations is ready.
+///
+/// Operations are added with [`send`] and [`recv`], each of which returns an
+/// index identifying the operation. [`ready`] then blocks until one of the
+/// operations can proceed without blocking and returns its index. The caller
+/// is expected to perform that operation afterwards, for example with
+/// [`try_recv`].
+///
+/// An operation is also considered ready when its channel is disconnected,
+/// since it would then fail without blocking.
+///
+/// If several operations are ready at the same time, one of them is picked
+/// at random.
+///
+/// Note that readiness is only a hint: another thread using the same channel
+/// may claim the message or the free slot before the caller gets to it, in
+/// which case the non-blocking operation fails and the caller should select
+/// again. Operations on a zero-capacity channel are ready only while a thread
+/// is blocked on the other side of that channel.
+///
+/// [`send`]: Select::send
+/// [`recv`]: Select::recv
+/// [`ready`]: Select::ready
+/// [`try_recv`]: Receiver::try_recv
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpmc::{channel, Select};
+/// use std::thread;
+///
+/// let (tx1, rx1) = channel();
+/// let (tx2, rx2) = channel();
+///
+/// thread::spawn(move || tx1.send(1).unwrap());
+/// thread::spawn(move || tx2.send(2).unwrap());
+///
+/// let mut sel = Select::new();
+/// let oper1 = sel.recv(&rx1);
+/// let oper2 = sel.recv(&rx2);
+///
+/// let mut received = Vec::new();
+/// while received.len() < 2 {
+///     let index = sel.ready();
+///     let rx = if index == oper1 { &rx1 } else { &rx2 };
+///     if let Ok(msg) = rx.try_recv() {
+///         received.push(msg);
+///     }
+/// }
+///
+/// received.sort();
+/// assert_eq!(received, [1, 2]);
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "none")]
+pub struct Select<'a> {
+    /// The added operations, along with the index returned for each of them.
+    handles: Vec<(&'a dyn SelectHandle, usize)>,
+
+    /// The index assigned to the next added operation.
+    next_index: usize,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty list of channel operations.
+    #[must_use]
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn new() -> Select<'a> {
+        Select { handles: Vec::new(), next_index: 0 }
+    }
+
+    /// Adds a send operation and returns its index.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn send<T>(&mut self, s: &'a Sender<T>) -> usize {
+        self.add(s)
+    }
+
+    /// Adds a receive operation and returns its index.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        self.add(r)
+    }
+
+    fn add(&mut self, handle: &'a dyn SelectHandle) -> usize {
+        let index = self.next_index;
+        self.handles.push((handle, index));
+        self.next_index += 1;
+        index
+    }
+
+    /// Removes a previously added operation.
+    ///
+    /// The indices of the remaining operations do not change.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operation with the given index was added, or if it was
+    /// already removed.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn remove(&mut self, index: usize) {
+        let i = self
+            .handles
+            .iter()
+            .position(|&(_, idx)| idx == index)
+            .expect("no operation has this index");
+        self.handles.swap_remove(i);
+    }
+
+    /// Attempts to find a ready operation without blocking.
+    ///
+    /// Returns the index of a ready operation, or an error if none of the
+    /// operations is ready.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn try_ready(&mut self) -> Result<usize, TryReadyError> {
+        self.find_ready().ok_or(TryReadyError)
+    }
+
+    /// Blocks until one of the operations becomes ready and returns its index.
+    ///
+    /// If no operations were added, this call blocks forever.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn ready(&mut self) -> usize {
+        match self.run_ready(None) {
+            Some(index) => index,
+            None => unreachable!(),
+        }
+    }
+
+    /// Blocks for a limited time until one of the operations becomes ready
+    /// and returns its index.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn ready_timeout(&mut self, timeout: Duration) -> Result<usize, ReadyTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.ready_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => Ok(self.ready()),
+        }
+    }
+
+    /// Blocks until a given deadline for one of the operations to become ready
+    /// and returns its index.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn ready_deadline(&mut self, deadline: Instant) -> Result<usize, ReadyTimeoutError> {
+        self.run_ready(Some(deadline)).ok_or(ReadyTimeoutError)
+    }
+
+    /// Returns the index of a ready operation, starting the search at a random
+    /// position so that no operation is favored.
+    fn find_ready(&self) -> Option<usize> {
+        let len = self.handles.len();
+        if len == 0 {
+            return None;
+        }
+
+        let start = utils::random(len);
+        (0..len)
+            .map(|i| self.handles[(start + i) % len])
+            .find(|(handle, _)| handle.is_ready())
+            .map(|(_, index)| index)
+    }
+
+    fn run_ready(&mut self, deadline: Option<Instant>) -> Option<usize> {
+        loop {
+            if let Some(index) = self.find_ready() {
+                return Some(index);
+            }
+
+            if let Some(d) = deadline {
+                if Instant::now() >= d {
+                    return None;
+                }
+            }
+
+            let handles = &mut self.handles;
+            Context::with(|cx| {
+                // Each entry lives at a stable address while the thread is blocked, so it
+                // can identify the operation.
+                for entry in handles.iter_mut() {
+                    let oper = Operation::hook(entry);
+                    entry.0.watch(oper, cx);
+                }
+
+                // An operation might have become ready before it was watched, in which case
+                // nobody is going to wake us up.
+                if handles.iter().any(|(handle, _)| handle.is_ready()) {
+                    let _ = cx.try_select(Selected::Aborted);
+                }
+
+                cx.wait_until(deadline);
+
+                for entry in handles.iter_mut() {
+                    let oper = Operation::hook(entry);
+                    entry.0.unwatch(oper);
+                }
+            });
+        }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<'a> Default for Select<'a> {
+    fn default() -> Select<'a> {
+        Select::new()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Select { .. }")
+    }
+}
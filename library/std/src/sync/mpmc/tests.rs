@@ -0,0 +1,198 @@
+use super::*;
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let (tx, rx) = channel::<i32>();
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv().unwrap(), 1);
+
+    let (tx, rx) = sync_channel::<i32>(1);
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv().unwrap(), 1);
+}
+
+#[test]
+fn capacity() {
+    let (tx, rx) = channel::<i32>();
+    assert_eq!(tx.capacity(), None);
+    assert_eq!(rx.capacity(), None);
+
+    let (tx, rx) = sync_channel::<i32>(3);
+    assert_eq!(tx.capacity(), Some(3));
+    assert_eq!(rx.capacity(), Some(3));
+
+    let (tx, rx) = sync_channel::<i32>(0);
+    assert_eq!(tx.capacity(), Some(0));
+    assert_eq!(rx.capacity(), Some(0));
+}
+
+#[test]
+fn bounded_full() {
+    let (tx, rx) = sync_channel::<i32>(1);
+    tx.try_send(1).unwrap();
+    assert!(tx.is_full());
+    assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert!(rx.is_empty());
+}
+
+#[test]
+fn zero_capacity_rendezvous() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+
+    let t = thread::spawn(move || rx.recv().unwrap());
+    tx.send(2).unwrap();
+    assert_eq!(t.join().unwrap(), 2);
+}
+
+#[test]
+fn cloned_receivers_share_messages() {
+    for cap in [None, Some(0), Some(4)] {
+        let (tx, rx) = match cap {
+            None => channel::<usize>(),
+            Some(cap) => sync_channel::<usize>(cap),
+        };
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let rx = rx.clone();
+                thread::spawn(move || rx.iter().collect::<Vec<_>>())
+            })
+            .collect();
+        drop(rx);
+
+        for i in 0..1000 {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+
+        let mut received: Vec<_> =
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+        received.sort();
+        assert_eq!(received, (0..1000).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn disconnect_after_last_receiver() {
+    let (tx, rx) = channel::<i32>();
+    let rx2 = rx.clone();
+    drop(rx);
+    tx.send(1).unwrap();
+    drop(rx2);
+    assert_eq!(tx.send(2), Err(SendError(2)));
+}
+
+#[test]
+fn same_channel() {
+    let (_tx, rx) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    assert!(rx.same_channel(&rx.clone()));
+    assert!(!rx.same_channel(&rx2));
+}
+
+#[test]
+fn select_try_ready() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let _oper2 = sel.recv(&rx2);
+    assert_eq!(sel.try_ready(), Err(TryReadyError));
+
+    tx1.send(1).unwrap();
+    assert_eq!(sel.try_ready(), Ok(oper1));
+}
+
+#[test]
+fn select_send_ready() {
+    let (tx, rx) = sync_channel::<i32>(1);
+
+    let mut sel = Select::new();
+    let oper = sel.send(&tx);
+    assert_eq!(sel.try_ready(), Ok(oper));
+
+    tx.send(1).unwrap();
+    assert_eq!(sel.try_ready(), Err(TryReadyError));
+
+    rx.recv().unwrap();
+    assert_eq!(sel.try_ready(), Ok(oper));
+}
+
+#[test]
+fn select_ready_wakes_up() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (_tx2, rx2) = channel::<i32>();
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx1.send(1).unwrap();
+    });
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let _oper2 = sel.recv(&rx2);
+    assert_eq!(sel.ready(), oper1);
+    assert_eq!(rx1.try_recv(), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+fn select_zero_capacity() {
+    let (tx, rx) = sync_channel::<i32>(0);
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx.send(1).unwrap();
+    });
+
+    let mut sel = Select::new();
+    let oper = sel.recv(&rx);
+    assert_eq!(sel.ready(), oper);
+    assert_eq!(rx.recv(), Ok(1));
+    t.join().unwrap();
+}
+
+#[test]
+fn select_disconnected() {
+    let (tx, rx) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let oper = sel.recv(&rx);
+    drop(tx);
+    assert_eq!(sel.ready(), oper);
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx, rx) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+
+    let start = Instant::now();
+    assert_eq!(sel.ready_timeout(Duration::from_millis(50)), Err(ReadyTimeoutError));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn select_remove() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    tx1.send(1).unwrap();
+    tx2.send(2).unwrap();
+
+    sel.remove(oper1);
+    for _ in 0..10 {
+        assert_eq!(sel.try_ready(), Ok(oper2));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::cell::Cell;
+use crate::num::Wrapping;
 use crate::ops::{Deref, DerefMut};
 
 /// Pads and aligns a value to the length of a cache line.
@@ -142,3 +143,26 @@ pub fn is_completed(&self) -> bool {
         self.step.get() > YIELD_LIMIT
     }
 }
+
+/// Returns a pseudo-random number in the range `0..n`.
+///
+/// `n` must be non-zero.
+pub fn random(n: usize) -> usize {
+    thread_local! {
+        static RNG: Cell<Wrapping<u32>> = Cell::new(Wrapping(1_406_868_647));
+    }
+
+    RNG.try_with(|rng| {
+        // This is the 32-bit variant of Xorshift.
+        //
+        // Source: https://en.wikipedia.org/wiki/Xorshift
+        let mut x = rng.get();
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        rng.set(x);
+
+        x.0 as usize % n
+    })
+    .unwrap_or(0)
+}
@@ -295,6 +295,42 @@ pub(crate) fn disconnect(&self) -> bool {
         }
     }
 
+    /// Returns `true` if a send operation can proceed without blocking.
+    ///
+    /// This is the case when a receiver is blocked on the other side of the channel.
+    pub(crate) fn can_send(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.receivers.can_select() || inner.is_disconnected
+    }
+
+    /// Returns `true` if a receive operation can proceed without blocking.
+    ///
+    /// This is the case when a sender is blocked on the other side of the channel.
+    pub(crate) fn can_recv(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.senders.can_select() || inner.is_disconnected
+    }
+
+    /// Registers an operation waiting for a receiver to appear.
+    pub(crate) fn watch_send(&self, oper: Operation, cx: &Context) {
+        self.inner.lock().unwrap().senders.watch(oper, cx);
+    }
+
+    /// Unregisters an operation waiting for a receiver to appear.
+    pub(crate) fn unwatch_send(&self, oper: Operation) {
+        self.inner.lock().unwrap().senders.unwatch(oper);
+    }
+
+    /// Registers an operation waiting for a sender to appear.
+    pub(crate) fn watch_recv(&self, oper: Operation, cx: &Context) {
+        self.inner.lock().unwrap().receivers.watch(oper, cx);
+    }
+
+    /// Unregisters an operation waiting for a sender to appear.
+    pub(crate) fn unwatch_recv(&self, oper: Operation) {
+        self.inner.lock().unwrap().receivers.unwatch(oper);
+    }
+
     /// Returns the current number of messages inside the channel.
     pub(crate) fn len(&self) -> usize {
         0
@@ -15,6 +15,7 @@
 use crate::sys::unsupported;
 
 pub use crate::sys_common::fs::{copy, try_exists};
+pub use crate::sys_common::fs::UnsupportedDir as Dir;
 //pub use crate::sys_common::fs::remove_dir_all;
 
 #[derive(Debug)]
@@ -13,6 +13,7 @@
 };
 
 pub use crate::sys_common::fs::try_exists;
+pub use crate::sys_common::fs::UnsupportedDir as Dir;
 
 /// A file descriptor.
 #[derive(Clone, Copy)]
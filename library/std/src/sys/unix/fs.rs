@@ -237,7 +237,7 @@ pub struct FileAttr {
 
 // all DirEntry's will have a reference to this struct
 struct InnerReadDir {
-    dirp: Dir,
+    dirp: DirStream,
     root: PathBuf,
 }
 
@@ -254,10 +254,10 @@ pub struct ReadDir {
     end_of_stream: bool,
 }
 
-struct Dir(*mut libc::DIR);
+struct DirStream(*mut libc::DIR);
 
-unsafe impl Send for Dir {}
-unsafe impl Sync for Dir {}
+unsafe impl Send for DirStream {}
+unsafe impl Sync for DirStream {}
 
 #[cfg(any(
     target_os = "android",
@@ -702,7 +702,7 @@ fn next(&mut self) -> Option<io::Result<DirEntry>> {
     }
 }
 
-impl Drop for Dir {
+impl Drop for DirStream {
     fn drop(&mut self) {
         let r = unsafe { libc::closedir(self.0) };
         assert!(
@@ -751,6 +751,20 @@ pub fn metadata(&self) -> io::Result<FileAttr> {
         miri
     ))]
     pub fn metadata(&self) -> io::Result<FileAttr> {
+        // Entries read through a `Dir` handle have no root path to resolve them against, so go
+        // through the directory stream instead.
+        #[cfg(not(any(
+            target_os = "redox",
+            target_os = "espidf",
+            target_os = "horizon",
+            target_os = "vxworks",
+            target_os = "l4re",
+            miri
+        )))]
+        if self.dir.root.as_os_str().is_empty() {
+            let fd = cvt(unsafe { libc::dirfd(self.dir.dirp.0) })?;
+            return dir_impl::stat_at(fd, self.name_cstr(), libc::AT_SYMLINK_NOFOLLOW);
+        }
         lstat(&self.path())
     }
 
@@ -1346,7 +1360,7 @@ pub fn readdir(path: &Path) -> io::Result<ReadDir> {
         Err(Error::last_os_error())
     } else {
         let root = path.to_path_buf();
-        let inner = InnerReadDir { dirp: Dir(ptr), root };
+        let inner = InnerReadDir { dirp: DirStream(ptr), root };
         Ok(ReadDir {
             inner: Arc::new(inner),
             #[cfg(not(any(
@@ -1577,7 +1591,7 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
 
     use super::kernel_copy::{copy_regular_files, CopyResult};
 
-    match copy_regular_files(reader.as_raw_fd(), writer.as_raw_fd(), max_len) {
+    match copy_regular_files(reader.as_raw_fd(), None, writer.as_raw_fd(), max_len) {
         CopyResult::Ended(bytes) => Ok(bytes),
         CopyResult::Error(e, _) => Err(e),
         CopyResult::Fallback(written) => match io::copy::generic_copy(&mut reader, &mut writer) {
@@ -1722,8 +1736,110 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     run_path_with_cstr(dir, |dir| cvt(unsafe { libc::chroot(dir.as_ptr()) }).map(|_| ()))
 }
 
+pub use dir_impl::Dir;
 pub use remove_dir_impl::remove_dir_all;
 
+// Weakly linked `*at` functions, which are only available since macOS 10.10
+#[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
+mod macos_weak {
+    use crate::sys::weak::weak;
+    use libc::{c_char, c_int, mode_t, stat, DIR};
+
+    fn get_openat_fn() -> Option<unsafe extern "C" fn(c_int, *const c_char, c_int) -> c_int> {
+        weak!(fn openat(c_int, *const c_char, c_int) -> c_int);
+        openat.get()
+    }
+
+    pub fn has_openat() -> bool {
+        get_openat_fn().is_some()
+    }
+
+    pub unsafe fn openat(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int {
+        get_openat_fn().map(|openat| openat(dirfd, pathname, flags)).unwrap_or_else(|| {
+            crate::sys::unix::os::set_errno(libc::ENOSYS);
+            -1
+        })
+    }
+
+    pub unsafe fn openat_mode(
+        dirfd: c_int,
+        pathname: *const c_char,
+        flags: c_int,
+        mode: c_int,
+    ) -> c_int {
+        // `openat` is variadic; the mode is passed like any other integer argument.
+        weak!(fn openat(c_int, *const c_char, c_int, c_int) -> c_int);
+        openat.get().map(|openat| openat(dirfd, pathname, flags, mode)).unwrap_or_else(|| {
+            crate::sys::unix::os::set_errno(libc::ENOSYS);
+            -1
+        })
+    }
+
+    pub unsafe fn fdopendir(fd: c_int) -> *mut DIR {
+        #[cfg(all(target_os = "macos", target_arch = "x86"))]
+        weak!(fn fdopendir(c_int) -> *mut DIR, "fdopendir$INODE64$UNIX2003");
+        #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+        weak!(fn fdopendir(c_int) -> *mut DIR, "fdopendir$INODE64");
+        fdopendir.get().map(|fdopendir| fdopendir(fd)).unwrap_or_else(|| {
+            crate::sys::unix::os::set_errno(libc::ENOSYS);
+            crate::ptr::null_mut()
+        })
+    }
+
+    pub unsafe fn unlinkat(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int {
+        weak!(fn unlinkat(c_int, *const c_char, c_int) -> c_int);
+        unlinkat.get().map(|unlinkat| unlinkat(dirfd, pathname, flags)).unwrap_or_else(|| {
+            crate::sys::unix::os::set_errno(libc::ENOSYS);
+            -1
+        })
+    }
+
+    pub unsafe fn mkdirat(dirfd: c_int, pathname: *const c_char, mode: mode_t) -> c_int {
+        weak!(fn mkdirat(c_int, *const c_char, mode_t) -> c_int);
+        mkdirat.get().map(|mkdirat| mkdirat(dirfd, pathname, mode)).unwrap_or_else(|| {
+            crate::sys::unix::os::set_errno(libc::ENOSYS);
+            -1
+        })
+    }
+
+    pub unsafe fn renameat(
+        olddirfd: c_int,
+        oldpath: *const c_char,
+        newdirfd: c_int,
+        newpath: *const c_char,
+    ) -> c_int {
+        weak!(fn renameat(c_int, *const c_char, c_int, *const c_char) -> c_int);
+        renameat
+            .get()
+            .map(|renameat| renameat(olddirfd, oldpath, newdirfd, newpath))
+            .unwrap_or_else(|| {
+                crate::sys::unix::os::set_errno(libc::ENOSYS);
+                -1
+            })
+    }
+
+    pub unsafe fn symlinkat(target: *const c_char, dirfd: c_int, linkpath: *const c_char) -> c_int {
+        weak!(fn symlinkat(*const c_char, c_int, *const c_char) -> c_int);
+        symlinkat.get().map(|symlinkat| symlinkat(target, dirfd, linkpath)).unwrap_or_else(|| {
+            crate::sys::unix::os::set_errno(libc::ENOSYS);
+            -1
+        })
+    }
+
+    pub unsafe fn fstatat(
+        dirfd: c_int,
+        pathname: *const c_char,
+        buf: *mut stat,
+        flags: c_int,
+    ) -> c_int {
+        weak!(fn fstatat(c_int, *const c_char, *mut stat, c_int) -> c_int, "fstatat$INODE64");
+        fstatat.get().map(|fstatat| fstatat(dirfd, pathname, buf, flags)).unwrap_or_else(|| {
+            crate::sys::unix::os::set_errno(libc::ENOSYS);
+            -1
+        })
+    }
+}
+
 // Fallback for REDOX, ESP-ID, Horizon, and Miri
 #[cfg(any(target_os = "redox", target_os = "espidf", target_os = "horizon", miri))]
 mod remove_dir_impl {
@@ -1733,7 +1849,7 @@ mod remove_dir_impl {
 // Modern implementation using openat(), unlinkat() and fdopendir()
 #[cfg(not(any(target_os = "redox", target_os = "espidf", target_os = "horizon", miri)))]
 mod remove_dir_impl {
-    use super::{lstat, Dir, DirEntry, InnerReadDir, ReadDir};
+    use super::{lstat, DirEntry, DirStream, InnerReadDir, ReadDir};
     use crate::ffi::CStr;
     use crate::io;
     use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
@@ -1746,48 +1862,7 @@ mod remove_dir_impl {
     #[cfg(not(all(target_os = "macos", not(target_arch = "aarch64")),))]
     use libc::{fdopendir, openat, unlinkat};
     #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
-    use macos_weak::{fdopendir, openat, unlinkat};
-
-    #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
-    mod macos_weak {
-        use crate::sys::weak::weak;
-        use libc::{c_char, c_int, DIR};
-
-        fn get_openat_fn() -> Option<unsafe extern "C" fn(c_int, *const c_char, c_int) -> c_int> {
-            weak!(fn openat(c_int, *const c_char, c_int) -> c_int);
-            openat.get()
-        }
-
-        pub fn has_openat() -> bool {
-            get_openat_fn().is_some()
-        }
-
-        pub unsafe fn openat(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int {
-            get_openat_fn().map(|openat| openat(dirfd, pathname, flags)).unwrap_or_else(|| {
-                crate::sys::unix::os::set_errno(libc::ENOSYS);
-                -1
-            })
-        }
-
-        pub unsafe fn fdopendir(fd: c_int) -> *mut DIR {
-            #[cfg(all(target_os = "macos", target_arch = "x86"))]
-            weak!(fn fdopendir(c_int) -> *mut DIR, "fdopendir$INODE64$UNIX2003");
-            #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
-            weak!(fn fdopendir(c_int) -> *mut DIR, "fdopendir$INODE64");
-            fdopendir.get().map(|fdopendir| fdopendir(fd)).unwrap_or_else(|| {
-                crate::sys::unix::os::set_errno(libc::ENOSYS);
-                crate::ptr::null_mut()
-            })
-        }
-
-        pub unsafe fn unlinkat(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int {
-            weak!(fn unlinkat(c_int, *const c_char, c_int) -> c_int);
-            unlinkat.get().map(|unlinkat| unlinkat(dirfd, pathname, flags)).unwrap_or_else(|| {
-                crate::sys::unix::os::set_errno(libc::ENOSYS);
-                -1
-            })
-        }
-    }
+    use super::macos_weak::{fdopendir, openat, unlinkat};
 
     pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result<OwnedFd> {
         let fd = cvt_r(|| unsafe {
@@ -1800,12 +1875,12 @@ pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result
         Ok(unsafe { OwnedFd::from_raw_fd(fd) })
     }
 
-    fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
+    pub(super) fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
         let ptr = unsafe { fdopendir(dir_fd.as_raw_fd()) };
         if ptr.is_null() {
             return Err(io::Error::last_os_error());
         }
-        let dirp = Dir(ptr);
+        let dirp = DirStream(ptr);
         // file descriptor is automatically closed by libc::closedir() now, so give up ownership
         let new_parent_fd = dir_fd.into_raw_fd();
         // a valid root is not needed because we do not call any functions involving the full path
@@ -1918,7 +1993,7 @@ pub fn remove_dir_all(p: &Path) -> io::Result<()> {
 
     #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
     pub fn remove_dir_all(p: &Path) -> io::Result<()> {
-        if macos_weak::has_openat() {
+        if super::macos_weak::has_openat() {
             // openat() is available with macOS 10.10+, just like unlinkat() and fdopendir()
             remove_dir_all_modern(p)
         } else {
@@ -1927,3 +2002,196 @@ pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         }
     }
 }
+
+// Fallback for platforms lacking the `*at` functions
+#[cfg(any(
+    target_os = "redox",
+    target_os = "espidf",
+    target_os = "horizon",
+    target_os = "vxworks",
+    target_os = "l4re",
+    miri
+))]
+mod dir_impl {
+    pub use crate::sys_common::fs::UnsupportedDir as Dir;
+}
+
+// Directory handles backed by a file descriptor and the `*at` functions
+#[cfg(not(any(
+    target_os = "redox",
+    target_os = "espidf",
+    target_os = "horizon",
+    target_os = "vxworks",
+    target_os = "l4re",
+    miri
+)))]
+mod dir_impl {
+    use super::remove_dir_impl::fdreaddir;
+    use super::{stat64, File, FileAttr, OpenOptions, ReadDir};
+    use crate::ffi::CStr;
+    use crate::fmt;
+    use crate::io;
+    use crate::mem;
+    use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd};
+    use crate::path::Path;
+    use crate::sys::common::small_c_string::run_path_with_cstr;
+    use crate::sys::fd::FileDesc;
+    use crate::sys::{cvt, cvt_r};
+    use crate::sys_common::IntoInner;
+    use libc::c_int;
+
+    #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
+    use super::macos_weak::{
+        fstatat as fstatat64, mkdirat, openat_mode as openat, renameat, symlinkat, unlinkat,
+    };
+    #[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "android"))]
+    use super::fstatat64;
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "emscripten",
+        target_os = "android",
+        all(target_os = "macos", not(target_arch = "aarch64"))
+    )))]
+    use libc::fstatat as fstatat64;
+    #[cfg(not(all(target_os = "macos", not(target_arch = "aarch64"))))]
+    use libc::{mkdirat, openat, renameat, symlinkat, unlinkat};
+
+    pub struct Dir(FileDesc);
+
+    impl Dir {
+        pub fn open(path: &Path) -> io::Result<Dir> {
+            run_path_with_cstr(path, |path| Dir::open_c(libc::AT_FDCWD, path, 0))
+        }
+
+        pub fn open_dir(&self, path: &Path) -> io::Result<Dir> {
+            run_path_with_cstr(path, |path| Dir::open_c(self.0.as_raw_fd(), path, 0))
+        }
+
+        pub fn open_dir_nofollow(&self, path: &Path) -> io::Result<Dir> {
+            run_path_with_cstr(path, |path| Dir::open_c(self.0.as_raw_fd(), path, libc::O_NOFOLLOW))
+        }
+
+        fn open_c(dirfd: c_int, path: &CStr, flags: c_int) -> io::Result<Dir> {
+            let fd = cvt_r(|| unsafe {
+                openat(
+                    dirfd,
+                    path.as_ptr(),
+                    libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY | flags,
+                    0,
+                )
+            })?;
+            Ok(Dir(unsafe { FileDesc::from_raw_fd(fd) }))
+        }
+
+        pub fn open_file(&self, path: &Path, opts: &OpenOptions) -> io::Result<File> {
+            #[cfg(target_os = "linux")]
+            const O_LARGEFILE: c_int = libc::O_LARGEFILE;
+            #[cfg(not(target_os = "linux"))]
+            const O_LARGEFILE: c_int = 0;
+
+            // Same as `File::open_c`, except that `openat` has no 64-bit variant to request large
+            // file support on 32-bit Linux for us.
+            let flags = libc::O_CLOEXEC
+                | O_LARGEFILE
+                | opts.get_access_mode()?
+                | opts.get_creation_mode()?
+                | (opts.custom_flags as c_int & !libc::O_ACCMODE);
+            run_path_with_cstr(path, |path| {
+                let fd = cvt_r(|| unsafe {
+                    openat(self.0.as_raw_fd(), path.as_ptr(), flags, opts.mode as c_int)
+                })?;
+                Ok(File(unsafe { FileDesc::from_raw_fd(fd) }))
+            })
+        }
+
+        pub fn create_dir(&self, path: &Path) -> io::Result<()> {
+            run_path_with_cstr(path, |path| {
+                cvt(unsafe { mkdirat(self.0.as_raw_fd(), path.as_ptr(), 0o777) }).map(drop)
+            })
+        }
+
+        pub fn remove_file(&self, path: &Path) -> io::Result<()> {
+            run_path_with_cstr(path, |path| {
+                cvt(unsafe { unlinkat(self.0.as_raw_fd(), path.as_ptr(), 0) }).map(drop)
+            })
+        }
+
+        pub fn remove_dir(&self, path: &Path) -> io::Result<()> {
+            run_path_with_cstr(path, |path| {
+                cvt(unsafe { unlinkat(self.0.as_raw_fd(), path.as_ptr(), libc::AT_REMOVEDIR) })
+                    .map(drop)
+            })
+        }
+
+        pub fn rename(&self, from: &Path, to_dir: &Dir, to: &Path) -> io::Result<()> {
+            run_path_with_cstr(from, |from| {
+                run_path_with_cstr(to, |to| {
+                    cvt(unsafe {
+                        renameat(
+                            self.0.as_raw_fd(),
+                            from.as_ptr(),
+                            to_dir.0.as_raw_fd(),
+                            to.as_ptr(),
+                        )
+                    })
+                    .map(drop)
+                })
+            })
+        }
+
+        pub fn metadata(&self, path: &Path) -> io::Result<FileAttr> {
+            run_path_with_cstr(path, |path| stat_at(self.0.as_raw_fd(), path, 0))
+        }
+
+        pub fn symlink_metadata(&self, path: &Path) -> io::Result<FileAttr> {
+            run_path_with_cstr(path, |path| {
+                stat_at(self.0.as_raw_fd(), path, libc::AT_SYMLINK_NOFOLLOW)
+            })
+        }
+
+        pub fn read_dir(&self) -> io::Result<ReadDir> {
+            // `fdopendir` takes ownership of the descriptor and reads from its current offset, so
+            // give it a fresh descriptor rather than a duplicate sharing our offset.
+            let dot = unsafe { CStr::from_bytes_with_nul_unchecked(b".\0") };
+            let dir = Dir::open_c(self.0.as_raw_fd(), dot, 0)?;
+            let (read_dir, _) = fdreaddir(dir.0.into_inner())?;
+            Ok(read_dir)
+        }
+
+        pub fn symlink(&self, original: &Path, link: &Path) -> io::Result<()> {
+            run_path_with_cstr(original, |original| {
+                run_path_with_cstr(link, |link| {
+                    cvt(unsafe { symlinkat(original.as_ptr(), self.0.as_raw_fd(), link.as_ptr()) })
+                        .map(drop)
+                })
+            })
+        }
+
+        pub fn as_fd(&self) -> BorrowedFd<'_> {
+            self.0.as_fd()
+        }
+    }
+
+    impl fmt::Debug for Dir {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Dir").field("fd", &self.0.as_raw_fd()).finish()
+        }
+    }
+
+    pub(super) fn stat_at(dirfd: c_int, path: &CStr, flags: c_int) -> io::Result<FileAttr> {
+        cfg_has_statx! {
+            if let Some(ret) = unsafe { super::try_statx(
+                dirfd,
+                path.as_ptr(),
+                flags | libc::AT_STATX_SYNC_AS_STAT,
+                libc::STATX_ALL,
+            ) } {
+                return ret;
+            }
+        }
+
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        cvt(unsafe { fstatat64(dirfd, path.as_ptr(), &mut stat, flags) })?;
+        Ok(FileAttr::from_stat64(stat))
+    }
+}
@@ -48,11 +48,15 @@
 use crate::fs::{File, Metadata};
 use crate::io::copy::generic_copy;
 use crate::io::{
-    BufRead, BufReader, BufWriter, Error, Read, Result, StderrLock, StdinLock, StdoutLock, Take,
-    Write,
+    self, BufRead, BufReader, BufWriter, Error, Read, Result, StderrLock, StdinLock, StdoutLock,
+    Take, Write,
 };
 use crate::mem::ManuallyDrop;
 use crate::net::TcpStream;
+#[cfg(target_os = "linux")]
+use crate::os::linux::io::CopyMethod;
+#[cfg(target_os = "linux")]
+use crate::os::unix::fs::FileExt;
 use crate::os::unix::fs::FileTypeExt;
 use crate::os::unix::io::{AsRawFd, FromRawFd, RawFd};
 use crate::os::unix::net::UnixStream;
@@ -61,6 +65,8 @@
 use crate::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use crate::sys::cvt;
 use crate::sys::weak::syscall;
+#[cfg(target_os = "linux")]
+use crate::sys_common::io::DEFAULT_BUF_SIZE;
 use libc::{EBADF, EINVAL, ENOSYS, EOPNOTSUPP, EOVERFLOW, EPERM, EXDEV};
 
 #[cfg(test)]
@@ -167,7 +173,7 @@ fn copy(self) -> Result<u64> {
             let max_write = reader.min_limit();
 
             if input_meta.copy_file_range_candidate() && output_meta.copy_file_range_candidate() {
-                let result = copy_regular_files(readfd, writefd, max_write);
+                let result = copy_regular_files(readfd, None, writefd, max_write);
                 result.update_take(reader);
 
                 match result {
@@ -183,7 +189,8 @@ fn copy(self) -> Result<u64> {
             // If current file offsets + write sizes overflow it may also fail, we do not try to fix that and instead
             // fall back to the generic copy loop.
             if input_meta.potential_sendfile_source() {
-                let result = sendfile_splice(SpliceMode::Sendfile, readfd, writefd, max_write);
+                let result =
+                    sendfile_splice(SpliceMode::Sendfile, readfd, None, writefd, max_write);
                 result.update_take(reader);
 
                 match result {
@@ -194,7 +201,7 @@ fn copy(self) -> Result<u64> {
             }
 
             if input_meta.maybe_fifo() || output_meta.maybe_fifo() {
-                let result = sendfile_splice(SpliceMode::Splice, readfd, writefd, max_write);
+                let result = sendfile_splice(SpliceMode::Splice, readfd, None, writefd, max_write);
                 result.update_take(reader);
 
                 match result {
@@ -502,10 +509,18 @@ fn update_take(&self, reader: &mut impl CopyRead) {
 /// Linux-specific implementation that will attempt to use copy_file_range for copy offloading.
 /// As the name says, it only works on regular files.
 ///
+/// If `offset` is given the data is read starting at that offset, which is advanced
+/// past the copied bytes, instead of the reader's file position.
+///
 /// Callers must handle fallback to a generic copy loop.
 /// `Fallback` may indicate non-zero number of bytes already written
 /// if one of the files' cursor +`max_len` would exceed u64::MAX (`EOVERFLOW`).
-pub(super) fn copy_regular_files(reader: RawFd, writer: RawFd, max_len: u64) -> CopyResult {
+pub(super) fn copy_regular_files(
+    reader: RawFd,
+    mut offset: Option<&mut u64>,
+    writer: RawFd,
+    max_len: u64,
+) -> CopyResult {
     use crate::cmp;
 
     const NOT_PROBED: u8 = 0;
@@ -554,10 +569,18 @@ fn copy_file_range(
         // this allows us to copy large chunks without hitting EOVERFLOW,
         // unless someone sets a file offset close to u64::MAX - 1GB, in which case a fallback would be required
         let bytes_to_copy = cmp::min(bytes_to_copy as usize, 0x4000_0000usize);
+        let mut off_in = offset.as_deref().map(|&off| off as libc::loff_t);
         let copy_result = unsafe {
-            // We actually don't have to adjust the offsets,
-            // because copy_file_range adjusts the file offset automatically
-            cvt(copy_file_range(reader, ptr::null_mut(), writer, ptr::null_mut(), bytes_to_copy, 0))
+            // We actually don't have to adjust the file positions,
+            // because copy_file_range adjusts them automatically
+            cvt(copy_file_range(
+                reader,
+                off_in.as_mut().map_or(ptr::null_mut(), |off| off as *mut _),
+                writer,
+                ptr::null_mut(),
+                bytes_to_copy,
+                0,
+            ))
         };
 
         match copy_result {
@@ -570,7 +593,12 @@ fn copy_file_range(
                 return CopyResult::Fallback(0);
             }
             Ok(0) => return CopyResult::Ended(written), // reached EOF
-            Ok(ret) => written += ret as u64,
+            Ok(ret) => {
+                written += ret as u64;
+                if let Some(off) = offset.as_deref_mut() {
+                    *off += ret as u64;
+                }
+            }
             Err(err) => {
                 return match err.raw_os_error() {
                     // when file offset + max_length > u64::MAX
@@ -608,8 +636,16 @@ enum SpliceMode {
 }
 
 /// performs splice or sendfile between file descriptors
+/// If `offset` is given the data is read starting at that offset instead of the reader's file
+/// position and the offset is advanced past the copied bytes.
 /// Does _not_ fall back to a generic copy loop.
-fn sendfile_splice(mode: SpliceMode, reader: RawFd, writer: RawFd, len: u64) -> CopyResult {
+fn sendfile_splice(
+    mode: SpliceMode,
+    reader: RawFd,
+    mut offset: Option<&mut u64>,
+    writer: RawFd,
+    len: u64,
+) -> CopyResult {
     static HAS_SENDFILE: AtomicBool = AtomicBool::new(true);
     static HAS_SPLICE: AtomicBool = AtomicBool::new(true);
 
@@ -647,16 +683,35 @@ fn splice(
 
         let result = match mode {
             SpliceMode::Sendfile => {
-                cvt(unsafe { libc::sendfile(writer, reader, ptr::null_mut(), chunk_size) })
+                #[cfg(target_os = "android")]
+                use libc::{off_t, sendfile};
+                #[cfg(target_os = "linux")]
+                use libc::{off64_t as off_t, sendfile64 as sendfile};
+
+                let off = offset.as_deref().map(|&off| off_t::try_from(off));
+                let mut off = match off.transpose() {
+                    Ok(off) => off,
+                    // 32-bit Android only has a sendfile with 32-bit offsets
+                    Err(_) => return CopyResult::Fallback(written),
+                };
+                let off = off.as_mut().map_or(ptr::null_mut(), |off| off as *mut _);
+                cvt(unsafe { sendfile(writer, reader, off, chunk_size) })
+            }
+            SpliceMode::Splice => {
+                let mut off = offset.as_deref().map(|&off| off as i64);
+                let off = off.as_mut().map_or(ptr::null_mut(), |off| off as *mut _);
+                cvt(unsafe { splice(reader, off, writer, ptr::null_mut(), chunk_size, 0) })
             }
-            SpliceMode::Splice => cvt(unsafe {
-                splice(reader, ptr::null_mut(), writer, ptr::null_mut(), chunk_size, 0)
-            }),
         };
 
         match result {
             Ok(0) => break, // EOF
-            Ok(ret) => written += ret as u64,
+            Ok(ret) => {
+                written += ret as u64;
+                if let Some(off) = offset.as_deref_mut() {
+                    *off += ret as u64;
+                }
+            }
             Err(err) => {
                 return match err.raw_os_error() {
                     Some(ENOSYS | EPERM) => {
@@ -684,3 +739,78 @@ fn splice(
     }
     CopyResult::Ended(written)
 }
+
+/// Copies up to `len` bytes from `reader` to `writer`, reading at `offset` instead of the reader's
+/// file position if given.
+///
+/// Unlike `copy_spec` this works on plain file descriptors and reports which of the syscalls
+/// ended up completing the copy. It probes them in the same order, but based on the actual file
+/// types instead of hints since it always has to obtain the metadata anyway.
+#[cfg(target_os = "linux")]
+pub(crate) fn copy_range(
+    reader: RawFd,
+    mut offset: Option<u64>,
+    writer: RawFd,
+    len: u64,
+) -> Result<(u64, CopyMethod)> {
+    if offset.map_or(false, |off| off > i64::MAX as u64) {
+        return Err(io::const_io_error!(
+            io::ErrorKind::InvalidInput,
+            "offset is too large to be passed to the kernel",
+        ));
+    }
+
+    let input_meta = fd_to_meta(&reader);
+    let output_meta = fd_to_meta(&writer);
+    let output_is_file = matches!(&output_meta, FdMeta::Metadata(meta) if meta.is_file());
+    let mut written = 0u64;
+
+    if input_meta.copy_file_range_candidate() && output_is_file {
+        match copy_regular_files(reader, offset.as_mut(), writer, len) {
+            CopyResult::Ended(bytes) => return Ok((written + bytes, CopyMethod::CopyFileRange)),
+            CopyResult::Error(e, _) => return Err(e),
+            CopyResult::Fallback(bytes) => written += bytes,
+        }
+    }
+
+    if input_meta.potential_sendfile_source() {
+        match sendfile_splice(SpliceMode::Sendfile, reader, offset.as_mut(), writer, len - written)
+        {
+            CopyResult::Ended(bytes) => return Ok((written + bytes, CopyMethod::Sendfile)),
+            CopyResult::Error(e, _) => return Err(e),
+            CopyResult::Fallback(bytes) => written += bytes,
+        }
+    }
+
+    if input_meta.maybe_fifo() || output_meta.maybe_fifo() {
+        match sendfile_splice(SpliceMode::Splice, reader, offset.as_mut(), writer, len - written) {
+            CopyResult::Ended(bytes) => return Ok((written + bytes, CopyMethod::Splice)),
+            CopyResult::Error(e, _) => return Err(e),
+            CopyResult::Fallback(bytes) => written += bytes,
+        }
+    }
+
+    // fallback if none of the more specialized syscalls wants to work with these file descriptors
+    let reader: ManuallyDrop<File> = ManuallyDrop::new(unsafe { File::from_raw_fd(reader) });
+    let writer: ManuallyDrop<File> = ManuallyDrop::new(unsafe { File::from_raw_fd(writer) });
+    let mut buf = [0u8; DEFAULT_BUF_SIZE];
+    while written < len {
+        let to_read = min(len - written, buf.len() as u64) as usize;
+        let read = match offset {
+            Some(off) => reader.read_at(&mut buf[..to_read], off),
+            None => (&*reader).read(&mut buf[..to_read]),
+        };
+        let bytes = match read {
+            Ok(0) => break, // EOF
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        (&*writer).write_all(&buf[..bytes])?;
+        written += bytes as u64;
+        if let Some(off) = offset.as_mut() {
+            *off += bytes as u64;
+        }
+    }
+    Ok((written, CopyMethod::ReadWrite))
+}
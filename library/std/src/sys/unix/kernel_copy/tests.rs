@@ -83,6 +83,48 @@ fn copies_append_mode_sink() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn copy_range_at_offset() -> Result<()> {
+    use crate::os::linux::io::{copy_range, CopyMethod};
+    use crate::process::{ChildStdin, ChildStdout};
+    use crate::sys_common::FromInner;
+
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copy_range.source");
+    let sink_path = tmp_path.join("copy_range.sink");
+    let mut source =
+        OpenOptions::new().create(true).truncate(true).write(true).read(true).open(&source_path)?;
+    source.write_all(b"0123456789")?;
+    source.seek(SeekFrom::Start(1))?;
+    let mut sink = OpenOptions::new().create(true).truncate(true).write(true).open(&sink_path)?;
+
+    // reading at an offset leaves the file position alone
+    let (copied, _) = copy_range(&source, Some(4), &sink, 3)?;
+    assert_eq!(copied, 3);
+    assert_eq!(source.stream_position()?, 1);
+
+    // reading from the file position stops at EOF
+    let (copied, _) = copy_range(&source, None, &sink, u64::MAX)?;
+    assert_eq!(copied, 9);
+    assert_eq!(source.stream_position()?, 10);
+    sink.flush()?;
+    assert_eq!(crate::fs::read(&sink_path)?, b"456123456789");
+
+    let (read_end, write_end) = crate::sys::pipe::anon_pipe()?;
+    let mut read_end = ChildStdout::from_inner(read_end);
+    let write_end = ChildStdin::from_inner(write_end);
+    let (copied, method) = copy_range(&source, Some(2), &write_end, 5)?;
+    assert_eq!(copied, 5);
+    assert_ne!(method, CopyMethod::CopyFileRange);
+    drop(write_end);
+    let mut buf = Vec::new();
+    read_end.read_to_end(&mut buf)?;
+    assert_eq!(buf, b"23456");
+
+    Ok(())
+}
+
 #[bench]
 fn bench_file_to_file_copy(b: &mut test::Bencher) {
     const BYTES: usize = 128 * 1024;
@@ -233,6 +275,7 @@ fn bench_socket_pipe_socket_copy(b: &mut test::Bencher) {
     let probe = super::sendfile_splice(
         super::SpliceMode::Splice,
         local_end.as_raw_fd(),
+        None,
         write_end.as_raw_fd(),
         1,
     );
@@ -253,6 +296,7 @@ fn bench_socket_pipe_socket_copy(b: &mut test::Bencher) {
             super::sendfile_splice(
                 super::SpliceMode::Splice,
                 local_source.as_raw_fd(),
+                None,
                 write_end.as_raw_fd(),
                 u64::MAX,
             );
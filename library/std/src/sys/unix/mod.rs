@@ -32,6 +32,8 @@
 pub mod os;
 pub mod os_str;
 pub mod path;
+#[cfg(target_os = "linux")]
+pub mod pidfd;
 pub mod pipe;
 pub mod process;
 pub mod rand;
@@ -0,0 +1,158 @@
+//! Process handles backed by Linux pidfds.
+//!
+//! Unlike a PID, a pidfd always refers to the same process, so signals sent and waits performed
+//! through it can't hit an unrelated process that happened to reuse the PID.
+
+use crate::cmp;
+use crate::io;
+use crate::mem;
+use crate::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+use crate::ptr;
+use crate::sys::fd::FileDesc;
+use crate::sys::process::ExitStatus;
+use crate::sys::{cvt, cvt_r};
+use crate::sys_common::{FromInner, IntoInner};
+use crate::time::{Duration, Instant};
+
+use libc::c_int;
+
+// `libc` only defines this for the gnu and musl targets. New system calls share their number
+// across architectures, apart from the per-ABI offsets on MIPS.
+#[cfg(not(any(target_env = "gnu", target_env = "musl")))]
+#[allow(non_upper_case_globals)]
+const SYS_pidfd_send_signal: libc::c_long =
+    if cfg!(any(target_arch = "mips", target_arch = "mips32r6")) { 4424 } else { 424 };
+#[cfg(any(target_env = "gnu", target_env = "musl"))]
+use libc::SYS_pidfd_send_signal;
+
+// The start of a `siginfo_t` filled in for `SIGCHLD`. `libc` only provides the `si_pid` and
+// `si_status` accessors for some Linux targets.
+#[repr(C)]
+struct SigchldInfo {
+    // `si_signo`, `si_errno` and `si_code`, whose order differs between architectures.
+    _header: [c_int; 3],
+    // The union of the signal-specific fields is pointer-aligned.
+    #[cfg(target_pointer_width = "64")]
+    _pad: c_int,
+    pid: libc::pid_t,
+    _uid: libc::uid_t,
+    status: c_int,
+}
+
+#[derive(Debug)]
+pub struct PidFd(FileDesc);
+
+impl PidFd {
+    pub fn kill(&self) -> io::Result<()> {
+        self.send_signal(libc::SIGKILL)
+    }
+
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
+        cvt(unsafe {
+            libc::syscall(
+                SYS_pidfd_send_signal,
+                self.0.as_raw_fd(),
+                signal,
+                ptr::null::<libc::siginfo_t>(),
+                0,
+            )
+        })
+        .map(drop)
+    }
+
+    pub fn wait(&self) -> io::Result<ExitStatus> {
+        let status = self.waitid(libc::WEXITED)?;
+        Ok(status.expect("waitid() without WNOHANG should always report a status"))
+    }
+
+    pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
+        self.waitid(libc::WEXITED | libc::WNOHANG)
+    }
+
+    pub fn wait_timeout(&self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        // A pidfd becomes readable once the process has exited, so there is no need to spin on
+        // `try_wait` until the timeout expires.
+        let deadline = Instant::now().checked_add(timeout);
+        let mut pollfd = libc::pollfd { fd: self.0.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+
+        loop {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    // Round up, so that we don't wake up just before the deadline.
+                    let nanos = deadline.saturating_duration_since(Instant::now()).as_nanos();
+                    let millis = (nanos + 999_999) / 1_000_000;
+                    cmp::min(millis, c_int::MAX as u128) as c_int
+                }
+                // So far in the future that it's practically the same as waiting indefinitely.
+                None => -1,
+            };
+
+            match unsafe { libc::poll(&mut pollfd, 1, timeout) } {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+                0 => return Ok(None),
+                _ => return self.try_wait(),
+            }
+        }
+    }
+
+    fn waitid(&self, options: c_int) -> io::Result<Option<ExitStatus>> {
+        let mut siginfo: libc::siginfo_t = unsafe { mem::zeroed() };
+        cvt_r(|| unsafe {
+            libc::waitid(libc::P_PIDFD, self.0.as_raw_fd() as libc::id_t, &mut siginfo, options)
+        })?;
+        // SAFETY: `siginfo_t` is larger than `SigchldInfo` and at least as aligned, and `waitid`
+        // only reports `SIGCHLD` information.
+        let info = unsafe { &*(&siginfo as *const libc::siginfo_t).cast::<SigchldInfo>() };
+        // With `WNOHANG`, `waitid` leaves `si_pid` zeroed if the process hasn't exited yet.
+        if info.pid == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(ExitStatus::from_waitid(siginfo.si_code, info.status)))
+        }
+    }
+
+    pub fn duplicate(&self) -> io::Result<PidFd> {
+        self.0.duplicate().map(PidFd)
+    }
+}
+
+impl AsRawFd for PidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl FromRawFd for PidFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        PidFd(FileDesc::from_raw_fd(fd))
+    }
+}
+
+impl IntoRawFd for PidFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_raw_fd()
+    }
+}
+
+impl AsFd for PidFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl FromInner<OwnedFd> for PidFd {
+    fn from_inner(fd: OwnedFd) -> Self {
+        PidFd(FileDesc::from_inner(fd))
+    }
+}
+
+impl IntoInner<OwnedFd> for PidFd {
+    fn into_inner(self) -> OwnedFd {
+        self.0.into_inner()
+    }
+}
@@ -574,18 +574,22 @@ pub struct Process {
     // This is None if the user did not request pidfd creation,
     // or if the pidfd could not be created for some reason
     // (e.g. the `clone3` syscall was not available).
+    // Once a pidfd exists, waits always go through it, since the child may
+    // have been reaped through the user's copy and its PID reused.
     #[cfg(target_os = "linux")]
     pidfd: Option<PidFd>,
+    // Whether the user took ownership of a duplicate of `pidfd` with `take_pidfd`.
+    #[cfg(target_os = "linux")]
+    pidfd_taken: bool,
 }
 
 impl Process {
     #[cfg(target_os = "linux")]
     unsafe fn new(pid: pid_t, pidfd: pid_t) -> Self {
         use crate::os::unix::io::FromRawFd;
-        use crate::sys_common::FromInner;
         // Safety: If `pidfd` is nonnegative, we assume it's valid and otherwise unowned.
-        let pidfd = (pidfd >= 0).then(|| PidFd::from_inner(sys::fd::FileDesc::from_raw_fd(pidfd)));
-        Process { pid, status: None, pidfd }
+        let pidfd = (pidfd >= 0).then(|| PidFd::from_raw_fd(pidfd));
+        Process { pid, status: None, pidfd, pidfd_taken: false }
     }
 
     #[cfg(not(target_os = "linux"))]
@@ -607,6 +611,12 @@ pub fn kill(&mut self) -> io::Result<()> {
                 "invalid argument: can't kill an exited process",
             ))
         } else {
+            // Prefer the pidfd if we have one, since it can't refer to another process even if
+            // the child was reaped behind our back.
+            #[cfg(target_os = "linux")]
+            if let Some(pidfd) = &self.pidfd {
+                return pidfd.kill();
+            }
             cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
         }
     }
@@ -616,6 +626,12 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
         if let Some(status) = self.status {
             return Ok(status);
         }
+        #[cfg(target_os = "linux")]
+        if let Some(status) = self.pidfd_wait(sys::pidfd::PidFd::wait) {
+            let status = status?;
+            self.status = Some(status);
+            return Ok(status);
+        }
         let mut status = 0 as c_int;
         cvt_r(|| unsafe { libc::waitpid(self.pid, &mut status, 0) })?;
         self.status = Some(ExitStatus::new(status));
@@ -626,6 +642,12 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         if let Some(status) = self.status {
             return Ok(Some(status));
         }
+        #[cfg(target_os = "linux")]
+        if let Some(status) = self.pidfd_wait(sys::pidfd::PidFd::try_wait) {
+            let status = status?;
+            self.status = status;
+            return Ok(status);
+        }
         let mut status = 0 as c_int;
         let pid = cvt(unsafe { libc::waitpid(self.pid, &mut status, libc::WNOHANG) })?;
         if pid == 0 {
@@ -635,6 +657,23 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    // Waits through the pidfd, if there is one. Returns `None` if there is no
+    // pidfd, or if the kernel can't wait on pidfds (Linux 5.3 supports creating
+    // them but not `P_PIDFD`). In both cases, nothing can have reaped the child
+    // through a pidfd, so waiting on its PID is sound.
+    #[cfg(target_os = "linux")]
+    fn pidfd_wait<T>(
+        &self,
+        wait: fn(&sys::pidfd::PidFd) -> io::Result<T>,
+    ) -> Option<io::Result<T>> {
+        use crate::sys_common::AsInner;
+
+        match wait(self.pidfd.as_ref()?.as_inner()) {
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => None,
+            result => Some(result),
+        }
+    }
 }
 
 /// Unix exit statuses
@@ -655,6 +694,20 @@ pub fn new(status: c_int) -> ExitStatus {
         ExitStatus(status)
     }
 
+    /// Converts the `si_code` and `si_status` reported by `waitid` into the wait status
+    /// `waitpid` would have reported.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn from_waitid(code: c_int, status: c_int) -> ExitStatus {
+        match code {
+            libc::CLD_EXITED => ExitStatus((status & 0xff) << 8),
+            libc::CLD_KILLED => ExitStatus(status),
+            libc::CLD_DUMPED => ExitStatus(status | 0x80),
+            libc::CLD_CONTINUED => ExitStatus(0xffff),
+            libc::CLD_STOPPED | libc::CLD_TRAPPED => ExitStatus(((status & 0xff) << 8) | 0x7f),
+            _ => unreachable!("waitid() should only return the above codes"),
+        }
+    }
+
     fn exited(&self) -> bool {
         libc::WIFEXITED(self.0)
     }
@@ -828,14 +881,18 @@ fn pidfd(&self) -> io::Result<&PidFd> {
         self.handle
             .pidfd
             .as_ref()
+            .filter(|_| !self.handle.pidfd_taken)
             .ok_or_else(|| Error::new(ErrorKind::Uncategorized, "No pidfd was created."))
     }
 
     fn take_pidfd(&mut self) -> io::Result<PidFd> {
-        self.handle
-            .pidfd
-            .take()
-            .ok_or_else(|| Error::new(ErrorKind::Uncategorized, "No pidfd was created."))
+        use crate::os::linux::process::ChildExt;
+        use crate::sys_common::{AsInner, FromInner};
+
+        let pidfd = PidFd::from_inner(ChildExt::pidfd(self)?.as_inner().duplicate()?);
+        // Keep our own copy, so that `Child` waits keep going through a pidfd.
+        self.handle.pidfd_taken = true;
+        Ok(pidfd)
     }
 }
 
@@ -7,6 +7,13 @@
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
 
+#[cfg(target_os = "linux")]
+use crate::fmt;
+#[cfg(target_os = "linux")]
+use crate::sync::mpsc;
+#[cfg(target_os = "linux")]
+use crate::sys::{cvt, cvt_nz};
+
 #[cfg(all(target_os = "linux", target_env = "gnu"))]
 use crate::sys::weak::dlsym;
 #[cfg(any(target_os = "solaris", target_os = "illumos"))]
@@ -37,6 +44,17 @@ pub fn zx_object_set_property(
     }
 }
 
+// Not exposed by the `libc` crate, but provided by every Linux libc.
+#[cfg(target_os = "linux")]
+mod pthread_guard {
+    extern "C" {
+        pub fn pthread_attr_setguardsize(
+            attr: *mut libc::pthread_attr_t,
+            guardsize: libc::size_t,
+        ) -> libc::c_int;
+    }
+}
+
 pub struct Thread {
     id: libc::pthread_t,
 }
@@ -49,6 +67,67 @@ unsafe impl Sync for Thread {}
 impl Thread {
     // unsafe: see thread::Builder::spawn_unchecked for safety requirements
     pub unsafe fn new(stack: usize, p: Box<dyn FnOnce()>) -> io::Result<Thread> {
+        Thread::new_with_attr(stack, p, |_| Ok(()))
+    }
+
+    /// Spawns a thread that applies `options` before running `p`.
+    ///
+    /// Options that can be expressed as pthread attributes are set on the
+    /// attributes. The others are applied by the new thread itself before it
+    /// runs `p`. In that case this waits until they have been applied, so that
+    /// failing to apply them is reported as an error here.
+    #[cfg(target_os = "linux")]
+    pub unsafe fn new_with_options(
+        stack: usize,
+        options: &ThreadOptions,
+        p: Box<dyn FnOnce()>,
+    ) -> io::Result<Thread> {
+        let guard_size = options.guard_size;
+        let configure_attr = |attr: &mut libc::pthread_attr_t| match guard_size {
+            // `guard::current` relies on every thread spawned by Rust having a guard area.
+            Some(0) => Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "the guard size of a thread must not be zero",
+            )),
+            Some(size) => cvt_nz(pthread_guard::pthread_attr_setguardsize(attr, size)),
+            None => Ok(()),
+        };
+
+        if !options.applies_in_thread() {
+            return Thread::new_with_attr(stack, p, configure_attr);
+        }
+
+        let (result_tx, result_rx) = mpsc::sync_channel(1);
+        let options = options.clone();
+        let p = Box::new(move || {
+            let result = options.apply_to_current();
+            let is_ok = result.is_ok();
+            // The spawning thread is blocked on the receiver, so this can't fail.
+            let _ = result_tx.send(result);
+            // If the options could not be applied, drop `p` without running it.
+            if is_ok {
+                p();
+            }
+        });
+        let thread = Thread::new_with_attr(stack, p, configure_attr)?;
+
+        match result_rx.recv() {
+            Ok(Ok(())) => Ok(thread),
+            Ok(Err(e)) => {
+                thread.join();
+                Err(e)
+            }
+            Err(_) => unreachable!("thread exited without applying its options"),
+        }
+    }
+
+    // `configure_attr` is called on the attributes of the new thread after its
+    // stack size has been set.
+    unsafe fn new_with_attr(
+        stack: usize,
+        p: Box<dyn FnOnce()>,
+        configure_attr: impl FnOnce(&mut libc::pthread_attr_t) -> io::Result<()>,
+    ) -> io::Result<Thread> {
         let p = Box::into_raw(box p);
         let mut native: libc::pthread_t = mem::zeroed();
         let mut attr: libc::pthread_attr_t = mem::zeroed();
@@ -84,6 +163,13 @@ pub unsafe fn new(stack: usize, p: Box<dyn FnOnce()>) -> io::Result<Thread> {
             };
         }
 
+        if let Err(e) = configure_attr(&mut attr) {
+            assert_eq!(libc::pthread_attr_destroy(&mut attr), 0);
+            // The thread was never created, so `p` was not consumed.
+            drop(Box::from_raw(p));
+            return Err(e);
+        }
+
         let ret = libc::pthread_create(&mut native, &attr, thread_start, p as *mut _);
         // Note: if the thread creation fails and this assert fails, then p will
         // be leaked. However, an alternative design could cause double-free
@@ -284,6 +370,129 @@ fn drop(&mut self) {
     }
 }
 
+/// Linux-specific options for a thread that has not been spawned yet, set
+/// through `std::os::linux::thread::BuilderExt`.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Debug, Default)]
+pub struct ThreadOptions {
+    pub affinity: Option<CpuSet>,
+    pub nice: Option<libc::c_int>,
+    pub sched_policy: Option<SchedPolicy>,
+    pub guard_size: Option<usize>,
+}
+
+#[cfg(target_os = "linux")]
+impl ThreadOptions {
+    // Whether any of the options has to be applied by the new thread itself.
+    fn applies_in_thread(&self) -> bool {
+        self.affinity.is_some() || self.nice.is_some() || self.sched_policy.is_some()
+    }
+
+    // On Linux, all of these calls affect only the calling thread when passed
+    // an id of 0.
+    fn apply_to_current(&self) -> io::Result<()> {
+        if let Some(cpus) = &self.affinity {
+            cvt(unsafe {
+                libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &cpus.0)
+            })?;
+        }
+        if let Some(policy) = self.sched_policy {
+            let (policy, priority) = match policy {
+                SchedPolicy::Other => (libc::SCHED_OTHER, 0),
+                SchedPolicy::Batch => (libc::SCHED_BATCH, 0),
+                SchedPolicy::Idle => (libc::SCHED_IDLE, 0),
+                SchedPolicy::Fifo(priority) => (libc::SCHED_FIFO, priority),
+                SchedPolicy::RoundRobin(priority) => (libc::SCHED_RR, priority),
+            };
+            // `sched_param` has more fields than `sched_priority` on some targets.
+            let mut param: libc::sched_param = unsafe { mem::zeroed() };
+            param.sched_priority = priority;
+            cvt(unsafe { libc::sched_setscheduler(0, policy, &param) })?;
+        }
+        if let Some(nice) = self.nice {
+            cvt(unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) })?;
+        }
+        Ok(())
+    }
+}
+
+/// The scheduling policy of a thread, see `sched(7)`.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchedPolicy {
+    Other,
+    Batch,
+    Idle,
+    Fifo(libc::c_int),
+    RoundRobin(libc::c_int),
+}
+
+/// A set of CPUs, as used by `sched_setaffinity(2)`.
+#[cfg(target_os = "linux")]
+#[derive(Clone)]
+pub struct CpuSet(libc::cpu_set_t);
+
+#[cfg(target_os = "linux")]
+impl CpuSet {
+    // `libc::CPU_SETSIZE` is not available for every Linux target, but it is
+    // always the number of bits in a `cpu_set_t`.
+    pub const CAPACITY: usize = mem::size_of::<libc::cpu_set_t>() * 8;
+
+    pub fn new() -> CpuSet {
+        // An all-zero `cpu_set_t` is the empty set.
+        CpuSet(unsafe { mem::zeroed() })
+    }
+
+    /// Returns the affinity mask of the calling thread.
+    pub fn current() -> io::Result<CpuSet> {
+        let mut set = CpuSet::new();
+        cvt(unsafe {
+            libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set.0)
+        })?;
+        Ok(set)
+    }
+
+    pub fn insert(&mut self, cpu: usize) {
+        assert!(cpu < Self::CAPACITY, "CPU index {cpu} is out of range for a CPU set");
+        unsafe { libc::CPU_SET(cpu, &mut self.0) }
+    }
+
+    pub fn remove(&mut self, cpu: usize) {
+        if cpu < Self::CAPACITY {
+            unsafe { libc::CPU_CLR(cpu, &mut self.0) }
+        }
+    }
+
+    pub fn contains(&self, cpu: usize) -> bool {
+        cpu < Self::CAPACITY && unsafe { libc::CPU_ISSET(cpu, &self.0) }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { libc::CPU_COUNT(&self.0) as usize }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..Self::CAPACITY).filter(|&cpu| self.contains(cpu))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl PartialEq for CpuSet {
+    fn eq(&self, other: &CpuSet) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Eq for CpuSet {}
+
+#[cfg(target_os = "linux")]
+impl fmt::Debug for CpuSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
 #[cfg(any(target_os = "linux", target_os = "macos", target_os = "ios", target_os = "watchos"))]
 fn truncate_cstr(cstr: &CStr, max_with_nul: usize) -> crate::borrow::Cow<'_, CStr> {
     use crate::{borrow::Cow, ffi::CString};
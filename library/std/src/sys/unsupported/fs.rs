@@ -6,6 +6,8 @@
 use crate::sys::time::SystemTime;
 use crate::sys::unsupported;
 
+pub use crate::sys_common::fs::UnsupportedDir as Dir;
+
 pub struct File(!);
 
 pub struct FileAttr(!);
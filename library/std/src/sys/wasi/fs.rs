@@ -18,6 +18,7 @@
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 
 pub use crate::sys_common::fs::try_exists;
+pub use crate::sys_common::fs::UnsupportedDir as Dir;
 
 pub struct File {
     fd: WasiFd,
@@ -18,6 +18,10 @@
 use super::path::maybe_verbatim;
 use super::to_u16s;
 
+// Directory handles would need `NtCreateFile` with a root directory handle, which isn't
+// implemented yet.
+pub use crate::sys_common::fs::UnsupportedDir as Dir;
+
 pub struct File {
     handle: Handle,
 }
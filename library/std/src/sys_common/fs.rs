@@ -3,6 +3,7 @@
 use crate::fs;
 use crate::io::{self, Error, ErrorKind};
 use crate::path::Path;
+use crate::sys::fs::{File, FileAttr, OpenOptions, ReadDir};
 
 pub(crate) const NOT_FILE_ERROR: Error = io::const_io_error!(
     ErrorKind::InvalidInput,
@@ -49,3 +50,72 @@ pub fn try_exists(path: &Path) -> io::Result<bool> {
         Err(error) => Err(error),
     }
 }
+
+/// Directory handle for platforms without directory-relative file system operations.
+///
+/// It can't be opened, so none of the operations on it can ever be reached.
+pub struct UnsupportedDir(!);
+
+impl UnsupportedDir {
+    pub fn open(_path: &Path) -> io::Result<UnsupportedDir> {
+        Err(io::const_io_error!(
+            ErrorKind::Unsupported,
+            "directory handles are not supported on this platform",
+        ))
+    }
+
+    pub fn open_dir(&self, _path: &Path) -> io::Result<UnsupportedDir> {
+        self.0
+    }
+
+    pub fn open_dir_nofollow(&self, _path: &Path) -> io::Result<UnsupportedDir> {
+        self.0
+    }
+
+    pub fn open_file(&self, _path: &Path, _opts: &OpenOptions) -> io::Result<File> {
+        self.0
+    }
+
+    pub fn create_dir(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn remove_file(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn remove_dir(&self, _path: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn rename(&self, _from: &Path, _to_dir: &UnsupportedDir, _to: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn metadata(&self, _path: &Path) -> io::Result<FileAttr> {
+        self.0
+    }
+
+    pub fn symlink_metadata(&self, _path: &Path) -> io::Result<FileAttr> {
+        self.0
+    }
+
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.0
+    }
+
+    pub fn symlink(&self, _original: &Path, _link: &Path) -> io::Result<()> {
+        self.0
+    }
+
+    #[cfg(any(unix, target_os = "wasi"))]
+    pub fn as_fd(&self) -> crate::os::fd::BorrowedFd<'_> {
+        self.0
+    }
+}
+
+impl crate::fmt::Debug for UnsupportedDir {
+    fn fmt(&self, _f: &mut crate::fmt::Formatter<'_>) -> crate::fmt::Result {
+        self.0
+    }
+}
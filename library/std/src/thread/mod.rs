@@ -287,8 +287,15 @@ pub struct Builder {
     name: Option<String>,
     // The size of the stack for the spawned thread in bytes
     stack_size: Option<usize>,
+    // Options set through `std::os::linux::thread::BuilderExt`
+    #[cfg(target_os = "linux")]
+    options: imp::ThreadOptions,
 }
 
+/// Allows extension traits within `std`.
+#[unstable(feature = "sealed", issue = "none")]
+impl crate::sealed::Sealed for Builder {}
+
 impl Builder {
     /// Generates the base configuration for spawning a thread, from which
     /// configuration methods can be chained.
@@ -310,7 +317,12 @@ impl Builder {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Builder {
-        Builder { name: None, stack_size: None }
+        Builder {
+            name: None,
+            stack_size: None,
+            #[cfg(target_os = "linux")]
+            options: Default::default(),
+        }
     }
 
     /// Names the thread-to-be. Currently the name is used for identification
@@ -490,7 +502,7 @@ unsafe fn spawn_unchecked_<'a, 'scope, F, T>(
         T: Send + 'a,
         'scope: 'a,
     {
-        let Builder { name, stack_size } = self;
+        let Builder { name, stack_size, .. } = self;
 
         let stack_size = stack_size.unwrap_or_else(thread::min_stack);
 
@@ -582,12 +594,14 @@ fn drop(&mut self) {
             // exist after the thread has terminated, which is signaled by `Thread::join`
             // returning.
             native: unsafe {
-                imp::Thread::new(
-                    stack_size,
-                    mem::transmute::<Box<dyn FnOnce() + 'a>, Box<dyn FnOnce() + 'static>>(
-                        Box::new(main),
-                    ),
-                )?
+                let main = mem::transmute::<Box<dyn FnOnce() + 'a>, Box<dyn FnOnce() + 'static>>(
+                    Box::new(main),
+                );
+                #[cfg(target_os = "linux")]
+                let native = imp::Thread::new_with_options(stack_size, &self.options, main);
+                #[cfg(not(target_os = "linux"))]
+                let native = imp::Thread::new(stack_size, main);
+                native?
             },
             thread: my_thread,
             packet: my_packet,
@@ -595,6 +609,13 @@ fn drop(&mut self) {
     }
 }
 
+#[cfg(target_os = "linux")]
+impl crate::sys_common::AsInnerMut<imp::ThreadOptions> for Builder {
+    fn as_inner_mut(&mut self) -> &mut imp::ThreadOptions {
+        &mut self.options
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Free functions
 ////////////////////////////////////////////////////////////////////////////////
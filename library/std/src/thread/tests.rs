@@ -68,6 +68,44 @@ fn test_named_thread_truncation() {
     result.unwrap().join().unwrap();
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_linux_builder_options() {
+    use crate::os::linux::thread::{current_affinity, BuilderExt, CpuSet, SchedPolicy};
+
+    let cpu = current_affinity().unwrap().iter().next().unwrap();
+    let cpus: CpuSet = [cpu].into_iter().collect();
+    let expected = cpus.clone();
+
+    Builder::new()
+        .affinity(cpus)
+        // Raising the nice value and switching to `SCHED_BATCH` need no privileges.
+        .nice(19)
+        .sched_policy(SchedPolicy::Batch)
+        .guard_size(64 * 1024)
+        .spawn(move || {
+            assert_eq!(current_affinity().unwrap(), expected);
+            assert_eq!(unsafe { libc::sched_getscheduler(0) }, libc::SCHED_BATCH);
+            assert_eq!(unsafe { libc::getpriority(libc::PRIO_PROCESS, 0) }, 19);
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_linux_builder_invalid_options() {
+    use crate::io::ErrorKind;
+    use crate::os::linux::thread::{BuilderExt, CpuSet};
+
+    let err = Builder::new().affinity(CpuSet::new()).spawn(|| panic!("ran")).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+
+    let err = Builder::new().guard_size(0).spawn(|| panic!("ran")).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
 #[test]
 #[should_panic]
 fn test_invalid_named_thread() {
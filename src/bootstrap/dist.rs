@@ -20,6 +20,7 @@
 use crate::compile;
 use crate::config::TargetSelection;
 use crate::doc::DocumentationFormat;
+use crate::metadata;
 use crate::tarball::{GeneratedTarball, OverlayKind, Tarball};
 use crate::tool::{self, Tool};
 use crate::util::{exe, is_dylib, output, t, timeit};
@@ -867,6 +868,17 @@ fn run(self, builder: &Builder<'_>) -> GeneratedTarball {
             builder.copy(&builder.src.join(file), &dst_src.join(file));
         }
 
+        // `compiler_builtins` comes from crates.io, but `-Z build-sysroot` needs its source to
+        // build the sysroot from this component.
+        if !builder.config.dry_run() {
+            let dst_compiler_builtins = dst_src.join("vendor/compiler_builtins");
+            t!(fs::create_dir_all(&dst_compiler_builtins));
+            builder.cp_r(
+                &metadata::registry_crate_dir(builder, "compiler_builtins"),
+                &dst_compiler_builtins,
+            );
+        }
+
         tarball.generate()
     }
 }
@@ -57,3 +57,36 @@ pub fn build(build: &mut Build) {
         }
     }
 }
+
+/// Returns the directory containing the source of `name`, a dependency of the standard library
+/// from crates.io, in the version locked by `Cargo.lock`.
+///
+/// Unlike in `build`, the dependencies have to be resolved to find their source, but `Cargo.lock`
+/// is never updated for this.
+pub fn registry_crate_dir(build: &Build, name: &str) -> PathBuf {
+    let mut cargo = Command::new(&build.initial_cargo);
+    cargo
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .arg("--locked")
+        .arg("--manifest-path")
+        .arg(build.src.join("library/std/Cargo.toml"));
+    if build.config.vendor || build.is_sudo {
+        cargo.arg("--frozen");
+    }
+    let output = output(&mut cargo);
+    let output: Output = serde_json::from_str(&output).unwrap();
+    let mut packages = output
+        .packages
+        .into_iter()
+        .filter(|package| package.name == name && package.source.is_some());
+    match (packages.next(), packages.next()) {
+        (Some(package), None) => {
+            let mut path = PathBuf::from(package.manifest_path);
+            path.pop();
+            path
+        }
+        _ => panic!("expected exactly one version of `{}` from crates.io in `Cargo.lock`", name),
+    }
+}
@@ -128,6 +128,7 @@ fn generate_for_trait(
                 items: Vec::new(),
                 polarity,
                 kind: ImplKind::Auto,
+                provenance: None,
             }))),
             cfg: None,
             inline_stmt_id: None,
@@ -94,6 +94,32 @@ pub(crate) fn get_blanket_impls(&mut self, item_def_id: DefId) -> Vec<Item> {
 
                 cx.generated_synthetics.insert((ty.0, trait_def_id));
 
+                // Substitute the documented type for the blanket impl's self type, so that the
+                // bounds are shown in terms of the documented type.
+                let ty::Param(self_param) = *trait_ref.0.self_ty().kind() else {
+                    unreachable!("blanket impls are implemented on a type parameter")
+                };
+                let provenance_substs =
+                    InternalSubsts::for_item(cx.tcx, impl_def_id, |param, _| {
+                        if param.index == self_param.index {
+                            ty.0.into()
+                        } else {
+                            cx.tcx.mk_param_from_def(param)
+                        }
+                    });
+                let sized_trait = cx.tcx.lang_items().sized_trait();
+                let bounds = cx
+                    .tcx
+                    .predicates_of(impl_def_id)
+                    .instantiate(cx.tcx, provenance_substs)
+                    .predicates
+                    .into_iter()
+                    .filter(|pred| {
+                        pred.to_opt_poly_trait_pred().map(|pred| pred.def_id()) != sized_trait
+                    })
+                    .filter_map(|pred| clean_predicate(pred, cx))
+                    .collect();
+
                 impls.push(Item {
                     name: None,
                     attrs: Default::default(),
@@ -125,6 +151,10 @@ pub(crate) fn get_blanket_impls(&mut self, item_def_id: DefId) -> Vec<Item> {
                             cx,
                             None,
                         ))),
+                        provenance: Some(ImplProvenance::Blanket {
+                            krate: impl_def_id.krate,
+                            bounds,
+                        }),
                     }))),
                     cfg: None,
                     inline_stmt_id: None,
@@ -551,6 +551,7 @@ pub(crate) fn build_impl(
             } else {
                 ImplKind::Normal
             },
+            provenance: utils::derive_macro_name(tcx, did).map(clean::ImplProvenance::Derive),
         })),
         Box::new(merged_attrs),
         cfg,
@@ -2109,6 +2109,7 @@ fn clean_maybe_renamed_item<'tcx>(
             extra_attrs.extend_from_slice(inline::load_attrs(cx, def_id));
             let attrs = Attributes::from_ast(&extra_attrs);
             let cfg = extra_attrs.cfg(cx.tcx, &cx.cache.hidden_cfg);
+            utils::load_linked_examples(cx, def_id, &attrs);
 
             vec![Item::from_def_id_and_attrs_and_parts(
                 def_id,
@@ -2167,6 +2168,8 @@ fn clean_impl<'tcx>(
             } else {
                 ImplKind::Normal
             },
+            provenance: utils::derive_macro_name(tcx, def_id.to_def_id())
+                .map(ImplProvenance::Derive),
         }));
         Item::from_hir_id_and_parts(hir_id, None, kind, cx)
     };
@@ -461,12 +461,16 @@ pub(crate) fn from_def_id_and_parts(
         cx: &mut DocContext<'_>,
     ) -> Item {
         let ast_attrs = cx.tcx.get_attrs_unchecked(def_id);
+        let attrs = Box::new(Attributes::from_ast(ast_attrs));
+        if def_id.is_local() {
+            crate::clean::utils::load_linked_examples(cx, def_id, &attrs);
+        }
 
         Self::from_def_id_and_attrs_and_parts(
             def_id,
             name,
             kind,
-            Box::new(Attributes::from_ast(ast_attrs)),
+            attrs,
             ast_attrs.cfg(cx.tcx, &cx.cache.hidden_cfg),
         )
     }
@@ -1180,6 +1184,14 @@ pub(crate) struct ItemLink {
     pub(crate) fragment: Option<UrlFragment>,
 }
 
+/// A file attached to an item with `#[doc(example = "path")]`.
+#[derive(Clone, Debug)]
+pub(crate) struct LinkedExample {
+    /// The file name, used as the title of the example.
+    pub(crate) name: String,
+    pub(crate) contents: String,
+}
+
 pub struct RenderedLink {
     /// The text the link was original written as.
     ///
@@ -2427,6 +2439,8 @@ pub(crate) struct Impl {
     pub(crate) items: Vec<Item>,
     pub(crate) polarity: ty::ImplPolarity,
     pub(crate) kind: ImplKind,
+    /// Where this impl comes from, if it wasn't written out by hand.
+    pub(crate) provenance: Option<ImplProvenance>,
 }
 
 impl Impl {
@@ -2468,6 +2482,16 @@ pub(crate) fn as_blanket_ty(&self) -> Option<&Type> {
     }
 }
 
+/// Describes how an impl which wasn't written out by hand for the documented type was generated.
+#[derive(Clone, Debug)]
+pub(crate) enum ImplProvenance {
+    /// The impl was generated by `#[derive(...)]`; this is the name of the derive macro.
+    Derive(Symbol),
+    /// A blanket impl defined in `krate`, which applies to the documented type as long as
+    /// `bounds` hold.
+    Blanket { krate: CrateNum, bounds: ThinVec<WherePredicate> },
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct Import {
     pub(crate) kind: ImportKind,
@@ -2,9 +2,10 @@
 use crate::clean::blanket_impl::BlanketImplFinder;
 use crate::clean::render_macro_matchers::render_macro_matcher;
 use crate::clean::{
-    clean_doc_module, clean_middle_const, clean_middle_region, clean_middle_ty, inline, Crate,
-    ExternalCrate, Generic, GenericArg, GenericArgs, ImportSource, Item, ItemKind, Lifetime, Path,
-    PathSegment, Primitive, PrimitiveType, Term, Type, TypeBinding, TypeBindingKind,
+    clean_doc_module, clean_middle_const, clean_middle_region, clean_middle_ty, inline, Attributes,
+    Crate, ExternalCrate, Generic, GenericArg, GenericArgs, ImportSource, Item, ItemId, ItemKind,
+    Lifetime, LinkedExample, Path, PathSegment, Primitive, PrimitiveType, Term, Type, TypeBinding,
+    TypeBindingKind,
 };
 use crate::core::DocContext;
 use crate::html::format::visibility_to_src_with_space;
@@ -18,9 +19,13 @@
 use rustc_middle::mir::interpret::ConstValue;
 use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
 use rustc_middle::ty::{self, DefIdTree, TyCtxt};
+use rustc_session::Session;
+use rustc_span::hygiene::{ExpnKind, MacroKind};
 use rustc_span::symbol::{kw, sym, Symbol};
+use rustc_span::Span;
 use std::fmt::Write as _;
 use std::mem;
+use std::path::PathBuf;
 use thin_vec::{thin_vec, ThinVec};
 
 #[cfg(test)]
@@ -552,6 +557,17 @@ pub(crate) fn has_doc_flag(tcx: TyCtxt<'_>, did: DefId, flag: Symbol) -> bool {
     })
 }
 
+/// Returns the name of the derive macro which generated the impl `did`, if any.
+pub(crate) fn derive_macro_name(tcx: TyCtxt<'_>, did: DefId) -> Option<Symbol> {
+    if !tcx.has_attr(did, sym::automatically_derived) {
+        return None;
+    }
+    match tcx.def_span(did).ctxt().outer_expn_data().kind {
+        ExpnKind::Macro(MacroKind::Derive, name) => Some(name),
+        _ => None,
+    }
+}
+
 /// A link to `doc.rust-lang.org` that includes the channel name. Use this instead of manual links
 /// so that the channel is consistent.
 ///
@@ -604,3 +620,52 @@ pub(super) fn display_macro_source(
         }
     }
 }
+
+/// Returns the files attached to an item with `#[doc(example = "path")]`, along with the span of
+/// each attribute. Relative paths are resolved from the file containing the attribute, like the
+/// path given to `include_str!`.
+///
+/// Must only be called on the attributes of local items.
+pub(crate) fn linked_examples(sess: &Session, attrs: &Attributes) -> Vec<(PathBuf, Span)> {
+    attrs
+        .lists(sym::doc)
+        .filter(|meta| meta.has_name(sym::example))
+        .filter_map(|meta| {
+            let path = meta.value_str()?;
+            let span = meta.span();
+            match rustc_expand::base::resolve_path(&sess.parse_sess, path.as_str(), span) {
+                Ok(path) => Some((path, span)),
+                Err(mut err) => {
+                    err.emit();
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Reads the files attached to the local item `def_id` with `#[doc(example = "path")]` into the
+/// cache, reporting the ones which can't be read. Items are only read the first time they're
+/// cleaned.
+pub(crate) fn load_linked_examples(cx: &mut DocContext<'_>, def_id: DefId, attrs: &Attributes) {
+    let item_id = ItemId::DefId(def_id);
+    if cx.cache.linked_examples.contains_key(&item_id) {
+        return;
+    }
+    let examples = linked_examples(cx.tcx.sess, attrs)
+        .into_iter()
+        .filter_map(|(path, span)| match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                let name = path.file_name().unwrap_or(path.as_os_str());
+                Some(LinkedExample { name: name.to_string_lossy().into_owned(), contents })
+            }
+            Err(err) => {
+                cx.tcx
+                    .sess
+                    .span_err(span, &format!("failed to read file {}: {}", path.display(), err));
+                None
+            }
+        })
+        .collect();
+    cx.cache.linked_examples.insert(item_id, examples);
+}
@@ -27,6 +27,7 @@
 use crate::html::render::StylePath;
 use crate::html::static_files;
 use crate::opts;
+use crate::passes::calculate_doc_coverage::CoverageOptions;
 use crate::passes::{self, Condition};
 use crate::scrape_examples::{AllCallLocations, ScrapeExamplesOptions};
 use crate::theme;
@@ -138,6 +139,8 @@ pub(crate) struct Options {
     /// Whether to run the `calculate-doc-coverage` pass, which counts the number of public items
     /// with and without documentation.
     pub(crate) show_coverage: bool,
+    /// Additional output and thresholds for the `calculate-doc-coverage` pass.
+    pub(crate) coverage_options: CoverageOptions,
 
     // Options that alter generated documentation pages
     /// Crate version to note on the sidebar of generated docs.
@@ -195,6 +198,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("test_run_directory", &self.test_run_directory)
             .field("persist_doctests", &self.persist_doctests)
             .field("show_coverage", &self.show_coverage)
+            .field("coverage_options", &self.coverage_options)
             .field("crate_version", &self.crate_version)
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
@@ -276,6 +280,12 @@ pub(crate) struct RenderOptions {
     pub(crate) call_locations: AllCallLocations,
     /// If `true`, Context::init will not emit shared files.
     pub(crate) no_emit_shared: bool,
+    /// If `true`, save the data this crate contributes to the shared files, so that the output
+    /// directory can be combined with others using `--link-bundle`.
+    pub(crate) write_bundle: bool,
+    /// Documentation bundles to combine into the output directory. If non-empty, no crate is
+    /// documented.
+    pub(crate) link_bundles: Vec<PathBuf>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -446,8 +456,17 @@ fn println_condition(condition: Condition) {
 
         let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
 
+        let link_bundles: Vec<PathBuf> =
+            matches.opt_strs("link-bundle").into_iter().map(PathBuf::from).collect();
+
         let input = PathBuf::from(if describe_lints {
             "" // dummy, this won't be used
+        } else if !link_bundles.is_empty() {
+            if !matches.free.is_empty() {
+                diag.struct_err("cannot document a crate while linking bundles").emit();
+                return Err(1);
+            }
+            "" // dummy, no crate is documented when linking bundles
         } else if matches.free.is_empty() {
             diag.struct_err("missing file operand").emit();
             return Err(1);
@@ -638,6 +657,7 @@ fn println_condition(condition: Condition) {
         let target = parse_target_triple(matches, error_format);
 
         let show_coverage = matches.opt_present("show-coverage");
+        let coverage_options = CoverageOptions::new(matches, &diag, show_coverage)?;
 
         let crate_types = match parse_crate_types_from_list(matches.opt_strs("crate-type")) {
             Ok(types) => types,
@@ -699,6 +719,7 @@ fn println_condition(condition: Condition) {
         let show_type_layout = matches.opt_present("show-type-layout");
         let nocapture = matches.opt_present("nocapture");
         let generate_link_to_definition = matches.opt_present("generate-link-to-definition");
+        let write_bundle = matches.opt_present("write-bundle");
         let extern_html_root_takes_precedence =
             matches.opt_present("extern-html-root-takes-precedence");
 
@@ -740,6 +761,7 @@ fn println_condition(condition: Condition) {
             should_test,
             test_args,
             show_coverage,
+            coverage_options,
             crate_version,
             test_run_directory,
             persist_doctests,
@@ -783,6 +805,8 @@ fn println_condition(condition: Condition) {
             generate_link_to_definition,
             call_locations,
             no_emit_shared: false,
+            write_bundle,
+            link_bundles,
         };
         Ok((options, render_options))
     }
@@ -28,6 +28,7 @@
 use crate::clean::{self, ItemId};
 use crate::config::{Options as RustdocOptions, OutputFormat, RenderOptions};
 use crate::formats::cache::Cache;
+use crate::passes::calculate_doc_coverage::CoverageOptions;
 use crate::passes::collect_intra_doc_links::PreprocessedMarkdownLink;
 use crate::passes::{self, Condition::*};
 
@@ -81,6 +82,8 @@ pub(crate) struct DocContext<'tcx> {
     pub(crate) output_format: OutputFormat,
     /// Used by `strip_private`.
     pub(crate) show_coverage: bool,
+    /// Used by `calculate_doc_coverage`.
+    pub(crate) coverage_options: CoverageOptions,
 }
 
 impl<'tcx> DocContext<'tcx> {
@@ -321,6 +324,7 @@ pub(crate) fn run_global_ctxt(
     resolver: Rc<RefCell<interface::BoxedResolver>>,
     resolver_caches: ResolverCaches,
     show_coverage: bool,
+    coverage_options: CoverageOptions,
     render_options: RenderOptions,
     output_format: OutputFormat,
 ) -> (clean::Crate, RenderOptions, Cache) {
@@ -367,6 +371,7 @@ pub(crate) fn run_global_ctxt(
         output_format,
         render_options,
         show_coverage,
+        coverage_options,
     };
 
     // Small hack to force the Sized trait to be present.
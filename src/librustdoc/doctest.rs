@@ -1246,6 +1246,22 @@ fn visit_testable<F: FnOnce(&mut Self)>(
             );
         }
 
+        for (path, span) in crate::clean::utils::linked_examples(self.sess, &attrs) {
+            match std::fs::read_to_string(&path) {
+                Ok(contents) => {
+                    self.collector.set_position(span);
+                    let line = self.collector.get_line() + 1;
+                    // Linked examples are only compiled, like `no_run` code blocks.
+                    let config = LangString { no_run: true, ..LangString::default() };
+                    self.collector.add_test(contents, config, line);
+                }
+                Err(err) => {
+                    self.sess
+                        .span_err(span, &format!("failed to read file {}: {}", path.display(), err));
+                }
+            }
+        }
+
         nested(self);
 
         if has_name {
@@ -13,7 +13,7 @@
 use crate::html::format::join_with_double_colon;
 use crate::html::markdown::short_markdown_summary;
 use crate::html::render::search_index::get_function_type_for_search;
-use crate::html::render::IndexItem;
+use crate::html::render::{bundle_extern_root, IndexItem};
 use crate::visit_lib::RustdocEffectiveVisibilities;
 
 /// This cache is used to store information about the [`clean::Crate`] being
@@ -119,6 +119,8 @@ pub(crate) struct Cache {
     ///
     /// Links are indexed by the DefId of the item they document.
     pub(crate) intra_doc_links: FxHashMap<ItemId, Vec<clean::ItemLink>>,
+    /// The files attached to local items with `#[doc(example = "path")]`, read while cleaning.
+    pub(crate) linked_examples: FxHashMap<ItemId, Vec<clean::LinkedExample>>,
     /// Cfg that have been hidden via #![doc(cfg_hide(...))]
     pub(crate) hidden_cfg: FxHashSet<clean::cfg::Cfg>,
 }
@@ -155,7 +157,12 @@ pub(crate) fn populate(cx: &mut DocContext<'_>, mut krate: clean::Crate) -> clea
             let extern_url = render_options.extern_html_root_urls.get(name.as_str()).map(|u| &**u);
             let extern_url_takes_precedence = render_options.extern_html_root_takes_precedence;
             let dst = &render_options.output;
-            let location = e.location(extern_url, extern_url_takes_precedence, dst, tcx);
+            let mut location = e.location(extern_url, extern_url_takes_precedence, dst, tcx);
+            if render_options.write_bundle && matches!(location, ExternalLocation::Unknown) {
+                // The location of the dependency is only known once the bundle is linked, so
+                // link to a placeholder which `--link-bundle` replaces.
+                location = ExternalLocation::Remote(bundle_extern_root(name.as_str()));
+            }
             cx.cache.extern_locations.insert(e.crate_num, location);
             cx.cache.external_paths.insert(e.def_id(), (vec![name], ItemType::Module));
         }
@@ -202,6 +202,49 @@ pub struct Playground {
     pub url: String,
 }
 
+impl Playground {
+    /// Returns the HTML of a "Run" link which opens `code` in the playground, or `None` if no
+    /// playground URL was given.
+    pub(crate) fn button(&self, code: &str, edition: Edition) -> Option<String> {
+        if self.url.is_empty() {
+            return None;
+        }
+        let krate = self.crate_name.as_deref();
+        let (test, _, _) =
+            doctest::make_test(code, krate, false, &Default::default(), edition, None);
+        let channel = if test.contains("#![feature(") { "&amp;version=nightly" } else { "" };
+
+        // These characters don't need to be escaped in a URI.
+        // FIXME: use a library function for percent encoding.
+        fn dont_escape(c: u8) -> bool {
+            (b'a' <= c && c <= b'z')
+                || (b'A' <= c && c <= b'Z')
+                || (b'0' <= c && c <= b'9')
+                || c == b'-'
+                || c == b'_'
+                || c == b'.'
+                || c == b'~'
+                || c == b'!'
+                || c == b'\''
+                || c == b'('
+                || c == b')'
+                || c == b'*'
+        }
+        let mut test_escaped = String::new();
+        for b in test.bytes() {
+            if dont_escape(b) {
+                test_escaped.push(char::from(b));
+            } else {
+                write!(test_escaped, "%{:02X}", b).unwrap();
+            }
+        }
+        Some(format!(
+            r#"<a class="test-arrow" target="_blank" href="{}?code={}{}&amp;edition={}">Run</a>"#,
+            self.url, test_escaped, channel, edition,
+        ))
+    }
+}
+
 /// Adds syntax highlighting and playground Run buttons to Rust code blocks.
 struct CodeBlocks<'p, 'a, I: Iterator<Item = Event<'a>>> {
     inner: I,
@@ -279,49 +322,12 @@ fn next(&mut self) -> Option<Self::Item> {
         let edition = edition.unwrap_or(self.edition);
 
         let playground_button = self.playground.as_ref().and_then(|playground| {
-            let krate = &playground.crate_name;
-            let url = &playground.url;
-            if url.is_empty() {
-                return None;
-            }
             let test = origtext
                 .lines()
                 .map(|l| map_line(l).for_code())
                 .intersperse("\n".into())
                 .collect::<String>();
-            let krate = krate.as_ref().map(|s| &**s);
-            let (test, _, _) =
-                doctest::make_test(&test, krate, false, &Default::default(), edition, None);
-            let channel = if test.contains("#![feature(") { "&amp;version=nightly" } else { "" };
-
-            // These characters don't need to be escaped in a URI.
-            // FIXME: use a library function for percent encoding.
-            fn dont_escape(c: u8) -> bool {
-                (b'a' <= c && c <= b'z')
-                    || (b'A' <= c && c <= b'Z')
-                    || (b'0' <= c && c <= b'9')
-                    || c == b'-'
-                    || c == b'_'
-                    || c == b'.'
-                    || c == b'~'
-                    || c == b'!'
-                    || c == b'\''
-                    || c == b'('
-                    || c == b')'
-                    || c == b'*'
-            }
-            let mut test_escaped = String::new();
-            for b in test.bytes() {
-                if dont_escape(b) {
-                    test_escaped.push(char::from(b));
-                } else {
-                    write!(test_escaped, "%{:02X}", b).unwrap();
-                }
-            }
-            Some(format!(
-                r#"<a class="test-arrow" target="_blank" href="{}?code={}{}&amp;edition={}">Run</a>"#,
-                url, test_escaped, channel, edition,
-            ))
+            playground.button(&test, edition)
         });
 
         let tooltip = if ignore != Ignore::None {
@@ -889,6 +895,11 @@ fn tokens(string: &str) -> impl Iterator<Item = &str> {
             .filter(|token| !token.is_empty())
     }
 
+    /// Whether this code block was explicitly marked as `text`.
+    pub(crate) fn is_text(&self) -> bool {
+        Self::tokens(&self.original).any(|token| token == "text")
+    }
+
     fn parse(
         string: &str,
         allow_error_code_check: ErrorCodes,
@@ -1344,6 +1355,17 @@ pub(crate) struct RustCodeBlock {
 /// Returns a range of bytes for each code block in the markdown that is tagged as `rust` or
 /// untagged (and assumed to be rust).
 pub(crate) fn rust_code_blocks(md: &str, extra_info: &ExtraInfo<'_>) -> Vec<RustCodeBlock> {
+    code_blocks(md, extra_info, |lang_string| lang_string.rust)
+}
+
+/// Returns a range of bytes for each code block in the markdown for which `filter` returns `true`
+/// when given the block's parsed language string. Indented code blocks are always assumed to be
+/// rust.
+pub(crate) fn code_blocks(
+    md: &str,
+    extra_info: &ExtraInfo<'_>,
+    filter: impl Fn(&LangString) -> bool,
+) -> Vec<RustCodeBlock> {
     let mut code_blocks = vec![];
 
     if md.is_empty() {
@@ -1362,7 +1384,7 @@ pub(crate) fn rust_code_blocks(md: &str, extra_info: &ExtraInfo<'_>) -> Vec<Rust
                     } else {
                         LangString::parse(&*syntax, ErrorCodes::Yes, false, Some(extra_info))
                     };
-                    if !lang_string.rust {
+                    if !filter(&lang_string) {
                         continue;
                     }
                     let (code_start, mut code_end) = match p.next() {
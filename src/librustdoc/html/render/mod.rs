@@ -35,6 +35,7 @@
 
 pub(crate) use self::context::*;
 pub(crate) use self::span_map::{collect_spans_and_sources, LinkFromSrc};
+pub(crate) use self::write_shared::{bundle_extern_root, link_bundles};
 
 use std::collections::VecDeque;
 use std::default::Default;
@@ -528,11 +529,46 @@ fn document_full_inner(
         clean::ItemKind::StrippedItem(box kind) | kind => kind,
     };
 
+    render_linked_examples(w, cx, item);
+
     if let clean::ItemKind::FunctionItem(..) | clean::ItemKind::MethodItem(..) = kind {
         render_call_locations(w, cx, item);
     }
 }
 
+/// Renders the files attached to the item with `#[doc(example = "path")]`.
+///
+/// The files are highlighted as Rust code, but otherwise rendered verbatim: unlike in code blocks
+/// of the documentation, lines starting with `# ` are not hidden.
+fn render_linked_examples(w: &mut Buffer, cx: &mut Context<'_>, item: &clean::Item) {
+    let shared = Rc::clone(&cx.shared);
+    let examples = match shared.cache.linked_examples.get(&item.item_id) {
+        Some(examples) => examples,
+        None => return,
+    };
+    for example in examples {
+        let id = cx.id_map.derive("linked-example");
+        write!(
+            w,
+            "<div class=\"linked-example\">\
+                <h5 id=\"{id}\"><a href=\"#{id}\">Example <code>{name}</code></a></h5>",
+            id = id,
+            name = Escape(&example.name),
+        );
+        let playground_button = shared
+            .playground
+            .as_ref()
+            .and_then(|playground| playground.button(&example.contents, shared.edition()));
+        highlight::render_example_with_highlighting(
+            &example.contents,
+            w,
+            highlight::Tooltip::None,
+            playground_button.as_deref(),
+        );
+        w.write_str("</div>");
+    }
+}
+
 /// Add extra information about an item such as:
 ///
 /// * Stability
@@ -1879,11 +1915,49 @@ pub(crate) fn render_impl_summary(
         if let Some(portability) = portability(&i.impl_item, Some(parent)) {
             write!(w, "<span class=\"item-info\">{}</span>", portability);
         }
+        if let Some(provenance) = impl_provenance(inner_impl, cx) {
+            write!(w, "<span class=\"item-info\">{}</span>", provenance);
+        }
     }
 
     w.write_str("</section>");
 }
 
+/// Render where an impl which wasn't written out by hand comes from: the derive macro which
+/// generated it, or the crate defining the blanket impl along with the bounds the documented
+/// type has to satisfy.
+fn impl_provenance(i: &clean::Impl, cx: &Context<'_>) -> Option<String> {
+    let provenance = match i.provenance.as_ref()? {
+        clean::ImplProvenance::Derive(name) => {
+            format!("Derived via <code>#[derive({})]</code>", Escape(name.as_str()))
+        }
+        clean::ImplProvenance::Blanket { krate, bounds } => {
+            let bounds = bounds
+                .iter()
+                .filter_map(|pred| match pred {
+                    clean::WherePredicate::BoundPredicate { ty, bounds, .. }
+                        if !bounds.is_empty() =>
+                    {
+                        Some(format!(
+                            "<code>{}: {}</code>",
+                            ty.print(cx),
+                            print_generic_bounds(bounds, cx)
+                        ))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let krate = cx.tcx().crate_name(*krate);
+            if bounds.is_empty() {
+                format!("Blanket impl from <code>{}</code>", krate)
+            } else {
+                format!("Blanket impl from <code>{}</code> where {}", krate, bounds.join(", "))
+            }
+        }
+    };
+    Some(format!("<div class=\"stab provenance\">{}</div>", provenance))
+}
+
 fn print_sidebar(cx: &Context<'_>, it: &clean::Item, buffer: &mut Buffer) {
     if it.is_struct()
         || it.is_trait()
@@ -1,19 +1,21 @@
+use std::ffi::OsString;
 use std::fs::{self, File};
 use std::io::prelude::*;
 use std::io::{self, BufReader};
-use std::path::{Component, Path};
-use std::rc::Rc;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::channel;
 
 use itertools::Itertools;
 use rustc_data_structures::flock;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_span::edition::Edition;
 use serde::ser::SerializeSeq;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::{collect_paths_for_type, ensure_trailing_slash, Context, BASIC_KEYWORDS};
 use crate::clean::Crate;
 use crate::config::{EmitType, RenderOptions};
-use crate::docfs::PathError;
+use crate::docfs::{DocFS, PathError};
 use crate::error::Error;
 use crate::html::{layout, static_files};
 use crate::{try_err, try_none};
@@ -44,17 +46,6 @@ pub(super) fn write_shared(
     let lock_file = cx.dst.join(".lock");
     let _lock = try_err!(flock::Lock::new(&lock_file, true, true, true), &lock_file);
 
-    // InvocationSpecific resources should always be dynamic.
-    let write_invocation_specific = |p: &str, make_content: &dyn Fn() -> Result<Vec<u8>, Error>| {
-        let content = make_content()?;
-        if options.emit.is_empty() || options.emit.contains(&EmitType::InvocationSpecific) {
-            let output_filename = static_files::suffix_path(p, &cx.shared.resource_suffix);
-            cx.shared.fs.write(cx.dst.join(output_filename), content)
-        } else {
-            Ok(())
-        }
-    };
-
     cx.shared
         .fs
         .create_dir_all(cx.dst.join("static.files"))
@@ -92,98 +83,6 @@ pub(super) fn write_shared(
         })?;
     }
 
-    /// Read a file and return all lines that match the `"{crate}":{data},` format,
-    /// and return a tuple `(Vec<DataString>, Vec<CrateNameString>)`.
-    ///
-    /// This forms the payload of files that look like this:
-    ///
-    /// ```javascript
-    /// var data = {
-    /// "{crate1}":{data},
-    /// "{crate2}":{data}
-    /// };
-    /// use_data(data);
-    /// ```
-    ///
-    /// The file needs to be formatted so that *only crate data lines start with `"`*.
-    fn collect(path: &Path, krate: &str) -> io::Result<(Vec<String>, Vec<String>)> {
-        let mut ret = Vec::new();
-        let mut krates = Vec::new();
-
-        if path.exists() {
-            let prefix = format!("\"{}\"", krate);
-            for line in BufReader::new(File::open(path)?).lines() {
-                let line = line?;
-                if !line.starts_with('"') {
-                    continue;
-                }
-                if line.starts_with(&prefix) {
-                    continue;
-                }
-                if line.ends_with(',') {
-                    ret.push(line[..line.len() - 1].to_string());
-                } else {
-                    // No comma (it's the case for the last added crate line)
-                    ret.push(line.to_string());
-                }
-                krates.push(
-                    line.split('"')
-                        .find(|s| !s.is_empty())
-                        .map(|s| s.to_owned())
-                        .unwrap_or_else(String::new),
-                );
-            }
-        }
-        Ok((ret, krates))
-    }
-
-    /// Read a file and return all lines that match the <code>"{crate}":{data},\</code> format,
-    /// and return a tuple `(Vec<DataString>, Vec<CrateNameString>)`.
-    ///
-    /// This forms the payload of files that look like this:
-    ///
-    /// ```javascript
-    /// var data = JSON.parse('{\
-    /// "{crate1}":{data},\
-    /// "{crate2}":{data}\
-    /// }');
-    /// use_data(data);
-    /// ```
-    ///
-    /// The file needs to be formatted so that *only crate data lines start with `"`*.
-    fn collect_json(path: &Path, krate: &str) -> io::Result<(Vec<String>, Vec<String>)> {
-        let mut ret = Vec::new();
-        let mut krates = Vec::new();
-
-        if path.exists() {
-            let prefix = format!("\"{}\"", krate);
-            for line in BufReader::new(File::open(path)?).lines() {
-                let line = line?;
-                if !line.starts_with('"') {
-                    continue;
-                }
-                if line.starts_with(&prefix) {
-                    continue;
-                }
-                if line.ends_with(",\\") {
-                    ret.push(line[..line.len() - 2].to_string());
-                } else {
-                    // Ends with "\\" (it's the case for the last added crate line)
-                    ret.push(line[..line.len() - 1].to_string());
-                }
-                krates.push(
-                    line.split('"')
-                        .find(|s| !s.is_empty())
-                        .map(|s| s.to_owned())
-                        .unwrap_or_else(String::new),
-                );
-            }
-        }
-        Ok((ret, krates))
-    }
-
-    use std::ffi::OsString;
-
     #[derive(Debug)]
     struct Hierarchy {
         elem: OsString,
@@ -222,7 +121,7 @@ fn to_json_string(&self) -> String {
         }
     }
 
-    if cx.include_sources {
+    let sources = if cx.include_sources {
         let mut hierarchy = Hierarchy::new(OsString::new());
         for source in cx
             .shared
@@ -250,101 +149,24 @@ fn to_json_string(&self) -> String {
             }
         }
 
-        let dst = cx.dst.join(&format!("source-files{}.js", cx.shared.resource_suffix));
-        let make_sources = || {
-            let (mut all_sources, _krates) =
-                try_err!(collect_json(&dst, krate.name(cx.tcx()).as_str()), &dst);
-            all_sources.push(format!(
-                r#""{}":{}"#,
-                &krate.name(cx.tcx()),
-                hierarchy
-                    .to_json_string()
-                    // All these `replace` calls are because we have to go through JS string for JSON content.
-                    .replace('\\', r"\\")
-                    .replace('\'', r"\'")
-                    // We need to escape double quotes for the JSON.
-                    .replace("\\\"", "\\\\\"")
-            ));
-            all_sources.sort();
-            let mut v = String::from("var sourcesIndex = JSON.parse('{\\\n");
-            v.push_str(&all_sources.join(",\\\n"));
-            v.push_str("\\\n}');\ncreateSourceSidebar();\n");
-            Ok(v.into_bytes())
-        };
-        write_invocation_specific("source-files.js", &make_sources)?;
-    }
-
-    // Update the search index and crate list.
-    let dst = cx.dst.join(&format!("search-index{}.js", cx.shared.resource_suffix));
-    let (mut all_indexes, mut krates) =
-        try_err!(collect_json(&dst, krate.name(cx.tcx()).as_str()), &dst);
-    all_indexes.push(search_index);
-    krates.push(krate.name(cx.tcx()).to_string());
-    krates.sort();
-
-    // Sort the indexes by crate so the file will be generated identically even
-    // with rustdoc running in parallel.
-    all_indexes.sort();
-    write_invocation_specific("search-index.js", &|| {
-        let mut v = String::from("var searchIndex = JSON.parse('{\\\n");
-        v.push_str(&all_indexes.join(",\\\n"));
-        v.push_str(
-            r#"\
-}');
-if (typeof window !== 'undefined' && window.initSearch) {window.initSearch(searchIndex)};
-if (typeof exports !== 'undefined') {exports.searchIndex = searchIndex};
-"#,
-        );
-        Ok(v.into_bytes())
-    })?;
-
-    write_invocation_specific("crates.js", &|| {
-        let krates = krates.iter().map(|k| format!("\"{}\"", k)).join(",");
-        Ok(format!("window.ALL_CRATES = [{}];", krates).into_bytes())
-    })?;
-
-    if options.enable_index_page {
-        if let Some(index_page) = options.index_page.clone() {
-            let mut md_opts = options.clone();
-            md_opts.output = cx.dst.clone();
-            md_opts.external_html = (*cx.shared).layout.external_html.clone();
-
-            crate::markdown::render(&index_page, md_opts, cx.shared.edition())
-                .map_err(|e| Error::new(e, &index_page))?;
-        } else {
-            let shared = Rc::clone(&cx.shared);
-            let dst = cx.dst.join("index.html");
-            let page = layout::Page {
-                title: "Index of crates",
-                css_class: "mod",
-                root_path: "./",
-                static_root_path: shared.static_root_path.as_deref(),
-                description: "List of crates",
-                keywords: BASIC_KEYWORDS,
-                resource_suffix: &shared.resource_suffix,
-            };
-
-            let content = format!(
-                "<h1 class=\"fqn\">List of all crates</h1><ul class=\"all-items\">{}</ul>",
-                krates
-                    .iter()
-                    .map(|s| {
-                        format!(
-                            "<li><a href=\"{}index.html\">{}</a></li>",
-                            ensure_trailing_slash(s),
-                            s
-                        )
-                    })
-                    .collect::<String>()
-            );
-            let v = layout::render(&shared.layout, &page, "", content, &shared.style_files);
-            shared.fs.write(dst, v)?;
-        }
-    }
+        Some(format!(
+            r#""{}":{}"#,
+            &krate.name(cx.tcx()),
+            hierarchy
+                .to_json_string()
+                // All these `replace` calls are because we have to go through JS string for JSON content.
+                .replace('\\', r"\\")
+                .replace('\'', r"\'")
+                // We need to escape double quotes for the JSON.
+                .replace("\\\"", "\\\\\"")
+        ))
+    } else {
+        None
+    };
 
-    // Update the list of all implementors for traits
-    let dst = cx.dst.join("implementors");
+    // Collect the implementors of traits from other crates.
     let cache = cx.cache();
+    let mut all_implementors = Vec::new();
     for (&did, imps) in &cache.implementors {
         // Private modules can leak through to this phase of rustdoc, which
         // could contain implementations for otherwise private types. In some
@@ -420,16 +242,299 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             serde_json::to_string(&implementors).expect("failed serde conversion"),
         );
 
-        let mut mydst = dst.clone();
+        let mut path = PathBuf::new();
         for part in &remote_path[..remote_path.len() - 1] {
-            mydst.push(part.to_string());
+            path.push(part.to_string());
         }
-        cx.shared.ensure_dir(&mydst)?;
-        mydst.push(&format!("{}.{}.js", remote_item_type, remote_path[remote_path.len() - 1]));
+        path.push(&format!("{}.{}.js", remote_item_type, remote_path[remote_path.len() - 1]));
+        all_implementors.push((path, implementors));
+    }
+
+    let parts = CrateParts {
+        rustdoc_version: rustdoc_version().to_owned(),
+        crate_name: krate.name(cx.tcx()).to_string(),
+        resource_suffix: cx.shared.resource_suffix.clone(),
+        search_index,
+        sources,
+        implementors: all_implementors,
+    };
+
+    // With `--write-bundle`, the parts this crate wrote during the previous run are kept in the
+    // output directory: use them to remove the lines it no longer contributes.
+    let previous = if options.write_bundle {
+        read_previous_parts(&cx.dst, &[parts.crate_name.as_str()])
+    } else {
+        Vec::new()
+    };
+    if options.write_bundle {
+        write_bundle_parts(&cx.shared.fs, &cx.dst, &parts)?;
+    }
+
+    let emit_invocation_specific =
+        options.emit.is_empty() || options.emit.contains(&EmitType::InvocationSpecific);
+    let krates = write_crate_parts(
+        &cx.shared.fs,
+        &cx.dst,
+        &cx.shared.resource_suffix,
+        &[parts],
+        &previous,
+        emit_invocation_specific,
+    )?;
+
+    if options.enable_index_page {
+        write_index_page(
+            &cx.shared.fs,
+            &cx.dst,
+            options,
+            &cx.shared.layout,
+            cx.shared.edition(),
+            &krates,
+        )?;
+    }
 
-        let (mut all_implementors, _) =
-            try_err!(collect(&mydst, krate.name(cx.tcx()).as_str()), &mydst);
-        all_implementors.push(implementors);
+    Ok(())
+}
+
+/// Writes the `index.html` page of `dst`, listing the documented `krates` unless a custom index
+/// page was passed with `--index-page`.
+fn write_index_page(
+    fs: &DocFS,
+    dst: &Path,
+    options: &RenderOptions,
+    layout: &layout::Layout,
+    edition: Edition,
+    krates: &[String],
+) -> Result<(), Error> {
+    if let Some(index_page) = options.index_page.clone() {
+        let mut md_opts = options.clone();
+        md_opts.output = dst.to_path_buf();
+        md_opts.external_html = layout.external_html.clone();
+
+        crate::markdown::render(&index_page, md_opts, edition)
+            .map_err(|e| Error::new(e, &index_page))?;
+    } else {
+        let page = layout::Page {
+            title: "Index of crates",
+            css_class: "mod",
+            root_path: "./",
+            static_root_path: options.static_root_path.as_deref(),
+            description: "List of crates",
+            keywords: BASIC_KEYWORDS,
+            resource_suffix: &options.resource_suffix,
+        };
+
+        let content = format!(
+            "<h1 class=\"fqn\">List of all crates</h1><ul class=\"all-items\">{}</ul>",
+            krates
+                .iter()
+                .map(|s| {
+                    format!("<li><a href=\"{}index.html\">{}</a></li>", ensure_trailing_slash(s), s)
+                })
+                .collect::<String>()
+        );
+        let v = layout::render(layout, &page, "", content, &options.themes);
+        fs.write(dst.join("index.html"), v)?;
+    }
+    Ok(())
+}
+
+/// The directory of a documentation bundle in which the [`CrateParts`] of its crates are stored.
+///
+/// Crate names can't contain dashes, so this can't clash with the directory of a crate.
+const BUNDLE_PARTS_DIR: &str = "bundle-parts";
+
+/// The start and the end of the placeholders written in bundles in place of the documentation
+/// root of their dependencies. See [`bundle_extern_root`].
+const BUNDLE_EXTERN_ROOT_START: &str = "{{rustdoc-bundle:";
+const BUNDLE_EXTERN_ROOT_END: &str = "}}";
+
+/// Returns the location used in a bundle for the documentation of the dependency `krate`, when
+/// it isn't known otherwise.
+///
+/// The links to the dependency start with a placeholder, which `--link-bundle` replaces with the
+/// actual location of its documentation. The placeholder only contains characters that are left
+/// as is when escaping HTML, JSON and JS strings, so it can be found in every file of the bundle.
+pub(crate) fn bundle_extern_root(krate: &str) -> String {
+    format!("{}{}{}/", BUNDLE_EXTERN_ROOT_START, krate, BUNDLE_EXTERN_ROOT_END)
+}
+
+/// Replaces the placeholders written by [`bundle_extern_root`] in `contents`.
+///
+/// The links to the crates in `local_crates` are made relative to `root_path`, the path from the
+/// file to the root of the output directory. The other crates are linked to with the URL given
+/// with `--extern-html-root-url` if there is one, and as if they were documented in the output
+/// directory otherwise, so that linking their bundle later fixes the links.
+fn resolve_bundle_links(
+    contents: &str,
+    root_path: &str,
+    local_crates: &FxHashSet<String>,
+    options: &RenderOptions,
+) -> String {
+    // The placeholder is followed by a slash, which may have been escaped, so only replace the
+    // placeholder itself.
+    let local_root = if root_path.is_empty() { "." } else { root_path.trim_end_matches('/') };
+    let mut resolved = String::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(start) = rest.find(BUNDLE_EXTERN_ROOT_START) {
+        resolved.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let krate = &placeholder[BUNDLE_EXTERN_ROOT_START.len()..];
+        let Some(end) = krate.find(BUNDLE_EXTERN_ROOT_END) else {
+            rest = placeholder;
+            break;
+        };
+        let krate = &krate[..end];
+        match options.extern_html_root_urls.get(krate) {
+            Some(url) if !local_crates.contains(krate) => {
+                resolved.push_str(url.trim_end_matches('/'))
+            }
+            _ => resolved.push_str(local_root),
+        }
+        rest = &placeholder[BUNDLE_EXTERN_ROOT_START.len() + end + BUNDLE_EXTERN_ROOT_END.len()..];
+    }
+    resolved.push_str(rest);
+    resolved
+}
+
+fn rustdoc_version() -> &'static str {
+    rustc_interface::util::version_str!().unwrap_or("unknown version")
+}
+
+/// The contributions of a crate to the files shared by all the crates documented in the same
+/// output directory.
+///
+/// They are merged into the shared files right after rendering the crate. With
+/// `--write-bundle`, they are also saved in the output directory, so that `--link-bundle` can
+/// merge them into another output directory without documenting the crate again.
+#[derive(Serialize, Deserialize)]
+struct CrateParts {
+    /// The search index format changes between versions, so only parts generated by the
+    /// same version of rustdoc can be merged.
+    rustdoc_version: String,
+    crate_name: String,
+    resource_suffix: String,
+    /// The line of this crate in `search-index.js`.
+    search_index: String,
+    /// The line of this crate in `source-files.js`, if its sources were rendered.
+    sources: Option<String>,
+    /// The lines of this crate in the implementors files of traits from other crates, along with
+    /// the path of these files relative to the `implementors` directory.
+    implementors: Vec<(PathBuf, String)>,
+}
+
+/// Writes `parts` into the `bundle-parts` directory of `dst`.
+fn write_bundle_parts(fs: &DocFS, dst: &Path, parts: &CrateParts) -> Result<(), Error> {
+    let bundle_dir = dst.join(BUNDLE_PARTS_DIR);
+    try_err!(fs.create_dir_all(&bundle_dir), &bundle_dir);
+    let path = bundle_dir.join(format!("{}.json", parts.crate_name));
+    let contents = serde_json::to_string(&parts).expect("failed serde conversion");
+    fs.write(path, contents)
+}
+
+/// Reads the parts previously written into the `bundle-parts` directory of `dst` for `krates`.
+///
+/// They are only used to find the lines which are now stale, so missing or unreadable parts are
+/// ignored.
+fn read_previous_parts(dst: &Path, krates: &[&str]) -> Vec<CrateParts> {
+    krates
+        .iter()
+        .filter_map(|krate| {
+            let path = dst.join(BUNDLE_PARTS_DIR).join(format!("{}.json", krate));
+            serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+        })
+        .collect()
+}
+
+/// Merges `parts` into the search index, the crate list, the source files index and the
+/// implementors files of `dst`, replacing the data previously written there for the same crates.
+///
+/// `previous` are the parts these crates contributed before, if they are known: the lines they
+/// added to implementors files which `parts` don't mention anymore are removed, as well as their
+/// line in the source files index if their sources are no longer rendered.
+///
+/// Returns the names of all the crates now documented in `dst`.
+fn write_crate_parts(
+    fs: &DocFS,
+    dst: &Path,
+    resource_suffix: &str,
+    parts: &[CrateParts],
+    previous: &[CrateParts],
+    emit_invocation_specific: bool,
+) -> Result<Vec<String>, Error> {
+    let names = parts.iter().map(|p| p.crate_name.as_str()).collect::<Vec<_>>();
+
+    // InvocationSpecific resources should always be dynamic.
+    let write_invocation_specific = |p: &str, make_content: &dyn Fn() -> Result<Vec<u8>, Error>| {
+        let content = make_content()?;
+        if emit_invocation_specific {
+            let output_filename = static_files::suffix_path(p, resource_suffix);
+            fs.write(dst.join(output_filename), content)
+        } else {
+            Ok(())
+        }
+    };
+
+    if parts.iter().chain(previous).any(|p| p.sources.is_some()) {
+        let dst = dst.join(&format!("source-files{}.js", resource_suffix));
+        let make_sources = || {
+            let (mut all_sources, _krates) = try_err!(collect_json(&dst, &names), &dst);
+            all_sources.extend(parts.iter().filter_map(|p| p.sources.clone()));
+            all_sources.sort();
+            let mut v = String::from("var sourcesIndex = JSON.parse('{\\\n");
+            v.push_str(&all_sources.join(",\\\n"));
+            v.push_str("\\\n}');\ncreateSourceSidebar();\n");
+            Ok(v.into_bytes())
+        };
+        write_invocation_specific("source-files.js", &make_sources)?;
+    }
+
+    // Update the search index and crate list.
+    let search_index_dst = dst.join(&format!("search-index{}.js", resource_suffix));
+    let (mut all_indexes, mut krates) =
+        try_err!(collect_json(&search_index_dst, &names), &search_index_dst);
+    all_indexes.extend(parts.iter().map(|p| p.search_index.clone()));
+    krates.extend(names.iter().map(|name| name.to_string()));
+    krates.sort();
+
+    // Sort the indexes by crate so the file will be generated identically even
+    // with rustdoc running in parallel.
+    all_indexes.sort();
+    write_invocation_specific("search-index.js", &|| {
+        let mut v = String::from("var searchIndex = JSON.parse('{\\\n");
+        v.push_str(&all_indexes.join(",\\\n"));
+        v.push_str(
+            r#"\
+}');
+if (typeof window !== 'undefined' && window.initSearch) {window.initSearch(searchIndex)};
+if (typeof exports !== 'undefined') {exports.searchIndex = searchIndex};
+"#,
+        );
+        Ok(v.into_bytes())
+    })?;
+
+    write_invocation_specific("crates.js", &|| {
+        let krates = krates.iter().map(|k| format!("\"{}\"", k)).join(",");
+        Ok(format!("window.ALL_CRATES = [{}];", krates).into_bytes())
+    })?;
+
+    // Update the list of all implementors for traits
+    let mut implementors: FxHashMap<&Path, Vec<String>> = FxHashMap::default();
+    for (path, _) in previous.iter().flat_map(|p| &p.implementors) {
+        // The crate doesn't implement this trait anymore: rewriting the file without its line
+        // is enough to remove it.
+        implementors.entry(path.as_path()).or_default();
+    }
+    for (path, line) in parts.iter().flat_map(|p| &p.implementors) {
+        implementors.entry(path.as_path()).or_default().push(line.clone());
+    }
+    for (path, lines) in implementors {
+        let mydst = dst.join("implementors").join(path);
+        if let Some(parent) = mydst.parent() {
+            try_err!(fs.create_dir_all(parent), parent);
+        }
+
+        let (mut all_implementors, _) = try_err!(collect(&mydst, &names), &mydst);
+        all_implementors.extend(lines);
         // Sort the implementors by crate so the file will be generated
         // identically even with rustdoc running in parallel.
         all_implementors.sort();
@@ -445,7 +550,261 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
              }",
         );
         v.push_str("})()");
-        cx.shared.fs.write(mydst, v)?;
+        fs.write(mydst, v)?;
+    }
+
+    Ok(krates)
+}
+
+/// Combines the documentation bundles written with `--write-bundle` into `options.output`.
+///
+/// The pages of each crate replace the ones previously linked for the same crate, with the links
+/// to its dependencies resolved (see [`resolve_bundle_links`]). The search index, the crate list,
+/// the source files index and the implementors files are merged with the ones already present in
+/// the output directory, and the crate index is regenerated. A bundle can therefore be linked
+/// again after its crate was documented anew, without touching the other crates.
+pub(crate) fn link_bundles(options: &RenderOptions, edition: Edition) -> Result<(), Error> {
+    let dst = &options.output;
+    try_err!(fs::create_dir_all(dst), dst);
+    let lock_file = dst.join(".lock");
+    let _lock = try_err!(flock::Lock::new(&lock_file, true, true, true), &lock_file);
+
+    let mut all_parts: Vec<CrateParts> = Vec::new();
+    for bundle in &options.link_bundles {
+        let parts_dir = bundle.join(BUNDLE_PARTS_DIR);
+        if !parts_dir.is_dir() {
+            return Err(Error::new(
+                "not a documentation bundle, was it generated with `--write-bundle`?",
+                bundle,
+            ));
+        }
+        for entry in try_err!(fs::read_dir(&parts_dir), &parts_dir) {
+            let path = try_err!(entry, &parts_dir).path();
+            let parts: CrateParts =
+                try_err!(serde_json::from_str(&try_err!(fs::read_to_string(&path), &path)), &path);
+            if parts.rustdoc_version != rustdoc_version() {
+                return Err(Error::new(
+                    format!(
+                        "bundle was written by rustdoc {}, which is incompatible with rustdoc {}",
+                        parts.rustdoc_version,
+                        rustdoc_version(),
+                    ),
+                    &path,
+                ));
+            }
+            if let Some(first) = all_parts.first() {
+                if parts.resource_suffix != first.resource_suffix {
+                    return Err(Error::new(
+                        format!(
+                            "bundle was written with resource suffix `{}` instead of `{}`",
+                            parts.resource_suffix, first.resource_suffix,
+                        ),
+                        &path,
+                    ));
+                }
+            }
+            if all_parts.iter().any(|p| p.crate_name == parts.crate_name) {
+                return Err(Error::new(
+                    format!("crate `{}` is part of several bundles", parts.crate_name),
+                    &path,
+                ));
+            }
+            all_parts.push(parts);
+        }
+    }
+
+    let Some(resource_suffix) = all_parts.first().map(|p| p.resource_suffix.clone()) else {
+        return Ok(());
+    };
+    let names = all_parts.iter().map(|p| p.crate_name.as_str()).collect::<Vec<_>>();
+    let previous = read_previous_parts(dst, &names);
+
+    // The crates whose documentation is in the output directory once the bundles are linked.
+    let search_index_dst = dst.join(&format!("search-index{}.js", resource_suffix));
+    let (_, mut local_crates) = try_err!(collect_json(&search_index_dst, &[]), &search_index_dst);
+    local_crates.extend(names.iter().map(|name| name.to_string()));
+    let local_crates = local_crates.into_iter().collect::<FxHashSet<_>>();
+
+    // Remove the pages previously linked for these crates, some of them may not exist anymore.
+    for name in &names {
+        for dir in [dst.join(name), dst.join("src").join(name)] {
+            if dir.is_dir() {
+                try_err!(fs::remove_dir_all(&dir), &dir);
+            }
+        }
+    }
+
+    // The merged files are regenerated below, everything else is copied. Toolchain files have
+    // their hash in their name, and the other files only depend on the options shared by all
+    // bundles, so overwriting them is fine.
+    let merged = ["search-index.js", "crates.js", "source-files.js"]
+        .into_iter()
+        .map(|f| static_files::suffix_path(f, &resource_suffix).into_os_string())
+        .chain(
+            [".lock", BUNDLE_PARTS_DIR, "implementors", "index.html"]
+                .into_iter()
+                .map(OsString::from),
+        )
+        .collect::<FxHashSet<_>>();
+    let resolve = |contents: &str, root_path: &str| {
+        resolve_bundle_links(contents, root_path, &local_crates, options)
+    };
+    let mut has_index_page = false;
+    for bundle in &options.link_bundles {
+        for entry in try_err!(fs::read_dir(bundle), bundle) {
+            let entry = try_err!(entry, bundle);
+            has_index_page |= entry.file_name() == "index.html";
+            if !merged.contains(&entry.file_name()) {
+                copy_bundle_files(&entry.path(), &dst.join(entry.file_name()), "", &resolve)?;
+            }
+        }
+    }
+
+    // The implementors are written in the implementors files, which are loaded by the pages of
+    // the traits: their links are relative to the root of the output directory.
+    for parts in &mut all_parts {
+        for (_, line) in &mut parts.implementors {
+            *line = resolve(line.as_str(), "");
+        }
+    }
+
+    let (sender, _receiver) = channel();
+    let mut docfs = DocFS::new(sender);
+    docfs.set_sync_only(true);
+    let krates = write_crate_parts(&docfs, dst, &resource_suffix, &all_parts, &previous, true)?;
+    for parts in &all_parts {
+        write_bundle_parts(&docfs, dst, parts)?;
+    }
+
+    if options.enable_index_page || has_index_page {
+        let layout = layout::Layout {
+            logo: String::new(),
+            favicon: String::new(),
+            external_html: options.external_html.clone(),
+            default_settings: options.default_settings.clone(),
+            krate: String::new(),
+            css_file_extension: options.extension_css.clone(),
+            scrape_examples_extension: false,
+        };
+        write_index_page(&docfs, dst, options, &layout, edition, &krates)?;
+    }
+    Ok(())
+}
+
+/// Copies the file or directory `src` of a bundle to `dst`, resolving the links to the
+/// dependencies of the crate in its pages and scripts.
+///
+/// `root_path` is the path from `src` to the root of the bundle.
+fn copy_bundle_files(
+    src: &Path,
+    dst: &Path,
+    root_path: &str,
+    resolve: &dyn Fn(&str, &str) -> String,
+) -> Result<(), Error> {
+    if src.is_dir() {
+        try_err!(fs::create_dir_all(dst), dst);
+        let root_path = format!("../{}", root_path);
+        for entry in try_err!(fs::read_dir(src), src) {
+            let entry = try_err!(entry, src);
+            copy_bundle_files(&entry.path(), &dst.join(entry.file_name()), &root_path, resolve)?;
+        }
+    } else if matches!(src.extension().and_then(|e| e.to_str()), Some("html" | "js")) {
+        let contents = try_err!(fs::read_to_string(src), src);
+        try_err!(fs::write(dst, resolve(&contents, root_path)), dst);
+    } else {
+        try_err!(fs::copy(src, dst), src);
     }
     Ok(())
 }
+
+/// Read a file and return all lines that match the `"{crate}":{data},` format,
+/// except the ones of the crates in `skip`, and return a tuple
+/// `(Vec<DataString>, Vec<CrateNameString>)`.
+///
+/// This forms the payload of files that look like this:
+///
+/// ```javascript
+/// var data = {
+/// "{crate1}":{data},
+/// "{crate2}":{data}
+/// };
+/// use_data(data);
+/// ```
+///
+/// The file needs to be formatted so that *only crate data lines start with `"`*.
+fn collect(path: &Path, skip: &[&str]) -> io::Result<(Vec<String>, Vec<String>)> {
+    let mut ret = Vec::new();
+    let mut krates = Vec::new();
+
+    if path.exists() {
+        let prefixes = skip.iter().map(|krate| format!("\"{}\"", krate)).collect::<Vec<_>>();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if !line.starts_with('"') {
+                continue;
+            }
+            if prefixes.iter().any(|prefix| line.starts_with(prefix)) {
+                continue;
+            }
+            if line.ends_with(',') {
+                ret.push(line[..line.len() - 1].to_string());
+            } else {
+                // No comma (it's the case for the last added crate line)
+                ret.push(line.to_string());
+            }
+            krates.push(
+                line.split('"')
+                    .find(|s| !s.is_empty())
+                    .map(|s| s.to_owned())
+                    .unwrap_or_else(String::new),
+            );
+        }
+    }
+    Ok((ret, krates))
+}
+
+/// Read a file and return all lines that match the <code>"{crate}":{data},\</code> format,
+/// except the ones of the crates in `skip`, and return a tuple
+/// `(Vec<DataString>, Vec<CrateNameString>)`.
+///
+/// This forms the payload of files that look like this:
+///
+/// ```javascript
+/// var data = JSON.parse('{\
+/// "{crate1}":{data},\
+/// "{crate2}":{data}\
+/// }');
+/// use_data(data);
+/// ```
+///
+/// The file needs to be formatted so that *only crate data lines start with `"`*.
+fn collect_json(path: &Path, skip: &[&str]) -> io::Result<(Vec<String>, Vec<String>)> {
+    let mut ret = Vec::new();
+    let mut krates = Vec::new();
+
+    if path.exists() {
+        let prefixes = skip.iter().map(|krate| format!("\"{}\"", krate)).collect::<Vec<_>>();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if !line.starts_with('"') {
+                continue;
+            }
+            if prefixes.iter().any(|prefix| line.starts_with(prefix)) {
+                continue;
+            }
+            if line.ends_with(",\\") {
+                ret.push(line[..line.len() - 2].to_string());
+            } else {
+                // Ends with "\\" (it's the case for the last added crate line)
+                ret.push(line[..line.len() - 1].to_string());
+            }
+            krates.push(
+                line.split('"')
+                    .find(|s| !s.is_empty())
+                    .map(|s| s.to_owned())
+                    .unwrap_or_else(String::new),
+            );
+        }
+    }
+    Ok((ret, krates))
+}
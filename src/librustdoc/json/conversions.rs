@@ -588,7 +588,8 @@ fn from_tcx(
 impl FromWithTcx<clean::Impl> for Impl {
     fn from_tcx(impl_: clean::Impl, tcx: TyCtxt<'_>) -> Self {
         let provided_trait_methods = impl_.provided_trait_methods(tcx);
-        let clean::Impl { unsafety, generics, trait_, for_, items, polarity, kind } = impl_;
+        let clean::Impl { unsafety, generics, trait_, for_, items, polarity, kind, provenance } =
+            impl_;
         // FIXME: use something like ImplKind in JSON?
         let (synthetic, blanket_impl) = match kind {
             clean::ImplKind::Normal | clean::ImplKind::FakeVaradic => (false, None),
@@ -612,6 +613,21 @@ fn from_tcx(impl_: clean::Impl, tcx: TyCtxt<'_>) -> Self {
             negative: negative_polarity,
             synthetic,
             blanket_impl: blanket_impl.map(|x| x.into_tcx(tcx)),
+            provenance: provenance.map(|x| x.into_tcx(tcx)),
+        }
+    }
+}
+
+impl FromWithTcx<clean::ImplProvenance> for ImplProvenance {
+    fn from_tcx(provenance: clean::ImplProvenance, tcx: TyCtxt<'_>) -> Self {
+        match provenance {
+            clean::ImplProvenance::Derive(macro_name) => {
+                ImplProvenance::Derive { macro_name: macro_name.to_string() }
+            }
+            clean::ImplProvenance::Blanket { krate, bounds } => ImplProvenance::Blanket {
+                crate_id: krate.as_u32(),
+                bounds: bounds.into_iter().map(|x| x.into_tcx(tcx)).collect(),
+            },
         }
     }
 }
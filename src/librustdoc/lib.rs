@@ -521,6 +521,21 @@ fn opts() -> Vec<RustcOptGroup> {
                 "calculate percentage of public items with documentation",
             )
         }),
+        unstable("coverage-details", |o| {
+            o.optflagmulti(
+                "",
+                "coverage-details",
+                "list items missing documentation or examples",
+            )
+        }),
+        unstable("coverage-threshold", |o| {
+            o.optmulti(
+                "",
+                "coverage-threshold",
+                "fail if documentation coverage is below a threshold",
+                "[PATH=]PERCENT",
+            )
+        }),
         unstable("enable-per-target-ignores", |o| {
             o.optflagmulti(
                 "",
@@ -563,6 +578,23 @@ fn opts() -> Vec<RustcOptGroup> {
                 "[unversioned-shared-resources,toolchain-shared-resources,invocation-specific]",
             )
         }),
+        unstable("write-bundle", |o| {
+            o.optflagmulti(
+                "",
+                "write-bundle",
+                "Write a documentation bundle which can be combined with others using \
+                 --link-bundle",
+            )
+        }),
+        unstable("link-bundle", |o| {
+            o.optmulti(
+                "",
+                "link-bundle",
+                "Combine the documentation bundle in DIR into the output directory instead of \
+                 documenting a crate",
+                "DIR",
+            )
+        }),
         unstable("no-run", |o| {
             o.optflagmulti("", "no-run", "Compile doctests without running them")
         }),
@@ -735,6 +767,14 @@ fn main_args(at_args: &[String]) -> MainResult {
         &options.unstable_opts,
     );
 
+    if !render_options.link_bundles.is_empty() {
+        return wrap_return(
+            &diag,
+            html::render::link_bundles(&render_options, options.edition)
+                .map_err(|e| e.to_string()),
+        );
+    }
+
     match (options.should_test, options.markdown_input()) {
         (true, true) => return wrap_return(&diag, markdown::test(options)),
         (true, false) => return doctest::run(options),
@@ -759,6 +799,7 @@ fn main_args(at_args: &[String]) -> MainResult {
     // need to move these items separately because we lose them by the time the closure is called,
     // but we can't create the Handler ahead of time because it's not Send
     let show_coverage = options.show_coverage;
+    let coverage_options = options.coverage_options.clone();
     let run_check = options.run_check;
 
     // First, parse the crate and extract all relevant information.
@@ -827,6 +868,7 @@ fn main_args(at_args: &[String]) -> MainResult {
                         resolver,
                         resolver_caches,
                         show_coverage,
+                        coverage_options,
                         render_options,
                         output_format,
                     )
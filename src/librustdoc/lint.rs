@@ -174,6 +174,18 @@ macro_rules! declare_rustdoc_lint {
    "codeblock could not be parsed as valid Rust or is empty"
 }
 
+declare_rustdoc_lint! {
+    /// The `unresolved_example_paths` lint detects paths to items of the
+    /// documented crate which cannot be resolved, inside code examples that
+    /// are never compiled (`ignore` and `text` code blocks). This is a
+    /// `rustdoc` only lint, see the documentation in the [rustdoc book].
+    ///
+    /// [rustdoc book]: ../../../rustdoc/lints.html#unresolved_example_paths
+    UNRESOLVED_EXAMPLE_PATHS,
+    Allow,
+    "detects unresolved paths in code examples which aren't compiled"
+}
+
 pub(crate) static RUSTDOC_LINTS: Lazy<Vec<&'static Lint>> = Lazy::new(|| {
     vec![
         BROKEN_INTRA_DOC_LINKS,
@@ -185,6 +197,7 @@ macro_rules! declare_rustdoc_lint {
         INVALID_HTML_TAGS,
         BARE_URLS,
         MISSING_CRATE_LEVEL_DOCS,
+        UNRESOLVED_EXAMPLE_PATHS,
     ]
 });
 
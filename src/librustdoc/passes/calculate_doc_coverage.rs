@@ -6,9 +6,11 @@
 use crate::passes::Pass;
 use crate::visit::DocVisitor;
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
 use rustc_lint::builtin::MISSING_DOCS;
 use rustc_middle::lint::LintLevelSource;
 use rustc_middle::ty::DefIdTree;
+use rustc_session::getopts;
 use rustc_session::lint;
 use rustc_span::FileName;
 use serde::Serialize;
@@ -22,11 +24,90 @@
     description: "counts the number of items with and without documentation",
 };
 
+/// A minimum documentation percentage passed with `--coverage-threshold`.
+#[derive(Clone, Debug)]
+pub(crate) struct CoverageThreshold {
+    /// The module the threshold applies to, relative to the crate root (e.g. `foo::bar`).
+    /// `None` means the threshold applies to the whole crate.
+    module: Option<String>,
+    percentage: f64,
+}
+
+impl CoverageThreshold {
+    fn parse(arg: &str) -> Result<Self, String> {
+        let (module, percentage) = match arg.rsplit_once('=') {
+            Some((module, percentage)) => (Some(module.trim().to_string()), percentage),
+            None => (None, arg),
+        };
+        let percentage = match percentage.trim().parse::<f64>() {
+            Ok(percentage) if (0.0..=100.0).contains(&percentage) => percentage,
+            _ => {
+                return Err(format!(
+                    "`--coverage-threshold` expects `[PATH=]PERCENT` with a percentage between \
+                     0 and 100, found `{}`",
+                    arg
+                ));
+            }
+        };
+        Ok(CoverageThreshold { module, percentage })
+    }
+
+    fn applies_to(&self, module: &str) -> bool {
+        match &self.module {
+            None => true,
+            Some(path) => {
+                module == path
+                    || (module.starts_with(path.as_str()) && module[path.len()..].starts_with("::"))
+            }
+        }
+    }
+}
+
+/// Options controlling the output of the `--show-coverage` flag.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CoverageOptions {
+    /// Whether to list every item missing documentation or a code example (`--coverage-details`).
+    pub(crate) details: bool,
+    /// Minimum percentages which make rustdoc fail when they aren't reached.
+    pub(crate) thresholds: Vec<CoverageThreshold>,
+}
+
+impl CoverageOptions {
+    pub(crate) fn new(
+        matches: &getopts::Matches,
+        diag: &rustc_errors::Handler,
+        show_coverage: bool,
+    ) -> Result<Self, i32> {
+        let details = matches.opt_present("coverage-details");
+        let thresholds = matches
+            .opt_strs("coverage-threshold")
+            .iter()
+            .map(|arg| CoverageThreshold::parse(arg))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                diag.err(&e);
+                1
+            })?;
+        if !show_coverage && (details || !thresholds.is_empty()) {
+            diag.err("`--coverage-details` and `--coverage-threshold` require `--show-coverage`");
+            return Err(1);
+        }
+        Ok(CoverageOptions { details, thresholds })
+    }
+}
+
 fn calculate_doc_coverage(krate: clean::Crate, ctx: &mut DocContext<'_>) -> clean::Crate {
-    let mut calc = CoverageCalculator { items: Default::default(), ctx };
+    let mut calc = CoverageCalculator {
+        items: Default::default(),
+        modules: Default::default(),
+        categories: Default::default(),
+        missing: Vec::new(),
+        ctx,
+    };
     calc.visit_crate(&krate);
 
     calc.print_results();
+    calc.check_thresholds();
 
     krate
 }
@@ -101,8 +182,56 @@ fn add_assign(&mut self, rhs: Self) {
     }
 }
 
+/// The kinds of items which are reported separately with `--coverage-details`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+enum CoverageCategory {
+    TraitMethod,
+    Variant,
+    Other,
+}
+
+impl CoverageCategory {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CoverageCategory::TraitMethod => "Trait methods",
+            CoverageCategory::Variant => "Enum variants",
+            CoverageCategory::Other => "Other items",
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct ItemLocation {
+    filename: String,
+    line: usize,
+    column: usize,
+}
+
+/// An item which is missing either its documentation or a code example.
+#[derive(Serialize, Debug)]
+struct MissingItem {
+    path: String,
+    kind: &'static str,
+    category: CoverageCategory,
+    span: ItemLocation,
+    has_docs: bool,
+    has_example: bool,
+}
+
+#[derive(Serialize)]
+struct DetailedReport<'a> {
+    files: BTreeMap<String, &'a ItemCount>,
+    categories: &'a BTreeMap<CoverageCategory, ItemCount>,
+    missing: &'a [MissingItem],
+}
+
 struct CoverageCalculator<'a, 'b> {
     items: BTreeMap<FileName, ItemCount>,
+    /// Counts for the items directly contained in each module, keyed by the module path.
+    modules: BTreeMap<String, ItemCount>,
+    categories: BTreeMap<CoverageCategory, ItemCount>,
+    missing: Vec<MissingItem>,
     ctx: &'a mut DocContext<'b>,
 }
 
@@ -118,16 +247,45 @@ fn limit_filename_len(filename: String) -> String {
 
 impl<'a, 'b> CoverageCalculator<'a, 'b> {
     fn to_json(&self) -> String {
-        serde_json::to_string(
-            &self
-                .items
-                .iter()
-                .map(|(k, v)| (k.prefer_local().to_string(), v))
-                .collect::<BTreeMap<String, &ItemCount>>(),
-        )
+        let files = self
+            .items
+            .iter()
+            .map(|(k, v)| (k.prefer_local().to_string(), v))
+            .collect::<BTreeMap<String, &ItemCount>>();
+        if self.ctx.coverage_options.details {
+            serde_json::to_string(&DetailedReport {
+                files,
+                categories: &self.categories,
+                missing: &self.missing,
+            })
+        } else {
+            serde_json::to_string(&files)
+        }
         .expect("failed to convert JSON data to string")
     }
 
+    fn check_thresholds(&self) {
+        for threshold in &self.ctx.coverage_options.thresholds {
+            let mut count = ItemCount::default();
+            for (_, &module_count) in
+                self.modules.iter().filter(|(module, _)| threshold.applies_to(module))
+            {
+                count += module_count;
+            }
+            let percentage = count.percentage().unwrap_or(100.0);
+            if percentage < threshold.percentage {
+                let target = match &threshold.module {
+                    Some(module) => format!("module `{}`", module),
+                    None => "this crate".to_string(),
+                };
+                self.ctx.sess().err(&format!(
+                    "documentation coverage for {} is {:.1}%, below the required {:.1}%",
+                    target, percentage, threshold.percentage,
+                ));
+            }
+        }
+    }
+
     fn print_results(&self) {
         let output_format = self.ctx.output_format;
         if output_format.is_json() {
@@ -180,6 +338,65 @@ fn print_table_record(
             total.examples_percentage().unwrap_or(0.0),
         );
         print_table_line();
+
+        if !self.ctx.coverage_options.details {
+            return;
+        }
+
+        println!();
+        print_table_line();
+        println!(
+            "| {:<35} | {:>10} | {:>10} | {:>10} | {:>10} |",
+            "Category", "Documented", "Percentage", "Examples", "Percentage",
+        );
+        print_table_line();
+        for (category, &count) in &self.categories {
+            if let Some(percentage) = count.percentage() {
+                print_table_record(
+                    category.as_str(),
+                    count,
+                    percentage,
+                    count.examples_percentage().unwrap_or(0.),
+                );
+            }
+        }
+        print_table_line();
+
+        if !self.missing.is_empty() {
+            println!();
+            for item in &self.missing {
+                let missing = match (item.has_docs, item.has_example) {
+                    (false, false) => "missing documentation and example",
+                    (false, true) => "missing documentation",
+                    (true, _) => "missing example",
+                };
+                println!(
+                    "{}:{}:{}: {} `{}`: {}",
+                    item.span.filename,
+                    item.span.line,
+                    item.span.column,
+                    item.kind,
+                    item.path,
+                    missing,
+                );
+            }
+        }
+    }
+
+    fn category(&self, i: &clean::Item) -> CoverageCategory {
+        if i.is_variant() {
+            return CoverageCategory::Variant;
+        }
+        let in_trait = i
+            .item_id
+            .as_def_id()
+            .and_then(|def_id| self.ctx.tcx.opt_parent(def_id))
+            .map_or(false, |parent| self.ctx.tcx.def_kind(parent) == DefKind::Trait);
+        if in_trait && (i.is_ty_method() || i.is_method()) {
+            CoverageCategory::TraitMethod
+        } else {
+            CoverageCategory::Other
+        }
     }
 }
 
@@ -263,12 +480,41 @@ fn visit_item(&mut self, i: &clean::Item) {
                 if let Some(span) = i.span(self.ctx.tcx) {
                     let filename = span.filename(self.ctx.sess());
                     debug!("counting {:?} {:?} in {:?}", i.type_(), i.name, filename);
-                    self.items.entry(filename).or_default().count_item(
+                    let should_have_doc_example = should_have_doc_example(self.ctx, i);
+                    let mut count = ItemCount::default();
+                    count.count_item(
                         has_docs,
                         has_doc_example,
-                        should_have_doc_example(self.ctx, i),
+                        should_have_doc_example,
                         should_have_docs,
                     );
+                    *self.items.entry(filename.clone()).or_default() += count;
+
+                    let tcx = self.ctx.tcx;
+                    let def_id = i.item_id.expect_def_id().expect_local();
+                    let module = tcx.parent_module_from_def_id(def_id);
+                    *self.modules.entry(tcx.def_path_str(module.to_def_id())).or_default() += count;
+                    let category = self.category(i);
+                    *self.categories.entry(category).or_default() += count;
+
+                    if self.ctx.coverage_options.details
+                        && ((should_have_docs && !has_docs)
+                            || (should_have_doc_example && !has_doc_example))
+                    {
+                        let lo = span.lo(self.ctx.sess());
+                        self.missing.push(MissingItem {
+                            path: tcx.def_path_str(def_id.to_def_id()),
+                            kind: i.type_().as_str(),
+                            category,
+                            span: ItemLocation {
+                                filename: filename.prefer_local().to_string(),
+                                line: lo.line,
+                                column: lo.col.0 + 1,
+                            },
+                            has_docs,
+                            has_example: has_doc_example,
+                        });
+                    }
                 }
             }
         }
@@ -0,0 +1,289 @@
+//! Resolves paths to the documented crate used inside code blocks which are never compiled
+//! (`ignore` and `text` blocks), so that examples referencing renamed or removed items are
+//! detected.
+use rustc_ast::token::{self, Delimiter, TokenKind};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast::NodeId;
+use rustc_hir::def::{DefKind, Namespace::*, Res};
+use rustc_hir::def_id::{DefId, CRATE_DEF_ID, LOCAL_CRATE};
+use rustc_parse::parse_stream_from_source_str;
+use rustc_resolve::ParentScope;
+use rustc_session::parse::ParseSess;
+use rustc_span::symbol::{kw, Symbol};
+use rustc_span::{BytePos, FileName, Span};
+
+use std::ops::Range;
+
+use crate::clean;
+use crate::core::DocContext;
+use crate::html::markdown::{self, RustCodeBlock};
+use crate::passes::{source_span_for_markdown_range, Pass};
+use crate::visit::DocVisitor;
+
+pub(crate) const CHECK_CODE_BLOCK_PATHS: Pass = Pass {
+    name: "check-code-block-paths",
+    run: check_code_block_paths,
+    description: "resolves paths to the current crate inside code blocks which aren't compiled",
+};
+
+pub(crate) fn check_code_block_paths(krate: clean::Crate, cx: &mut DocContext<'_>) -> clean::Crate {
+    let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+    PathChecker { cx, crate_name }.visit_crate(&krate);
+    krate
+}
+
+/// A path found in a code block, starting with the name of the documented crate.
+struct CodeBlockPath {
+    segments: Vec<Symbol>,
+    /// The range of the path, relative to the start of the code block.
+    range: Range<usize>,
+}
+
+struct PathChecker<'a, 'tcx> {
+    cx: &'a DocContext<'tcx>,
+    crate_name: Symbol,
+}
+
+impl<'a, 'tcx> PathChecker<'a, 'tcx> {
+    fn check_paths(&self, item: &clean::Item, dox: &str, code_block: RustCodeBlock) {
+        let Some(local_id) = item.item_id.as_def_id().and_then(|x| x.as_local()) else {
+            // Only the docs written in this crate can be fixed by the user.
+            return;
+        };
+
+        let sess = ParseSess::with_silent_emitter(None);
+        let source = dox[code_block.code.clone()].to_owned();
+        let Ok(stream) = rustc_driver::catch_fatal_errors(|| {
+            parse_stream_from_source_str(
+                FileName::Custom(String::from("doctest")),
+                source,
+                &sess,
+                None,
+            )
+        }) else {
+            return;
+        };
+        if sess.span_diagnostic.has_errors().is_some() {
+            // Not Rust code (this is common for `text` blocks), there is nothing to resolve.
+            return;
+        }
+
+        let start_pos = sess.source_map().files()[0].start_pos;
+        let mut paths = Vec::new();
+        self.collect_paths(&stream, start_pos, &mut paths);
+
+        let hir_id = self.cx.tcx.hir().local_def_id_to_hir_id(local_id);
+        for path in paths {
+            if path.segments.len() < 2 || self.resolves(&path.segments) {
+                continue;
+            }
+
+            let path_str = path.segments.iter().map(|s| s.as_str()).collect::<Vec<_>>().join("::");
+            let md_range = (code_block.code.start + path.range.start)
+                ..(code_block.code.start + path.range.end);
+            let (sp, precise_span) =
+                match source_span_for_markdown_range(self.cx.tcx, dox, &md_range, &item.attrs) {
+                    Some(sp) => (sp, true),
+                    None => (item.attr_span(self.cx.tcx), false),
+                };
+
+            self.cx.tcx.struct_span_lint_hir(
+                crate::lint::UNRESOLVED_EXAMPLE_PATHS,
+                hir_id,
+                sp,
+                format!("unresolved path `{}` in code example", path_str),
+                |lint| {
+                    if !precise_span {
+                        lint.note(&format!("the code example refers to `{}`", path_str));
+                    }
+                    lint.note("this code block isn't compiled, so the path was never checked")
+                        .help("the item may have been renamed or removed")
+                },
+            );
+        }
+    }
+
+    /// Walks `stream`, collecting every path which starts with the name of the current crate.
+    /// `use` trees are flattened, so `krate::{a, b::c}` produces both `krate::a` and
+    /// `krate::b::c`.
+    fn collect_paths(
+        &self,
+        stream: &TokenStream,
+        start_pos: BytePos,
+        out: &mut Vec<CodeBlockPath>,
+    ) {
+        let trees: Vec<&TokenTree> = stream.trees().collect();
+        let mut i = 0;
+        while i < trees.len() {
+            match trees[i] {
+                TokenTree::Token(tok, _)
+                    if tok.is_ident_named(self.crate_name)
+                        && !matches!(
+                            i.checked_sub(1).map(|prev| trees[prev]),
+                            Some(TokenTree::Token(
+                                token::Token { kind: TokenKind::ModSep | TokenKind::Dot, .. },
+                                _
+                            ))
+                        )
+                        && is_mod_sep(trees.get(i + 1).copied()) =>
+                {
+                    i = self.collect_path(&trees, i, Vec::new(), None, start_pos, out);
+                }
+                TokenTree::Delimited(_, _, inner) => {
+                    self.collect_paths(inner, start_pos, out);
+                    i += 1;
+                }
+                _ => i += 1,
+            }
+        }
+    }
+
+    /// Collects the path starting at `trees[start]`, prefixed by `prefix` (which is non-empty
+    /// inside `use` groups), and returns the index of the first token after it.
+    fn collect_path(
+        &self,
+        trees: &[&TokenTree],
+        start: usize,
+        mut segments: Vec<Symbol>,
+        mut lo: Option<Span>,
+        start_pos: BytePos,
+        out: &mut Vec<CodeBlockPath>,
+    ) -> usize {
+        let mut i = start;
+        let mut hi = lo;
+        loop {
+            let Some(TokenTree::Token(tok, _)) = trees.get(i).copied() else { break };
+            let Some((ident, _)) = tok.ident() else { break };
+            if ident.name == kw::SelfLower && !segments.is_empty() {
+                // `use krate::foo::{self}`
+                break;
+            }
+            segments.push(ident.name);
+            lo.get_or_insert(tok.span);
+            hi = Some(tok.span);
+            i += 1;
+
+            if !is_mod_sep(trees.get(i).copied()) {
+                break;
+            }
+            i += 1;
+
+            match trees.get(i).copied() {
+                Some(TokenTree::Delimited(_, Delimiter::Brace, inner)) => {
+                    let inner: Vec<&TokenTree> = inner.trees().collect();
+                    for group in inner.split(|tree| {
+                        matches!(
+                            tree,
+                            TokenTree::Token(token::Token { kind: TokenKind::Comma, .. }, _)
+                        )
+                    }) {
+                        if !group.is_empty() {
+                            self.collect_path(group, 0, segments.clone(), lo, start_pos, out);
+                        }
+                    }
+                    return i + 1;
+                }
+                Some(TokenTree::Token(tok, _)) if tok.ident().is_some() => {}
+                // A glob import or turbofish ends the path.
+                _ => break,
+            }
+        }
+
+        if let (Some(lo), Some(hi)) = (lo, hi) {
+            out.push(CodeBlockPath {
+                segments,
+                range: (lo.lo() - start_pos).0 as usize..(hi.hi() - start_pos).0 as usize,
+            });
+        }
+        i
+    }
+
+    fn resolve(&self, segments: &[Symbol], ns: rustc_hir::def::Namespace) -> Option<Res<NodeId>> {
+        let path_str = std::iter::once(kw::Crate)
+            .chain(segments[1..].iter().copied())
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        self.cx.enter_resolver(|resolver| {
+            let parent_scope =
+                ParentScope::module(resolver.expect_module(CRATE_DEF_ID.to_def_id()), resolver);
+            resolver.resolve_rustdoc_path(&path_str, ns, parent_scope)
+        })
+    }
+
+    fn resolves(&self, segments: &[Symbol]) -> bool {
+        if [TypeNS, ValueNS, MacroNS].into_iter().any(|ns| self.resolve(segments, ns).is_some()) {
+            return true;
+        }
+
+        // The resolver doesn't know about associated items, look them up on the parent type.
+        let (&name, parent) = segments.split_last().unwrap();
+        if parent.len() < 2 {
+            return false;
+        }
+        match self.resolve(parent, TypeNS) {
+            Some(Res::Def(kind, def_id)) => self.has_associated_item(kind, def_id, name),
+            _ => false,
+        }
+    }
+
+    fn has_associated_item(&self, kind: DefKind, def_id: DefId, name: Symbol) -> bool {
+        let tcx = self.cx.tcx;
+        let has_item = |container: DefId| {
+            tcx.associated_items(container).in_definition_order().any(|item| item.name == name)
+        };
+        match kind {
+            DefKind::Trait => has_item(def_id),
+            DefKind::Struct | DefKind::Enum | DefKind::Union => {
+                tcx.inherent_impls(def_id).iter().any(|&impl_id| has_item(impl_id))
+                    // Methods may come from any trait implemented by the type. Foreign types
+                    // can't be checked cheaply, so assume the path is fine.
+                    || !def_id.is_local()
+                    || self.has_trait_impl_item(def_id, name)
+            }
+            // Type aliases can't be checked without normalizing them, so assume the path is fine.
+            DefKind::TyAlias | DefKind::ForeignTy => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if a trait implemented for the local ADT `def_id` has an associated item
+    /// called `name`. By the orphan rules, every impl for a local type is local too, except for
+    /// blanket impls, which aren't considered.
+    fn has_trait_impl_item(&self, def_id: DefId, name: Symbol) -> bool {
+        let tcx = self.cx.tcx;
+        tcx.all_local_trait_impls(()).iter().any(|(&trait_id, impls)| {
+            impls.iter().any(|&impl_id| {
+                tcx.type_of(impl_id).ty_adt_def().map(|adt| adt.did()) == Some(def_id)
+            }) && tcx.associated_items(trait_id).in_definition_order().any(|item| item.name == name)
+        })
+    }
+}
+
+fn is_mod_sep(tree: Option<&TokenTree>) -> bool {
+    matches!(tree, Some(TokenTree::Token(token::Token { kind: TokenKind::ModSep, .. }, _)))
+}
+
+impl<'a, 'tcx> DocVisitor for PathChecker<'a, 'tcx> {
+    fn visit_item(&mut self, item: &clean::Item) {
+        if let Some(dox) = &item.attrs.collapsed_doc_value() {
+            let sp = item.attr_span(self.cx.tcx);
+            let extra = crate::html::markdown::ExtraInfo::new_did(
+                self.cx.tcx,
+                item.item_id.expect_def_id(),
+                sp,
+            );
+            let is_unchecked = |lang_string: &markdown::LangString| {
+                (lang_string.rust && lang_string.ignore == markdown::Ignore::All)
+                    || lang_string.is_text()
+            };
+            for code_block in markdown::code_blocks(dox, &extra, is_unchecked) {
+                if is_unchecked(&code_block.lang_string) {
+                    self.check_paths(item, dox, code_block);
+                }
+            }
+        }
+
+        self.visit_item_recur(item)
+    }
+}
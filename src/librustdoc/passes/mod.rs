@@ -39,7 +39,10 @@
 mod check_code_block_syntax;
 pub(crate) use self::check_code_block_syntax::CHECK_CODE_BLOCK_SYNTAX;
 
-mod calculate_doc_coverage;
+mod check_code_block_paths;
+pub(crate) use self::check_code_block_paths::CHECK_CODE_BLOCK_PATHS;
+
+pub(crate) mod calculate_doc_coverage;
 pub(crate) use self::calculate_doc_coverage::CALCULATE_DOC_COVERAGE;
 
 mod html_tags;
@@ -83,6 +86,7 @@ pub(crate) enum Condition {
     PROPAGATE_DOC_CFG,
     COLLECT_INTRA_DOC_LINKS,
     CHECK_CODE_BLOCK_SYNTAX,
+    CHECK_CODE_BLOCK_PATHS,
     COLLECT_TRAIT_IMPLS,
     CALCULATE_DOC_COVERAGE,
     CHECK_INVALID_HTML_TAGS,
@@ -98,6 +102,7 @@ pub(crate) enum Condition {
     ConditionalPass::new(STRIP_PRIV_IMPORTS, WhenDocumentPrivate),
     ConditionalPass::always(COLLECT_INTRA_DOC_LINKS),
     ConditionalPass::always(CHECK_CODE_BLOCK_SYNTAX),
+    ConditionalPass::always(CHECK_CODE_BLOCK_PATHS),
     ConditionalPass::always(CHECK_INVALID_HTML_TAGS),
     ConditionalPass::always(PROPAGATE_DOC_CFG),
     ConditionalPass::always(CHECK_BARE_URLS),
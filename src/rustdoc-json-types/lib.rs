@@ -9,7 +9,7 @@
 use serde::{Deserialize, Serialize};
 
 /// rustdoc format-version.
-pub const FORMAT_VERSION: u32 = 22;
+pub const FORMAT_VERSION: u32 = 23;
 
 /// A `Crate` is the root of the emitted JSON blob. It contains all type/documentation information
 /// about the language items in the local crate, as well as info about external items to allow
@@ -657,6 +657,26 @@ pub struct Impl {
     pub negative: bool,
     pub synthetic: bool,
     pub blanket_impl: Option<Type>,
+    /// Where this impl comes from, if it wasn't written out by hand.
+    pub provenance: Option<ImplProvenance>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImplProvenance {
+    /// The impl was generated by `#[derive(...)]`.
+    Derive {
+        /// The name of the derive macro, e.g. `Clone`.
+        macro_name: String,
+    },
+    /// A blanket impl, such as `impl<T: Display> ToString for T`.
+    Blanket {
+        /// The crate which defines the blanket impl.
+        crate_id: u32,
+        /// The bounds the documented type must satisfy for the impl to apply,
+        /// e.g. `MyType: Display`.
+        bounds: Vec<WherePredicate>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
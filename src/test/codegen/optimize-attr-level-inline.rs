@@ -0,0 +1,57 @@
+// Functions meant to be inlined keep being optimized with their callers when the crate is
+// unoptimized but the module is optimized for a function with `#[optimize(level = "...")]`.
+//
+// The order of the attribute groups isn't known, so the `ATTRS` revision checks their contents
+// and the `NO-OPTNONE` revision checks that no group of an inlined function is `optnone` up to
+// the end of the module.
+// revisions: ATTRS NO-OPTNONE
+// compile-flags: -Copt-level=0 -Ccodegen-units=1
+
+#![feature(optimize_attribute)]
+#![crate_type = "rlib"]
+
+// CHECK-LABEL: define{{.*}}i32 @hot
+#[optimize(level = "3")]
+#[no_mangle]
+pub fn hot(x: i32) -> i32 {
+    plain(x) + hinted(x) + always(x) + generic_hinted(x)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @plain
+// CHECK-SAME: [[PLAIN_ATTRS:#[0-9]+]]
+#[no_mangle]
+pub fn plain(x: i32) -> i32 {
+    x.wrapping_mul(4)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @hinted
+// CHECK-SAME: [[HINTED_ATTRS:#[0-9]+]]
+#[inline]
+#[no_mangle]
+pub fn hinted(x: i32) -> i32 {
+    x.wrapping_mul(4)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @always
+// CHECK-SAME: [[ALWAYS_ATTRS:#[0-9]+]]
+#[inline(always)]
+#[no_mangle]
+pub fn always(x: i32) -> i32 {
+    x.wrapping_mul(4)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @{{.*}}generic_hinted
+// CHECK-SAME: [[GENERIC_ATTRS:#[0-9]+]]
+#[inline]
+pub fn generic_hinted<T: Copy>(x: T) -> T {
+    x
+}
+
+// ATTRS-DAG: attributes [[PLAIN_ATTRS]] = {{.*}}noinline{{.*}}optnone
+// ATTRS-DAG: attributes [[HINTED_ATTRS]] = {{.*}}inlinehint
+// ATTRS-DAG: attributes [[GENERIC_ATTRS]] = {{.*}}inlinehint
+// ATTRS-DAG: attributes [[ALWAYS_ATTRS]] = {{.*}}alwaysinline
+
+// NO-OPTNONE-NOT: attributes [[HINTED_ATTRS]] = {{.*}}optnone
+// NO-OPTNONE-NOT: attributes [[GENERIC_ATTRS]] = {{.*}}optnone
+// NO-OPTNONE-NOT: attributes [[ALWAYS_ATTRS]] = {{.*}}optnone
@@ -0,0 +1,42 @@
+// revisions: NO-OPT SPEED-OPT
+//[NO-OPT] compile-flags: -Copt-level=0 -Ccodegen-units=1
+//[SPEED-OPT] compile-flags: -Copt-level=3 -Ccodegen-units=1
+
+#![feature(optimize_attribute)]
+#![crate_type = "rlib"]
+
+// Functions without a level keep the optimization level of the crate.
+// CHECK-LABEL: define{{.*}}i32 @nothing
+// NO-OPT-SAME: [[NOTHING_ATTRS:#[0-9]+]]
+#[no_mangle]
+pub fn nothing(x: i32) -> i32 {
+    x.wrapping_mul(4)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @hot
+// CHECK: shl i32 {{.*}}, 2
+#[optimize(level = "3")]
+#[no_mangle]
+pub fn hot(x: i32) -> i32 {
+    x.wrapping_mul(4)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @unoptimized
+// SPEED-OPT-SAME: [[UNOPTIMIZED_ATTRS:#[0-9]+]]
+#[optimize(level = "0")]
+#[no_mangle]
+pub fn unoptimized(x: i32) -> i32 {
+    x.wrapping_mul(4)
+}
+
+// CHECK-LABEL: define{{.*}}i32 @small
+// CHECK-SAME: [[SMALL_ATTRS:#[0-9]+]]
+#[optimize(level = "s")]
+#[no_mangle]
+pub fn small(x: i32) -> i32 {
+    x.wrapping_mul(4)
+}
+
+// NO-OPT-DAG: attributes [[NOTHING_ATTRS]] = {{.*}}noinline{{.*}}optnone
+// SPEED-OPT-DAG: attributes [[UNOPTIMIZED_ATTRS]] = {{.*}}noinline{{.*}}optnone
+// CHECK-DAG: attributes [[SMALL_ATTRS]] = {{.*}}optsize
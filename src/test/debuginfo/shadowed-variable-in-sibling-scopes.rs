@@ -0,0 +1,91 @@
+// min-lldb-version: 310
+// compile-flags:-g
+
+// Checks that a variable shadowed inside a block is only visible while the block runs and that
+// variables with the same name in sibling blocks don't get mixed up.
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command:run
+
+// gdb-command:print x
+// gdb-check:$1 = 1
+// gdb-command:continue
+
+// gdb-command:print x
+// gdb-check:$2 = 2.5
+// gdb-command:continue
+
+// gdb-command:print x
+// gdb-check:$3 = 1
+// gdb-command:continue
+
+// gdb-command:print x
+// gdb-check:$4 = true
+// gdb-command:continue
+
+// gdb-command:print x
+// gdb-check:$5 = 1
+// gdb-command:continue
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:run
+
+// lldb-command:print x
+// lldbg-check:[...]$0 = 1
+// lldbr-check:(i32) x = 1
+// lldb-command:continue
+
+// lldb-command:print x
+// lldbg-check:[...]$1 = 2.5
+// lldbr-check:(f64) x = 2.5
+// lldb-command:continue
+
+// lldb-command:print x
+// lldbg-check:[...]$2 = 1
+// lldbr-check:(i32) x = 1
+// lldb-command:continue
+
+// lldb-command:print x
+// lldbg-check:[...]$3 = true
+// lldbr-check:(bool) x = true
+// lldb-command:continue
+
+// lldb-command:print x
+// lldbg-check:[...]$4 = 1
+// lldbr-check:(i32) x = 1
+// lldb-command:continue
+
+#![feature(omit_gdb_pretty_printer_section)]
+#![omit_gdb_pretty_printer_section]
+
+fn main() {
+    let x = 1;
+
+    zzz(); // #break
+    sentinel();
+
+    {
+        let x = 2.5f64;
+
+        zzz(); // #break
+        sentinel();
+    }
+
+    zzz(); // #break
+    sentinel();
+
+    {
+        let x = true;
+
+        zzz(); // #break
+        sentinel();
+    }
+
+    zzz(); // #break
+    sentinel();
+}
+
+fn zzz() {()}
+fn sentinel() {()}
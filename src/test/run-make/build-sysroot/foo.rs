@@ -0,0 +1,10 @@
+#![crate_type = "rlib"]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+pub fn squares(n: u32) -> Vec<u32> {
+    (0..n).map(|i| i * i).collect()
+}
@@ -0,0 +1,25 @@
+#![crate_type = "lib"]
+
+pub mod a {
+    pub fn f() -> u32 {
+        1
+    }
+}
+
+pub mod b {
+    pub fn f() -> u32 {
+        2
+    }
+}
+
+pub mod c {
+    pub fn f() -> u32 {
+        3
+    }
+}
+
+pub mod d {
+    pub fn f() -> u32 {
+        4
+    }
+}
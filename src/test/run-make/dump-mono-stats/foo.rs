@@ -0,0 +1,7 @@
+pub fn bar<T: Default>() -> T {
+    T::default()
+}
+
+pub fn baz() -> (u8, u16, u32) {
+    (bar(), bar(), bar())
+}
@@ -0,0 +1,8 @@
+#[inline(never)]
+fn answer() -> u32 {
+    42
+}
+
+fn main() {
+    println!("{}", answer());
+}
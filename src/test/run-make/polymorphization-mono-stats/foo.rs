@@ -0,0 +1,13 @@
+pub fn unused<T>() -> u32 {
+    1
+}
+
+pub fn used<T: Default>() -> T {
+    T::default()
+}
+
+pub fn dispatch() -> u32 {
+    let unused = unused::<u8>() + unused::<u16>() + unused::<u32>() + unused::<u64>();
+    let used = used::<u32>() + used::<u64>() as u32;
+    unused + used
+}
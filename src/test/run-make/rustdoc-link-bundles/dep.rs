@@ -0,0 +1,5 @@
+pub struct Dep;
+
+pub trait Greet {
+    fn greet(&self) {}
+}
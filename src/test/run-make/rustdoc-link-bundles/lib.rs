@@ -0,0 +1,26 @@
+extern crate dep;
+
+// @has mycrate/fn.make.html '//a[@href="../dep/struct.Dep.html"]' 'Dep'
+// @!hasraw mycrate/fn.make.html 'rustdoc-bundle'
+// @has dep/struct.Dep.html
+pub fn make() -> dep::Dep {
+    dep::Dep
+}
+
+// @hasraw crates.js '"dep","mycrate"'
+// @hasraw search-index.js '"dep":'
+// @hasraw search-index.js '"mycrate":'
+// @has index.html '//a[@href="dep/index.html"]' 'dep'
+// @has index.html '//a[@href="mycrate/index.html"]' 'mycrate'
+// @hasraw implementors/dep/trait.Greet.js '"dep":[]'
+// @hasraw implementors/dep/trait.Greet.js 'struct.Mine.html'
+// @hasraw implementors/dep/trait.Greet.js './dep/trait.Greet.html'
+// @!hasraw implementors/dep/trait.Greet.js 'rustdoc-bundle'
+pub struct Mine;
+
+#[cfg(not(no_impl))]
+impl dep::Greet for Mine {}
+
+// @has mycrate/struct.Gone.html
+#[cfg(not(no_impl))]
+pub struct Gone;
@@ -0,0 +1,13 @@
+// Checks of the output directory once `lib.rs` was documented again with `--cfg no_impl` and its
+// bundle linked again.
+
+// @has mycrate/struct.Mine.html
+// @!has mycrate/struct.Gone.html
+// @hasraw crates.js '"dep","mycrate"'
+// @hasraw search-index.js '"dep":'
+// @hasraw search-index.js '"mycrate":'
+// @!hasraw search-index.js 'Gone'
+// @has index.html '//a[@href="dep/index.html"]' 'dep'
+// @has index.html '//a[@href="mycrate/index.html"]' 'mycrate'
+// @hasraw implementors/dep/trait.Greet.js '"dep":[]'
+// @!hasraw implementors/dep/trait.Greet.js 'struct.Mine.html'
@@ -0,0 +1,5 @@
+// Checks of the output directory where only the bundle of `lib.rs` is linked, with
+// `--extern-html-root-url dep=https://example.com/`.
+
+// @has mycrate/fn.make.html '//a[@href="https://example.com/dep/struct.Dep.html"]' 'Dep'
+// @!hasraw mycrate/fn.make.html 'rustdoc-bundle'
@@ -0,0 +1,20 @@
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang = "sized"]
+trait Sized {}
+
+pub trait Display {}
+pub trait Greet {}
+
+impl<T: Display> Greet for T {}
+
+impl Display for Foo {}
+
+// @is "$.index[*][?(@.docs=='not generated')].inner.provenance" null
+// @is "$.index[*][?(@.kind=='impl' && @.inner.blanket_impl)].inner.provenance.blanket.crate_id" 0
+// @count "$.index[*][?(@.kind=='impl' && @.inner.blanket_impl)].inner.provenance.blanket.bounds[*]" 1
+pub struct Foo;
+
+/// not generated
+impl Foo {}
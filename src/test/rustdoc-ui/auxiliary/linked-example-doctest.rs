@@ -0,0 +1,4 @@
+fn main() {
+    let greeting: &str = linked_example_doctest::greet();
+    assert_eq!(greeting, "hello");
+}
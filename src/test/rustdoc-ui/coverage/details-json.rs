@@ -0,0 +1,22 @@
+// check-pass
+// compile-flags:-Z unstable-options --output-format json --show-coverage --coverage-details
+
+//! Crate docs
+//!
+//! ```
+//! let x = 1;
+//! ```
+
+/// Documented
+pub trait Foo {
+    /// Documented
+    fn documented();
+    fn undocumented();
+}
+
+/// Documented
+pub enum Bar {
+    /// Documented
+    A,
+    B,
+}
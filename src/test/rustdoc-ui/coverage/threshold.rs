@@ -0,0 +1,10 @@
+// compile-flags:-Z unstable-options --show-coverage --coverage-threshold 50
+// compile-flags:--coverage-threshold foo=100
+
+//! Crate docs
+
+pub mod foo {
+    /// Documented
+    pub struct Documented;
+    pub struct Undocumented;
+}
@@ -0,0 +1,11 @@
+// compile-flags:--test --test-args=--test-threads=1
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// check-pass
+
+#![feature(doc_example)]
+
+#[doc(example = "auxiliary/linked-example-doctest.rs")]
+pub fn greet() -> &'static str {
+    "hello"
+}
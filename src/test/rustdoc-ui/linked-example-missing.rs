@@ -0,0 +1,7 @@
+// normalize-stderr-test: ": [^:]*\(os error \d+\)" -> ": OS ERROR"
+
+#![feature(doc_example)]
+
+#[doc(example = "auxiliary/does-not-exist.rs")]
+//~^ ERROR failed to read file
+pub fn greet() {}
@@ -0,0 +1,8 @@
+// check-pass
+// The lint is allowed by default: `text` blocks which aren't Rust code are common in the
+// documentation of std and the compiler, which is built with warnings denied.
+
+/// ```ignore
+/// unresolved_example_paths_default::missing();
+/// ```
+pub fn present() {}
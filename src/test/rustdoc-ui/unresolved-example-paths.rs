@@ -0,0 +1,41 @@
+#![deny(rustdoc::unresolved_example_paths)]
+
+/// ```ignore
+/// use unresolved_example_paths::{Foo, Bar};
+/// unresolved_example_paths::foo::baz();
+/// unresolved_example_paths::foo::bar();
+/// unresolved_example_paths::Foo::new();
+/// unresolved_example_paths::Foo::renamed();
+/// unresolved_example_paths::Foo::name();
+/// unresolved_example_paths::Foo::clone();
+/// ```
+///
+/// ```text
+/// unresolved_example_paths::Qux
+/// ```
+///
+/// ```text
+/// This is not Rust code, unresolved_example_paths::Nope is not checked here '
+/// ```
+#[derive(Clone)]
+pub struct Foo;
+
+pub trait Named {
+    fn name() -> &'static str;
+}
+
+impl Named for Foo {
+    fn name() -> &'static str {
+        "Foo"
+    }
+}
+
+impl Foo {
+    pub fn new() -> Self {
+        Foo
+    }
+}
+
+pub mod foo {
+    pub fn bar() {}
+}
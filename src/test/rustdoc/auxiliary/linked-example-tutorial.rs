@@ -0,0 +1,8 @@
+// This file is attached to an item of `linked-example.rs`, it is not built on its own.
+
+fn main() {
+    foo::greet();
+    // The attribute is not hidden, unlike lines starting with `# ` in code blocks.
+    # [allow(unused_variables)]
+    let greeting = "hello";
+}
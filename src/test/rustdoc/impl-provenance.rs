@@ -0,0 +1,22 @@
+#![crate_name = "foo"]
+
+use std::fmt;
+
+pub trait Greet {}
+
+impl<T: fmt::Display> Greet for T {}
+
+// @has foo/struct.Foo.html
+// @has - '//*[@id="impl-Clone-for-Foo"]//*[@class="stab provenance"]' \
+//     'Derived via #[derive(Clone)]'
+// @has - '//*[@id="impl-Greet-for-Foo"]//*[@class="stab provenance"]' \
+//     'Blanket impl from foo where Foo: Display'
+// @count - '//*[@id="impl-Display-for-Foo"]//*[@class="stab provenance"]' 0
+#[derive(Clone)]
+pub struct Foo;
+
+impl fmt::Display for Foo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("foo")
+    }
+}
@@ -0,0 +1,22 @@
+#![feature(doc_example)]
+#![crate_name = "foo"]
+#![doc(html_playground_url = "https://example.com/")]
+
+// @has foo/fn.greet.html
+// @has - '//div[@class="linked-example"]/h5' 'Example linked-example-tutorial.rs'
+// @has - '//div[@class="linked-example"]//pre[@class="rust rust-example-rendered"]' \
+//     'foo::greet();'
+// @has - '//div[@class="linked-example"]//pre[@class="rust rust-example-rendered"]' \
+//     '# [allow(unused_variables)]'
+// @has - '//div[@class="linked-example"]//a[@class="test-arrow"]' 'Run'
+// @matches - '//div[@class="linked-example"]//a[@class="test-arrow"]/@href' \
+//     '^https://example.com/\?code=.*foo%3A%3Agreet\(\)%3B.*&edition=2015$'
+/// Says hello.
+#[doc(example = "auxiliary/linked-example-tutorial.rs")]
+pub fn greet() {}
+
+// @has foo/struct.Greeter.html
+// @count - '//div[@class="linked-example"]' 2
+#[doc(example = "auxiliary/linked-example-tutorial.rs")]
+#[doc(example = "auxiliary/linked-example-tutorial.rs")]
+pub struct Greeter;
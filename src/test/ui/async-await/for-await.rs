@@ -0,0 +1,51 @@
+// run-pass
+// edition:2021
+
+#![feature(async_iterator, async_iter_from_iter, async_for_loop)]
+
+use std::async_iter::{self, AsyncIterator};
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+async fn sum(iter: impl AsyncIterator<Item = u32>) -> u32 {
+    let mut total = 0;
+    for await x in iter {
+        total += x;
+    }
+    total
+}
+
+async fn first_even(iter: impl AsyncIterator<Item = u32>) -> Option<u32> {
+    'outer: for await x in iter {
+        if x % 2 != 0 {
+            continue 'outer;
+        }
+        return Some(x);
+    }
+    None
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let waker = Arc::new(NoopWaker).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = Box::pin(fut);
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(block_on(sum(async_iter::from_iter(1..=4))), 10);
+    assert_eq!(block_on(sum(async_iter::from_iter([1, 2, 3]).map(|x| x * 10))), 60);
+    assert_eq!(block_on(first_even(async_iter::from_iter([1, 3, 4, 5]))), Some(4));
+    assert_eq!(block_on(first_even(async_iter::from_iter([1, 3]))), None);
+}
@@ -0,0 +1,11 @@
+#![feature(optimize_attribute)]
+#![crate_type = "lib"]
+
+#[optimize(level = "4")] //~ ERROR E0722
+pub fn four() {}
+
+#[optimize(level = 3)] //~ ERROR E0722
+pub fn not_a_string() {}
+
+#[optimize(level = "z")]
+pub fn smallest() {}
@@ -9,6 +9,7 @@
 use std::io::Error;
 use std::os::linux::process::{ChildExt, CommandExt};
 use std::process::Command;
+use std::time::Duration;
 
 fn has_clone3() -> bool {
     let res = unsafe { libc::syscall(libc::SYS_clone3, 0, 0) };
@@ -53,4 +54,34 @@ fn main() {
         .spawn()
         .unwrap()
         .pidfd().expect_err("pidfd should not have been created");
+
+    let mut child = Command::new("sleep")
+        .arg("1000")
+        .create_pidfd(true)
+        .spawn()
+        .unwrap();
+    let pidfd = child.take_pidfd().expect("failed to obtain pidfd");
+
+    // The pidfd wait APIs need `waitid(P_PIDFD)`, which is newer than `clone3`.
+    match pidfd.try_wait() {
+        Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {
+            child.kill().unwrap();
+            child.wait().unwrap();
+            return;
+        }
+        result => assert!(result.unwrap().is_none(), "child exited early"),
+    }
+
+    assert!(pidfd.wait_timeout(Duration::from_millis(10)).unwrap().is_none());
+    pidfd.kill().unwrap();
+    let status = pidfd.wait_timeout(Duration::from_secs(60)).unwrap().expect("child not killed");
+    assert_eq!(std::os::unix::process::ExitStatusExt::signal(&status), Some(libc::SIGKILL));
+
+    // The child has been reaped through the pidfd, so it can't be signalled anymore.
+    pidfd.kill().expect_err("signalled a reaped process");
+
+    // Nor can the `Child` wait on it again, even though its PID might have been reused.
+    let err = child.try_wait().expect_err("waited on a reaped process");
+    assert_eq!(err.raw_os_error(), Some(libc::ECHILD));
+    child.pidfd().expect_err("pidfd should have been taken");
 }
@@ -0,0 +1,15 @@
+// edition:2021
+
+#![feature(async_iterator)]
+
+use std::async_iter::AsyncIterator;
+
+async fn sum(iter: impl AsyncIterator<Item = u32>) -> u32 {
+    let mut total = 0;
+    for await x in iter { //~ ERROR `for await` loops are experimental
+        total += x;
+    }
+    total
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[doc(example = "examples/tutorial.rs")] //~ ERROR: `#[doc(example)]` is experimental
+pub fn foo() {}
+
+fn main() {}
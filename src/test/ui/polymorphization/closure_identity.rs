@@ -0,0 +1,46 @@
+// build-fail
+// compile-flags:-Zpolymorphize=on
+#![feature(rustc_attrs)]
+
+// This test checks that the generic parameters of a function are considered used when the
+// identity of a closure, which doesn't use them itself, can be observed.
+
+use std::any::{Any, TypeId};
+
+fn type_id_of_val<T: 'static>(_: &T) -> TypeId {
+    TypeId::of::<T>()
+}
+
+// Closure is passed to another function, which could call `TypeId::of` on it.
+#[rustc_polymorphize_error]
+pub fn passed_to_fn<T: 'static>() -> TypeId {
+    let x = || {};
+    //~^ ERROR item has unused generic parameters
+    x();
+    type_id_of_val(&x)
+}
+
+// Closure is unsized to a trait object, whose vtable could be used to call `type_id` on it.
+#[rustc_polymorphize_error]
+pub fn unsized_to_dyn<T: 'static>() -> TypeId {
+    let x = || {};
+    //~^ ERROR item has unused generic parameters
+    x();
+    let any: &dyn Any = &x;
+    any.type_id()
+}
+
+// Calling the closure doesn't observe its identity.
+#[rustc_polymorphize_error]
+pub fn called<T>() -> u32 {
+    //~^ ERROR item has unused generic parameters
+    let x = || 3;
+    //~^ ERROR item has unused generic parameters
+    x()
+}
+
+fn main() {
+    passed_to_fn::<u32>();
+    unsized_to_dyn::<u32>();
+    called::<u32>();
+}
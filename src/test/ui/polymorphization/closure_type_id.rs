@@ -0,0 +1,39 @@
+// run-pass
+// compile-flags:-Zpolymorphize=on
+
+// This test checks that closures which don't use the generic parameters of their parent still
+// have a distinct type for every instantiation of the parent.
+
+use std::any::{type_name, Any, TypeId};
+
+fn type_id_of_val<T: 'static>(_: &T) -> TypeId {
+    TypeId::of::<T>()
+}
+
+fn type_name_of_val<T>(_: &T) -> &'static str {
+    type_name::<T>()
+}
+
+fn closure_type_id<T: 'static>() -> TypeId {
+    let x = || {};
+    type_id_of_val(&x)
+}
+
+fn closure_type_name<T>() -> &'static str {
+    let x = || {};
+    type_name_of_val(&x)
+}
+
+fn boxed_closure<T: 'static>() -> Box<dyn Any> {
+    Box::new(|| {})
+}
+
+fn main() {
+    assert_ne!(closure_type_id::<u8>(), closure_type_id::<u16>());
+    assert_eq!(closure_type_id::<u8>(), closure_type_id::<u8>());
+
+    assert!(closure_type_name::<u8>().contains("closure_type_name"));
+
+    let (a, b) = (boxed_closure::<u8>(), boxed_closure::<u16>());
+    assert_ne!((*a).type_id(), (*b).type_id());
+}
@@ -3,9 +3,9 @@
 
 use rustdoc_json_types::{
     Constant, Crate, DynTrait, Enum, FnDecl, Function, FunctionPointer, GenericArg, GenericArgs,
-    GenericBound, GenericParamDef, Generics, Id, Impl, Import, ItemEnum, Method, Module, OpaqueTy,
-    Path, Primitive, ProcMacro, Static, Struct, StructKind, Term, Trait, TraitAlias, Type,
-    TypeBinding, TypeBindingKind, Typedef, Union, Variant, WherePredicate,
+    GenericBound, GenericParamDef, Generics, Id, Impl, ImplProvenance, Import, ItemEnum, Method,
+    Module, OpaqueTy, Path, Primitive, ProcMacro, Static, Struct, StructKind, Term, Trait,
+    TraitAlias, Type, TypeBinding, TypeBindingKind, Typedef, Union, Variant, WherePredicate,
 };
 
 use crate::{item_kind::Kind, Error, ErrorKind};
@@ -191,6 +191,9 @@ fn check_impl(&mut self, x: &'a Impl) {
         if let Some(blanket_impl) = &x.blanket_impl {
             self.check_type(blanket_impl)
         }
+        if let Some(ImplProvenance::Blanket { bounds, .. }) = &x.provenance {
+            bounds.iter().for_each(|w| self.check_where_predicate(w));
+        }
     }
 
     fn check_typedef(&mut self, x: &'a Typedef) {
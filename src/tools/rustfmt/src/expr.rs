@@ -657,8 +657,8 @@ fn to_control_flow(expr: &ast::Expr, expr_type: ExprType) -> Option<ControlFlow<
                 expr.span,
             ))
         }
-        ast::ExprKind::ForLoop(ref pat, ref cond, ref block, label) => {
-            Some(ControlFlow::new_for(pat, cond, block, label, expr.span))
+        ast::ExprKind::ForLoop(ref pat, ref cond, ref block, label, kind) => {
+            Some(ControlFlow::new_for(pat, cond, block, label, kind, expr.span))
         }
         ast::ExprKind::Loop(ref block, label) => {
             Some(ControlFlow::new_loop(block, label, expr.span))
@@ -745,6 +745,7 @@ fn new_for(
         cond: &'a ast::Expr,
         block: &'a ast::Block,
         label: Option<ast::Label>,
+        kind: ast::ForLoopKind,
         span: Span,
     ) -> ControlFlow<'a> {
         ControlFlow {
@@ -753,7 +754,10 @@ fn new_for(
             else_block: None,
             label,
             pat: Some(pat),
-            keyword: "for",
+            keyword: match kind {
+                ast::ForLoopKind::For => "for",
+                ast::ForLoopKind::ForAwait => "for await",
+            },
             matcher: "",
             connector: " in",
             allow_single_line: false,